@@ -0,0 +1,135 @@
+//! Derive macros for [`futures-signals`](https://docs.rs/futures-signals).
+//!
+//! You should use these through the `derive` feature of `futures-signals`, rather than depending on
+//! this crate directly.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{quote, format_ident};
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, Error};
+
+
+/// Generates a struct which has the same fields, except each field is wrapped in a `Mutable`.
+///
+/// For a struct called `Foo`, the generated struct is called `MutableFoo`, and it has these methods:
+///
+/// * `new(Foo) -> MutableFoo`
+/// * `signal_<field>()`, which returns the `signal_cloned()` of the field.
+/// * `snapshot() -> Foo`, which clones the current values of the fields.
+/// * `apply(Foo)`, which sets all of the fields (inside of a `batch`).
+///
+/// `signal_<field>()` and `snapshot()` can only be called if the fields are `Clone`, but the fields don't
+/// need to be `Clone` to derive `ReactiveStruct`.
+///
+/// It also implements `From<Foo>` for `MutableFoo`.
+///
+/// ```rust
+/// use futures_signals_derive::ReactiveStruct;
+///
+/// #[derive(ReactiveStruct)]
+/// struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// let user = MutableUser::new(User { name: "foo".to_string(), age: 5 });
+///
+/// user.age.set(10);
+///
+/// let age = user.signal_age();
+///
+/// assert_eq!(user.snapshot().age, 10);
+/// ```
+#[proc_macro_derive(ReactiveStruct)]
+pub fn derive_reactive_struct(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match reactive_struct(input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+
+fn reactive_struct(input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => return Err(Error::new(Span::call_site(), "ReactiveStruct only supports structs with named fields")),
+        },
+        _ => return Err(Error::new(Span::call_site(), "ReactiveStruct only supports structs")),
+    };
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let mutable_name = format_ident!("Mutable{}", name);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let field_names: Vec<&Ident> = fields.iter().map(|field| field.ident.as_ref().unwrap()).collect();
+    let field_vis = fields.iter().map(|field| &field.vis);
+    let field_types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let signal_names = field_names.iter().map(|name| format_ident!("signal_{}", name));
+
+    let predicates = where_clause.map(|where_clause| &where_clause.predicates);
+
+    let mutable_doc = format!("A version of [`{}`] where every field is a `Mutable`.", name);
+
+    Ok(quote! {
+        #[doc = #mutable_doc]
+        #vis struct #mutable_name #impl_generics #where_clause {
+            #(#field_vis #field_names: ::futures_signals::signal::Mutable<#field_types>,)*
+        }
+
+        impl #impl_generics #mutable_name #ty_generics #where_clause {
+            #[inline]
+            pub fn new(value: #name #ty_generics) -> Self {
+                Self {
+                    #(#field_names: ::futures_signals::signal::Mutable::new(value.#field_names),)*
+                }
+            }
+
+            // The bounds are higher-ranked so that they are checked when the method is called, rather
+            // than when the struct is defined, otherwise a field which isn't Clone would be an error.
+            #(
+                #[inline]
+                pub fn #signal_names(&self) -> ::futures_signals::signal::MutableSignalCloned<#field_types>
+                    where for<'__signals> #field_types: ::core::clone::Clone {
+                    self.#field_names.signal_cloned()
+                }
+            )*
+
+            /// Returns the current values of all of the fields.
+            pub fn snapshot(&self) -> #name #ty_generics where #(for<'__signals> #field_types: ::core::clone::Clone,)* {
+                #name {
+                    #(#field_names: self.#field_names.get_cloned(),)*
+                }
+            }
+
+            /// Sets all of the fields. The Signals are only woken up once, after all of the fields are set.
+            pub fn apply(&self, value: #name #ty_generics) {
+                ::futures_signals::signal::batch(|| {
+                    #(self.#field_names.set(value.#field_names);)*
+                })
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#name #ty_generics> for #mutable_name #ty_generics #where_clause {
+            #[inline]
+            fn from(value: #name #ty_generics) -> Self {
+                Self::new(value)
+            }
+        }
+
+        impl #impl_generics ::core::fmt::Debug for #mutable_name #ty_generics
+            where #(#field_types: ::core::fmt::Debug,)* #predicates {
+
+            fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                fmt.debug_struct(::core::stringify!(#mutable_name))
+                    #(.field(::core::stringify!(#field_names), &self.#field_names))*
+                    .finish()
+            }
+        }
+    })
+}
@@ -0,0 +1,100 @@
+use std::task::{Poll, Context};
+use futures_util::task::noop_waker_ref;
+use futures_signals::signal::SignalExt;
+use futures_signals_derive::ReactiveStruct;
+
+
+#[derive(Debug, Clone, PartialEq, ReactiveStruct)]
+pub struct User {
+    pub name: String,
+    age: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, ReactiveStruct)]
+struct Wrapper<A> where A: Copy {
+    value: A,
+}
+
+#[derive(Debug, PartialEq)]
+struct NotClone(u32);
+
+#[derive(Debug, ReactiveStruct)]
+struct Handle {
+    id: u32,
+    inner: NotClone,
+}
+
+#[derive(Debug, ReactiveStruct)]
+struct Pair<A, B> {
+    first: A,
+    second: B,
+}
+
+
+#[test]
+fn test_reactive_struct() {
+    let cx = &mut Context::from_waker(noop_waker_ref());
+
+    let user = MutableUser::new(User { name: "foo".to_string(), age: 5 });
+
+    let mut name = user.signal_name();
+    let mut age = user.signal_age();
+
+    assert_eq!(name.poll_change_unpin(cx), Poll::Ready(Some("foo".to_string())));
+    assert_eq!(age.poll_change_unpin(cx), Poll::Ready(Some(5)));
+
+    user.age.set(10);
+    assert_eq!(name.poll_change_unpin(cx), Poll::Pending);
+    assert_eq!(age.poll_change_unpin(cx), Poll::Ready(Some(10)));
+
+    assert_eq!(user.snapshot(), User { name: "foo".to_string(), age: 10 });
+
+    user.apply(User { name: "bar".to_string(), age: 20 });
+    assert_eq!(name.poll_change_unpin(cx), Poll::Ready(Some("bar".to_string())));
+    assert_eq!(age.poll_change_unpin(cx), Poll::Ready(Some(20)));
+
+    assert_eq!(format!("{:?}", user), r#"MutableUser { name: Mutable("bar"), age: Mutable(20) }"#);
+}
+
+
+#[test]
+fn test_generics() {
+    let wrapper: MutableWrapper<u32> = Wrapper { value: 5 }.into();
+
+    wrapper.value.set(10);
+
+    assert_eq!(wrapper.snapshot(), Wrapper { value: 10 });
+}
+
+
+#[test]
+fn test_not_clone() {
+    let cx = &mut Context::from_waker(noop_waker_ref());
+
+    let handle = MutableHandle::new(Handle { id: 1, inner: NotClone(5) });
+
+    let mut id = handle.signal_id();
+    assert_eq!(id.poll_change_unpin(cx), Poll::Ready(Some(1)));
+
+    handle.apply(Handle { id: 2, inner: NotClone(10) });
+    assert_eq!(id.poll_change_unpin(cx), Poll::Ready(Some(2)));
+    assert_eq!(*handle.inner.lock_ref(), NotClone(10));
+}
+
+
+#[test]
+fn test_generic_not_clone() {
+    let cx = &mut Context::from_waker(noop_waker_ref());
+
+    let pair = MutablePair::new(Pair { first: "foo".to_string(), second: NotClone(5) });
+
+    let mut first = pair.signal_first();
+    assert_eq!(first.poll_change_unpin(cx), Poll::Ready(Some("foo".to_string())));
+
+    pair.first.set("bar".to_string());
+    assert_eq!(first.poll_change_unpin(cx), Poll::Ready(Some("bar".to_string())));
+
+    let pair = MutablePair::new(Pair { first: 1, second: 2 });
+    let snapshot = pair.snapshot();
+    assert_eq!((snapshot.first, snapshot.second), (1, 2));
+}
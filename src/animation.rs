@@ -0,0 +1,262 @@
+//! Time-based animations.
+//!
+//! This crate doesn't depend on any particular runtime, so the time is provided by a
+//! `Signal` of timestamps (in milliseconds). In the browser that would usually be a
+//! `Signal` which changes on every `requestAnimationFrame`, otherwise it can be
+//! created from a [`Timer`](../timer/trait.Timer.html) with
+//! [`timestamps`](../timer/fn.timestamps.html).
+
+use core::pin::Pin;
+use core::marker::Unpin;
+use alloc::sync::Arc;
+use core::task::{Poll, Context};
+use crate::signal::{Signal, Mutable, MutableSignal};
+use crate::sync::Mutex;
+
+
+#[derive(Debug)]
+struct AnimationState {
+    value: f64,
+    target: f64,
+    duration: f64,
+    playing: bool,
+    last_time: Option<f64>,
+}
+
+impl AnimationState {
+    #[inline]
+    fn is_animating(&self) -> bool {
+        self.playing && self.value != self.target
+    }
+
+    fn tick(&mut self, now: f64) {
+        if self.is_animating() {
+            if self.duration <= 0.0 {
+                self.value = self.target;
+
+            } else if let Some(last_time) = self.last_time {
+                if now > last_time {
+                    let amount = (now - last_time) / self.duration;
+
+                    if self.value < self.target {
+                        self.value = (self.value + amount).min(self.target);
+
+                    } else {
+                        self.value = (self.value - amount).max(self.target);
+                    }
+                }
+            }
+        }
+
+        self.last_time = Some(now);
+    }
+}
+
+
+/// A value between `0.0` and `1.0` which moves towards a target over time.
+///
+/// The `duration` is how many milliseconds it takes to go from `0.0` to `1.0`, so
+/// smaller distances take less time.
+///
+/// ```rust
+/// use futures_signals::animation::MutableAnimation;
+/// use futures_signals::signal::Mutable;
+///
+/// let timestamps = Mutable::new(0.0);
+///
+/// let animation = MutableAnimation::new(500.0);
+/// let signal = animation.signal(timestamps.signal());
+///
+/// animation.animate_to(1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MutableAnimation {
+    state: Arc<Mutex<AnimationState>>,
+    changed: Mutable<()>,
+}
+
+impl MutableAnimation {
+    /// Creates a new animation which starts at `0.0`
+    #[inline]
+    pub fn new(duration: f64) -> Self {
+        Self::new_with_initial(duration, 0.0)
+    }
+
+    pub fn new_with_initial(duration: f64, initial: f64) -> Self {
+        let initial = initial.clamp(0.0, 1.0);
+
+        Self {
+            state: Arc::new(Mutex::new(AnimationState {
+                value: initial,
+                target: initial,
+                duration,
+                playing: true,
+                last_time: None,
+            })),
+            changed: Mutable::new(()),
+        }
+    }
+
+    #[inline]
+    fn notify(&self) {
+        self.changed.set(());
+    }
+
+    /// Returns the current value, this only changes while a signal is being polled
+    #[inline]
+    pub fn current(&self) -> f64 {
+        self.state.lock().value
+    }
+
+    /// Starts animating from the current value towards `target`
+    pub fn animate_to(&self, target: f64) {
+        let target = target.clamp(0.0, 1.0);
+
+        let mut state = self.state.lock();
+
+        if state.target != target {
+            // If it isn't already animating then the next timestamp is the start time
+            if !state.is_animating() {
+                state.last_time = None;
+            }
+
+            state.target = target;
+            drop(state);
+            self.notify();
+        }
+    }
+
+    /// Immediately sets the value, without animating
+    pub fn jump_to(&self, value: f64) {
+        let value = value.clamp(0.0, 1.0);
+
+        let mut state = self.state.lock();
+
+        if state.value != value || state.target != value {
+            state.value = value;
+            state.target = value;
+            drop(state);
+            self.notify();
+        }
+    }
+
+    /// Changes how long it takes to go from `0.0` to `1.0`, this also affects
+    /// the animation which is currently running
+    pub fn set_duration(&self, duration: f64) {
+        let mut state = self.state.lock();
+
+        if state.duration != duration {
+            state.duration = duration;
+            drop(state);
+            self.notify();
+        }
+    }
+
+    pub fn pause(&self) {
+        let mut state = self.state.lock();
+
+        if state.playing {
+            state.playing = false;
+            drop(state);
+            self.notify();
+        }
+    }
+
+    pub fn play(&self) {
+        let mut state = self.state.lock();
+
+        if !state.playing {
+            state.playing = true;
+            // The time spent paused shouldn't count
+            state.last_time = None;
+            drop(state);
+            self.notify();
+        }
+    }
+
+    /// Creates a `Signal` which contains the current value of the animation.
+    ///
+    /// While the animation is running, the value is updated whenever `timestamps` changes.
+    #[inline]
+    pub fn signal<A>(&self, timestamps: A) -> AnimationSignal<A> where A: Signal<Item = f64> {
+        AnimationSignal {
+            state: self.state.clone(),
+            changed: Some(self.changed.signal()),
+            timestamps: Some(timestamps),
+            value: None,
+        }
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct AnimationSignal<A> {
+    state: Arc<Mutex<AnimationState>>,
+    changed: Option<MutableSignal<()>>,
+    timestamps: Option<A>,
+    value: Option<f64>,
+}
+
+impl<A> Unpin for AnimationSignal<A> where A: Unpin {}
+
+impl<A> Signal for AnimationSignal<A> where A: Signal<Item = f64> {
+    type Item = f64;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            mut state,
+            mut changed,
+            pin timestamps,
+            mut value,
+        });
+
+        // This is only used to wake up the task, the changes are stored in the state
+        loop {
+            match changed.as_mut().map(|changed| Pin::new(changed).poll_change(cx)) {
+                Some(Poll::Ready(Some(()))) => continue,
+                Some(Poll::Ready(None)) => {
+                    *changed = None;
+                },
+                Some(Poll::Pending) | None => {},
+            }
+
+            break;
+        }
+
+        let mut state = state.lock();
+
+        // The timestamps are only polled while animating, so that it doesn't wake up the task when idle
+        while state.is_animating() {
+            match timestamps.as_mut().as_pin_mut().map(|timestamps| timestamps.poll_change(cx)) {
+                Some(Poll::Ready(Some(now))) => {
+                    state.tick(now);
+                },
+                Some(Poll::Ready(None)) => {
+                    timestamps.set(None);
+                    break;
+                },
+                Some(Poll::Pending) | None => {
+                    break;
+                },
+            }
+        }
+
+        let new_value = state.value;
+
+        let done = changed.is_none() && (timestamps.is_none() || !state.is_animating());
+
+        drop(state);
+
+        if *value != Some(new_value) {
+            *value = Some(new_value);
+            Poll::Ready(Some(new_value))
+
+        } else if done {
+            Poll::Ready(None)
+
+        } else {
+            Poll::Pending
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use std::pin::Pin;
 use std::marker::Unpin;
-// TODO use parking_lot ?
-use std::sync::{Arc, Weak, Mutex};
+use std::sync::{Arc, Weak};
+use crate::sync::Mutex;
 use std::future::Future;
 use std::task::{Poll, Waker, Context};
 // TODO use parking_lot ?
@@ -24,10 +24,10 @@ pub struct CancelableFutureHandle {
 impl Discard for CancelableFutureHandle {
     fn discard(self) {
         if let Some(state) = self.state.upgrade() {
-            let mut lock = state.waker.lock().unwrap();
+            let mut lock = state.waker.lock();
 
-            // TODO verify that this is correct
-            state.is_cancelled.store(true, Ordering::SeqCst);
+            // This is read without the lock, so it needs Release (see crate::sync)
+            state.is_cancelled.store(true, Ordering::Release);
 
             if let Some(waker) = lock.take() {
                 drop(lock);
@@ -63,7 +63,7 @@ impl<A, B> Future for CancelableFuture<A, B>
         });
 
         // TODO is this correct ?
-        if state.is_cancelled.load(Ordering::SeqCst) {
+        if state.is_cancelled.load(Ordering::Acquire) {
             // This is necessary in order to prevent the future from calling `waker.wake()` later
             future.set(None);
             let callback = when_cancelled.take().unwrap();
@@ -74,7 +74,7 @@ impl<A, B> Future for CancelableFuture<A, B>
             match future.as_pin_mut().unwrap().poll(cx) {
                 Poll::Pending => {
                     // TODO is this correct ?
-                    *state.waker.lock().unwrap() = Some(cx.waker().clone());
+                    *state.waker.lock() = Some(cx.waker().clone());
                     Poll::Pending
                 },
                 a => a,
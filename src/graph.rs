@@ -0,0 +1,117 @@
+//! Introspection of the live Signal dependency graph, for debugging.
+//!
+//! This is only available with the `graph` feature.
+//!
+//! `Mutable`s and `Broadcaster`s automatically register themselves as nodes,
+//! and any `Signal` can be added as a node with
+//! [`SignalExt::named`](../signal/trait.SignalExt.html#method.named).
+//!
+//! The edges are discovered while polling: when a node is polled by another node,
+//! an edge is added from the polled node to the node which polled it. That means
+//! the graph only contains the edges for Signals which have been polled at least once.
+//!
+//! When a node is dropped it is removed from the graph, along with all of its edges.
+
+use std::fmt::Write;
+use std::cell::Cell;
+use std::sync::Mutex;
+use std::collections::{BTreeMap, BTreeSet};
+use crate::sync::recover;
+use std::string::String;
+
+
+#[derive(Debug)]
+struct Registry {
+    next_id: usize,
+    nodes: BTreeMap<usize, String>,
+    edges: BTreeSet<(usize, usize)>,
+}
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+    next_id: 0,
+    nodes: BTreeMap::new(),
+    edges: BTreeSet::new(),
+});
+
+std::thread_local! {
+    // The node which is currently being polled on this thread
+    static CURRENT: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+
+// A node in the graph, it is removed from the graph when it is dropped
+#[derive(Debug)]
+pub(crate) struct Node {
+    id: usize,
+}
+
+impl Node {
+    pub(crate) fn new(name: String) -> Self {
+        let mut lock = recover(REGISTRY.lock());
+
+        let id = lock.next_id;
+        lock.next_id += 1;
+        lock.nodes.insert(id, name);
+
+        Self { id }
+    }
+
+    // Adds an edge to the node which is currently being polled (if any)
+    pub(crate) fn polled(&self) {
+        if let Some(parent) = CURRENT.with(|current| current.get()) {
+            if parent != self.id {
+                recover(REGISTRY.lock()).edges.insert((self.id, parent));
+            }
+        }
+    }
+
+    // Runs `f` with this node as the current node, so anything polled inside of `f` gets an edge to this node
+    pub(crate) fn poll<A, F>(&self, f: F) -> A where F: FnOnce() -> A {
+        struct Restore(Option<usize>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| current.set(self.0));
+            }
+        }
+
+        let _restore = Restore(CURRENT.with(|current| current.replace(Some(self.id))));
+
+        f()
+    }
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        let mut lock = recover(REGISTRY.lock());
+        let id = self.id;
+        lock.nodes.remove(&id);
+        lock.edges.retain(|&(from, to)| from != id && to != id);
+    }
+}
+
+
+fn escape(name: &str) -> String {
+    name.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Returns a [Graphviz](https://graphviz.org/) DOT description of all of the
+/// nodes which currently exist, and the edges between them.
+///
+/// The edges point from a node to the nodes which depend on it.
+pub fn dump_dot() -> String {
+    let lock = recover(REGISTRY.lock());
+
+    let mut output = String::from("digraph signals {\n");
+
+    for (id, name) in lock.nodes.iter() {
+        writeln!(output, "    node{} [label=\"{}\"];", id, escape(name)).unwrap();
+    }
+
+    for (from, to) in lock.edges.iter() {
+        writeln!(output, "    node{} -> node{};", from, to).unwrap();
+    }
+
+    output.push_str("}\n");
+    output
+}
@@ -1,19 +1,53 @@
 use super::signal::Signal;
-use std::pin::Pin;
-use std::marker::Unpin;
+use core::pin::Pin;
+use core::marker::Unpin;
+use core::task::{Poll, Context};
+use alloc::sync::Arc;
 // TODO use parking_lot ?
-use std::sync::{Arc, RwLock, Mutex, MutexGuard, RwLockReadGuard};
-use std::task::{Poll, Context};
+#[cfg(feature = "std")]
+use std::sync::{RwLock, Mutex, MutexGuard, RwLockReadGuard, RwLockWriteGuard};
+#[cfg(feature = "std")]
+use crate::sync::recover;
+#[cfg(feature = "test-util")]
+use alloc::{vec, vec::Vec, boxed::Box};
+#[cfg(not(feature = "std"))]
+use spin::{RwLock, Mutex, MutexGuard, RwLockReadGuard, RwLockWriteGuard};
 
 
+#[cfg(feature = "std")]
 #[inline]
-pub fn lock_mut<A>(x: &Mutex<A>) -> MutexGuard<A> {
-    x.lock().unwrap()
+pub fn lock_mut<A>(x: &Mutex<A>) -> MutexGuard<'_, A> {
+    recover(x.lock())
 }
 
+#[cfg(not(feature = "std"))]
 #[inline]
-pub fn lock_ref<A>(x: &RwLock<A>) -> RwLockReadGuard<A> {
-    x.read().unwrap()
+pub fn lock_mut<A>(x: &Mutex<A>) -> MutexGuard<'_, A> {
+    x.lock()
+}
+
+#[cfg(feature = "std")]
+#[inline]
+pub fn lock_ref<A>(x: &RwLock<A>) -> RwLockReadGuard<'_, A> {
+    recover(x.read())
+}
+
+#[cfg(not(feature = "std"))]
+#[inline]
+pub fn lock_ref<A>(x: &RwLock<A>) -> RwLockReadGuard<'_, A> {
+    x.read()
+}
+
+#[cfg(feature = "std")]
+#[inline]
+fn lock_write<A>(x: &RwLock<A>) -> RwLockWriteGuard<'_, A> {
+    recover(x.write())
+}
+
+#[cfg(not(feature = "std"))]
+#[inline]
+fn lock_write<A>(x: &RwLock<A>) -> RwLockWriteGuard<'_, A> {
+    x.write()
 }
 
 pub fn unwrap_mut<A>(x: &mut Option<A>) -> &mut A {
@@ -38,7 +72,7 @@ macro_rules! unsafe_project {
     (@parse $value:expr,) => {};
     (@parse $value:expr, pin $name:ident, $($rest:tt)*) => {
         #[allow(unused_mut)]
-        let mut $name = unsafe { ::std::pin::Pin::new_unchecked(&mut $value.$name) };
+        let mut $name = unsafe { ::core::pin::Pin::new_unchecked(&mut $value.$name) };
         $crate::unsafe_project! { @parse $value, $($rest)* }
     };
     (@parse $value:expr, mut $name:ident, $($rest:tt)*) => {
@@ -48,7 +82,7 @@ macro_rules! unsafe_project {
     };
 
     ($value:expr => { $($bindings:tt)+ }) => {
-        let value = unsafe { ::std::pin::Pin::get_unchecked_mut($value) };
+        let value = unsafe { ::core::pin::Pin::get_unchecked_mut($value) };
         $crate::unsafe_project! { @parse value, $($bindings)+ }
     };
 }
@@ -148,7 +182,11 @@ impl<A, B, C, D> Signal for Map2<A, B, C>
             Some(Poll::Pending) => false,
         };
 
-        if changed {
+        // If a Signal ended without a value then there will never be an output
+        if (left_done && left.is_none()) || (right_done && right.is_none()) {
+            Poll::Ready(None)
+
+        } else if changed && left.is_some() && right.is_some() {
             Poll::Ready(Some(callback(
                 left.as_mut().unwrap(),
                 right.as_mut().unwrap(),
@@ -207,10 +245,10 @@ impl<A, B> Signal for MapPairMut<A, B>
         let mut changed = false;
 
         // TODO can this deadlock ?
-        let mut borrow_left = inner.0.lock().unwrap();
+        let mut borrow_left = lock_mut(&inner.0);
 
         // TODO is it okay to move this to just above right_done ?
-        let mut borrow_right = inner.1.lock().unwrap();
+        let mut borrow_right = lock_mut(&inner.1);
 
         let left_done = match signal1.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
             None => true,
@@ -295,7 +333,7 @@ impl<A, B> Signal for MapPair<A, B>
 
         let mut changed = false;
 
-        let mut borrow = inner.write().unwrap();
+        let mut borrow = lock_write(inner);
 
         let left_done = match signal1.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
             None => true,
@@ -336,3 +374,26 @@ impl<A, B> Signal for MapPair<A, B>
         }
     }
 }
+
+
+// Used by the `assert_signal_eq!` macro
+#[cfg(feature = "test-util")]
+pub fn get_signal_polls<A>(signal: A, count: usize) -> Vec<Poll<Option<A::Item>>> where A: Signal {
+    let mut signal = Box::pin(signal);
+    let cx = &mut Context::from_waker(futures_util::task::noop_waker_ref());
+
+    let mut output = vec![];
+
+    while output.len() < count {
+        let poll = signal.as_mut().poll_change(cx);
+        let done = matches!(poll, Poll::Ready(None));
+
+        output.push(poll);
+
+        if done {
+            break;
+        }
+    }
+
+    output
+}
@@ -1,10 +1,37 @@
+#![no_std]
 #![recursion_limit="128"]
 #![warn(unreachable_pub)]
 // missing_docs
 #![deny(warnings, missing_debug_implementations, macro_use_extern_crate)]
 
-///! It is *very highly* recommended to read the tutorial.
-///! It explains all of the concepts you will need to use Signals effectively.
+//! It is *very highly* recommended to read the tutorial.
+//! It explains all of the concepts you will need to use Signals effectively.
+//!
+//! # `no_std`
+//!
+//! This crate can be used without `std` (it still needs `alloc`) by disabling the default
+//! `std` feature and enabling the `spin` feature, which uses spinlocks instead of the
+//! `std` locks:
+//!
+//! ```toml
+//! futures-signals = { version = "0.3", default-features = false, features = ["spin"] }
+//! ```
+//!
+//! On targets which don't have native atomics (or 64-bit atomics), also enable the
+//! `portable-atomic` feature. The target still needs to support `alloc::sync::Arc`.
+//!
+//! Without `std` the following are not available, because they use `std` channels:
+//! `MutableVec`, `MutableBTreeMap`, `MutableHashMap`, `MutableSet`, and the `SignalVec` /
+//! `SignalMap` broadcasters. `cancelable_future` is also not available, and
+//! [`batch`](signal/fn.batch.html) doesn't delay the wakeups.
+
+#[cfg(not(any(feature = "std", feature = "spin")))]
+compile_error!("futures-signals needs either the `std` feature or the `spin` feature");
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
 
 #[cfg(test)]
 extern crate futures_executor;
@@ -17,10 +44,38 @@ pub mod internal;
 pub mod signal;
 pub mod signal_vec;
 pub mod signal_map;
+pub mod signal_set;
+pub mod animation;
+pub mod timer;
+
+#[cfg(feature = "graph")]
+pub mod graph;
+
+#[cfg(feature = "test-util")]
+pub mod test_util;
+
+#[cfg(loom)]
+pub mod loom_util;
 
+#[cfg(feature = "persist")]
+pub mod persist;
+
+#[cfg(feature = "replication")]
+pub mod replication;
+
+#[cfg(feature = "wasm")]
+pub mod spawn;
+
+mod sync;
+
+#[cfg(feature = "std")]
 mod future;
+#[cfg(feature = "std")]
 pub use crate::future::{cancelable_future, CancelableFutureHandle, CancelableFuture};
 
+#[cfg(feature = "derive")]
+pub use futures_signals_derive::ReactiveStruct;
+
 
 /// # Tutorial
 ///
@@ -473,4 +528,6 @@ pub use crate::future::{cancelable_future, CancelableFutureHandle, CancelableFut
 /// And that's the end of the tutorial! We didn't cover every method, but we covered enough for you to get started.
 ///
 /// You can look at the documentation for information on every method (there's a lot of useful stuff in there!).
+// The tutorial uses MutableVec, which needs std
+#[cfg(feature = "std")]
 pub mod tutorial {}
@@ -0,0 +1,56 @@
+//! Model checking Signals with [`loom`](https://docs.rs/loom).
+//!
+//! This is only available when compiling with `RUSTFLAGS="--cfg loom"`. In that case the locks and
+//! atomics inside of `Mutable` and `Broadcaster` use loom, so loom can check every possible interleaving
+//! of `set` and `poll_change` across threads.
+//!
+//! Everything which uses a `Mutable` (including creating it) must happen inside of [`model`](fn.model.html).
+//!
+//! ```rust,ignore
+//! use futures_signals::loom_util::{model, thread, collect};
+//! use futures_signals::signal::Mutable;
+//!
+//! model(|| {
+//!     let mutable = Mutable::new(0);
+//!     let signal = mutable.signal();
+//!
+//!     let handle = thread::spawn(move || mutable.set(1));
+//!
+//!     // If a change was missed this would deadlock, and loom would report it
+//!     let values = collect(signal);
+//!     assert_eq!(values.last(), Some(&1));
+//!
+//!     handle.join().unwrap();
+//! });
+//! ```
+//!
+//! Run the tests with `RUSTFLAGS="--cfg loom" cargo test --release`.
+
+use std::future::Future;
+use std::vec::Vec;
+use futures_util::stream::StreamExt;
+use crate::signal::{Signal, SignalExt};
+
+pub use loom::thread;
+
+
+/// Runs `f` with every possible interleaving of its threads.
+///
+/// This is the same as `loom::model`.
+#[inline]
+pub fn model<F>(f: F) where F: Fn() + Sync + Send + 'static {
+    loom::model(f)
+}
+
+/// Runs `future` on the current loom thread until it is finished.
+///
+/// The `Waker` is loom-aware, so a missing wakeup is reported as a deadlock.
+#[inline]
+pub fn block_on<F>(future: F) -> F::Output where F: Future {
+    loom::future::block_on(future)
+}
+
+/// Returns all of the values of `signal`, waiting until the `Signal` ends.
+pub fn collect<A>(signal: A) -> Vec<A::Item> where A: Signal {
+    block_on(signal.to_stream().collect())
+}
@@ -0,0 +1,163 @@
+//! Automatically saving a `Mutable` to storage.
+//!
+//! This is only available with the `persist` feature.
+
+use std::fmt;
+use std::ops::Deref;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::future::Future;
+use std::time::Duration;
+use futures_util::future::{select, Either};
+use futures_util::stream::StreamExt;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use crate::signal::{Mutable, SignalExt};
+use crate::timer::Timer;
+use std::boxed::Box;
+use std::vec::Vec;
+
+
+/// Somewhere to load and save bytes, such as a file or the browser's local storage.
+pub trait Storage {
+    /// Returns the saved bytes, or `None` if nothing has been saved yet.
+    fn load(&self) -> Option<Vec<u8>>;
+
+    fn save(&self, bytes: &[u8]);
+}
+
+impl<A> Storage for &A where A: ?Sized + Storage {
+    #[inline]
+    fn load(&self) -> Option<Vec<u8>> {
+        A::load(self)
+    }
+
+    #[inline]
+    fn save(&self, bytes: &[u8]) {
+        A::save(self, bytes)
+    }
+}
+
+impl<A> Storage for Box<A> where A: ?Sized + Storage {
+    #[inline]
+    fn load(&self) -> Option<Vec<u8>> {
+        A::load(self)
+    }
+
+    #[inline]
+    fn save(&self, bytes: &[u8]) {
+        A::save(self, bytes)
+    }
+}
+
+impl<A> Storage for Rc<A> where A: ?Sized + Storage {
+    #[inline]
+    fn load(&self) -> Option<Vec<u8>> {
+        A::load(self)
+    }
+
+    #[inline]
+    fn save(&self, bytes: &[u8]) {
+        A::save(self, bytes)
+    }
+}
+
+impl<A> Storage for Arc<A> where A: ?Sized + Storage {
+    #[inline]
+    fn load(&self) -> Option<Vec<u8>> {
+        A::load(self)
+    }
+
+    #[inline]
+    fn save(&self, bytes: &[u8]) {
+        A::save(self, bytes)
+    }
+}
+
+
+/// A `Mutable` which is loaded from a [`Storage`](trait.Storage.html), and which is saved
+/// to the `Storage` whenever it changes.
+///
+/// The value is saved as JSON.
+pub struct Persisted<A> {
+    mutable: Mutable<A>,
+}
+
+impl<A> Persisted<A> where A: Serialize + DeserializeOwned {
+    /// Loads the value from `storage`. If nothing was saved (or if the saved value is invalid)
+    /// then `default` is used instead.
+    ///
+    /// This returns a `Future` which saves the value. It must be spawned. Whenever the value
+    /// changes, it waits until the value hasn't changed for `debounce`, and then it saves the value.
+    ///
+    /// The `Future` finishes when the `Persisted` is dropped. If there is a change which hasn't
+    /// been saved yet, it is saved immediately.
+    pub fn new<S, T, F>(storage: S, timer: T, debounce: Duration, default: F) -> (Self, impl Future<Output = ()>)
+        where S: Storage,
+              T: Timer,
+              F: FnOnce() -> A {
+
+        let value = storage.load()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_else(default);
+
+        let mutable = Mutable::new(value);
+
+        let future = {
+            let read_only = mutable.read_only();
+
+            async move {
+                let save = || {
+                    // TODO report the error somehow ?
+                    if let Ok(bytes) = serde_json::to_vec(&*read_only.lock_ref()) {
+                        storage.save(&bytes);
+                    }
+                };
+
+                let mut changes = read_only.signal_ref(|_| ()).to_stream();
+
+                // The initial value doesn't need to be saved
+                changes.next().await;
+
+                while let Some(()) = changes.next().await {
+                    // Wait until there aren't any changes for `debounce`
+                    loop {
+                        let sleep = Box::pin(timer.sleep(debounce));
+
+                        match select(changes.next(), sleep).await {
+                            Either::Left((Some(()), _)) => {},
+                            Either::Left((None, _)) => {
+                                save();
+                                return;
+                            },
+                            Either::Right(((), _)) => {
+                                break;
+                            },
+                        }
+                    }
+
+                    save();
+                }
+            }
+        };
+
+        (Self { mutable }, future)
+    }
+}
+
+impl<A> Deref for Persisted<A> {
+    type Target = Mutable<A>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.mutable
+    }
+}
+
+impl<A> fmt::Debug for Persisted<A> where A: fmt::Debug {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_tuple("Persisted")
+            .field(&self.mutable)
+            .finish()
+    }
+}
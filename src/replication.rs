@@ -0,0 +1,632 @@
+//! Sending Signals to another process, and replicating them on the other side.
+//!
+//! This is only available with the `replication` feature.
+//!
+//! The sending side converts a `Signal`, `SignalVec` or `SignalMap` into a `Stream` of frames
+//! (with [`signal_frames`](fn.signal_frames.html), [`signal_vec_frames`](fn.signal_vec_frames.html),
+//! or [`signal_map_frames`](fn.signal_map_frames.html)). Each frame is a `Vec<u8>` which can be
+//! sent over any byte transport (a socket, a pipe, a WebSocket, etc.)
+//!
+//! The receiving side passes the bytes into a [`SignalReplica`](struct.SignalReplica.html),
+//! [`SignalVecReplica`](struct.SignalVecReplica.html), or [`SignalMapReplica`](struct.SignalMapReplica.html),
+//! which contains a read-only copy of the original.
+//!
+//! The bytes do not need to be split at frame boundaries: the replicas buffer partial frames.
+//!
+//! [`send_frames`](fn.send_frames.html) and [`receive_frames`](fn.receive_frames.html) send the frames over
+//! any `AsyncWrite` / `AsyncRead` (such as a TCP socket), and the `_reconnect` versions reconnect when the
+//! connection is lost.
+//!
+//! # Protocol
+//!
+//! Each frame is a 4 byte big-endian length, followed by that many bytes of JSON.
+//!
+//! The replicas reject frames which are longer than [`DEFAULT_MAX_FRAME_SIZE`](constant.DEFAULT_MAX_FRAME_SIZE.html)
+//! (or the limit which was given to `with_max_frame_size`), without buffering them.
+//!
+//! For a `Signal` the JSON is the value. For a `SignalVec` or `SignalMap` the JSON is a
+//! [`VecDiff`](../signal_vec/enum.VecDiff.html) or [`MapDiff`](../signal_map/enum.MapDiff.html).
+//! The first frame is always the initial snapshot: for a `SignalVec` or `SignalMap` it is a
+//! `Replace`, even if it is empty.
+
+use std::io;
+use std::fmt;
+use std::future::Future;
+use std::convert::TryInto;
+use std::task::{Poll, Context};
+use futures_core::Stream;
+use futures_util::stream::{self, StreamExt};
+use futures_util::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use crate::signal::{Signal, SignalExt, Mutable, ReadOnlyMutable, MutableSignalCloned};
+use crate::signal_vec::{SignalVec, VecDiff, MutableVec, MutableVecLockRef, MutableSignalVec};
+use crate::signal_map::{SignalMap, MapDiff, MutableBTreeMap, MutableBTreeMapLockRef, MutableSignalMap};
+use std::boxed::Box;
+use std::vec;
+use std::vec::Vec;
+
+
+/// The maximum length (in bytes) of a frame, unless a different limit is given to `with_max_frame_size`.
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+
+
+#[derive(Debug)]
+enum ErrorKind {
+    Json(serde_json::Error),
+    FrameTooLarge { len: usize, max: usize },
+    InvalidIndex { index: usize, len: usize },
+}
+
+/// The error which is returned when a replica receives an invalid frame.
+///
+/// A frame is invalid if it isn't valid JSON, if it is longer than the maximum frame size,
+/// or if it is a change which doesn't match the replica (such as removing an index which
+/// doesn't exist).
+#[derive(Debug)]
+pub struct ReplicationError(ErrorKind);
+
+impl fmt::Display for ReplicationError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            ErrorKind::Json(error) => write!(fmt, "invalid replication frame: {}", error),
+            ErrorKind::FrameTooLarge { len, max } => write!(fmt, "replication frame is {} bytes, which is larger than the maximum of {} bytes", len, max),
+            ErrorKind::InvalidIndex { index, len } => write!(fmt, "invalid replication frame: index {} is out of bounds for a length of {}", index, len),
+        }
+    }
+}
+
+impl std::error::Error for ReplicationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.0 {
+            ErrorKind::Json(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+
+/// Encodes `value` as a single frame.
+pub fn encode_frame<A>(value: &A) -> Vec<u8> where A: Serialize {
+    // Serializing into a Vec can only fail if the Serialize impl fails
+    let json = serde_json::to_vec(value).unwrap();
+
+    let len: u32 = json.len().try_into().expect("replication frame is too large");
+
+    let mut frame = Vec::with_capacity(4 + json.len());
+    frame.extend_from_slice(&len.to_be_bytes());
+    frame.extend_from_slice(&json);
+    frame
+}
+
+
+/// Splits bytes into frames.
+///
+/// The bytes can be split anywhere, incomplete frames are buffered until the rest of the bytes arrive.
+#[derive(Debug)]
+pub struct FrameDecoder {
+    buffer: Vec<u8>,
+    max_frame_size: usize,
+    // The number of bytes which still need to be discarded from a frame which was too large
+    skip: usize,
+}
+
+impl FrameDecoder {
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_max_frame_size(DEFAULT_MAX_FRAME_SIZE)
+    }
+
+    /// Creates a `FrameDecoder` which rejects frames which are longer than `max_frame_size` bytes.
+    ///
+    /// A frame which is too large is rejected as soon as its length is received, and the rest of its
+    /// bytes are discarded as they arrive, so it is never buffered.
+    #[inline]
+    pub fn with_max_frame_size(max_frame_size: usize) -> Self {
+        Self {
+            buffer: vec![],
+            max_frame_size,
+            skip: 0,
+        }
+    }
+
+    /// Adds `bytes` to the end of the buffer.
+    #[inline]
+    pub fn push(&mut self, bytes: &[u8]) {
+        let skip = self.skip.min(bytes.len());
+        self.skip -= skip;
+        self.buffer.extend_from_slice(&bytes[skip..]);
+    }
+
+    /// Discards any incomplete frame which is in the buffer.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.skip = 0;
+    }
+
+    /// Removes the next complete frame from the buffer and decodes it.
+    ///
+    /// Returns `None` if there isn't a complete frame yet.
+    pub fn next_frame<A>(&mut self) -> Option<Result<A, ReplicationError>> where A: DeserializeOwned {
+        if self.buffer.len() < 4 {
+            return None;
+        }
+
+        let len = u32::from_be_bytes(self.buffer[0..4].try_into().unwrap()) as usize;
+
+        if len > self.max_frame_size {
+            let available = self.buffer.len().min(4 + len);
+            self.buffer.drain(0..available);
+            self.skip = (4 + len) - available;
+
+            return Some(Err(ReplicationError(ErrorKind::FrameTooLarge { len, max: self.max_frame_size })));
+        }
+
+        if self.buffer.len() < 4 + len {
+            return None;
+        }
+
+        let result = serde_json::from_slice(&self.buffer[4..(4 + len)]).map_err(|error| ReplicationError(ErrorKind::Json(error)));
+
+        self.buffer.drain(0..(4 + len));
+
+        Some(result)
+    }
+
+    fn for_each<A, F>(&mut self, bytes: &[u8], mut f: F) -> Result<(), ReplicationError>
+        where A: DeserializeOwned,
+              F: FnMut(A) -> Result<(), ReplicationError> {
+
+        self.push(bytes);
+
+        while let Some(value) = self.next_frame().transpose()? {
+            f(value)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for FrameDecoder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+/// Converts a `Signal` into a `Stream` of frames, which can be replicated with a [`SignalReplica`](struct.SignalReplica.html).
+#[inline]
+pub fn signal_frames<A>(signal: A) -> impl Stream<Item = Vec<u8>>
+    where A: Signal,
+          A::Item: Serialize {
+    signal.to_stream().map(|value| encode_frame(&value))
+}
+
+// Makes sure that the first change is a snapshot, even if the SignalVec / SignalMap is empty.
+// Otherwise a replica which reconnects to an empty source would keep its old values.
+fn initial_snapshot<A, P>(mut poll: P, is_snapshot: fn(&A) -> bool, empty: A) -> impl Stream<Item = A>
+    where P: FnMut(&mut Context) -> Poll<Option<A>> {
+
+    let mut empty = Some(empty);
+    let mut queued = None;
+
+    stream::poll_fn(move |cx| {
+        if let Some(empty) = empty.take() {
+            match poll(cx) {
+                Poll::Ready(Some(change)) if is_snapshot(&change) => Poll::Ready(Some(change)),
+                Poll::Ready(change) => {
+                    queued = Some(change);
+                    Poll::Ready(Some(empty))
+                },
+                // The waker is registered, so it will be polled again when it changes
+                Poll::Pending => Poll::Ready(Some(empty)),
+            }
+
+        } else if let Some(change) = queued.take() {
+            Poll::Ready(change)
+
+        } else {
+            poll(cx)
+        }
+    })
+}
+
+/// Converts a `SignalVec` into a `Stream` of frames, which can be replicated with a [`SignalVecReplica`](struct.SignalVecReplica.html).
+///
+/// The first frame is always a `VecDiff::Replace`, even if `signal` is empty.
+#[inline]
+pub fn signal_vec_frames<A>(signal: A) -> impl Stream<Item = Vec<u8>>
+    where A: SignalVec,
+          A::Item: Serialize {
+    let mut signal = Box::pin(signal);
+
+    initial_snapshot(
+        move |cx| signal.as_mut().poll_vec_change(cx),
+        |change| matches!(change, VecDiff::Replace { .. }),
+        VecDiff::Replace { values: vec![] },
+    ).map(|change| encode_frame(&change))
+}
+
+/// Converts a `SignalMap` into a `Stream` of frames, which can be replicated with a [`SignalMapReplica`](struct.SignalMapReplica.html).
+///
+/// The first frame is always a `MapDiff::Replace`, even if `signal` is empty.
+#[inline]
+pub fn signal_map_frames<A>(signal: A) -> impl Stream<Item = Vec<u8>>
+    where A: SignalMap,
+          A::Key: Serialize,
+          A::Value: Serialize {
+    let mut signal = Box::pin(signal);
+
+    initial_snapshot(
+        move |cx| signal.as_mut().poll_map_change(cx),
+        |change| matches!(change, MapDiff::Replace { .. }),
+        MapDiff::Replace { entries: vec![] },
+    ).map(|change| encode_frame(&change))
+}
+
+
+/// The replicas which bytes can be received into.
+///
+/// This is used by [`receive_frames`](fn.receive_frames.html) so that it works with every kind of replica.
+pub trait Replica {
+    /// Applies all of the complete frames in `bytes` (and any bytes which were buffered from earlier calls).
+    fn receive(&mut self, bytes: &[u8]) -> Result<(), ReplicationError>;
+
+    /// Discards any bytes which were buffered from earlier calls.
+    ///
+    /// This should be called when the connection is lost, because the next connection starts with a new frame.
+    ///
+    /// The values are kept until the first frame of the next connection replaces them, so the replica
+    /// doesn't briefly become empty while reconnecting.
+    fn reset(&mut self);
+}
+
+
+/// A read-only copy of a `Signal` which was sent with [`signal_frames`](fn.signal_frames.html).
+///
+/// The value is `None` until the first frame is received.
+#[derive(Debug)]
+pub struct SignalReplica<A> {
+    decoder: FrameDecoder,
+    value: Mutable<Option<A>>,
+}
+
+impl<A> SignalReplica<A> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_max_frame_size(DEFAULT_MAX_FRAME_SIZE)
+    }
+
+    /// Creates a replica which rejects frames which are longer than `max_frame_size` bytes.
+    #[inline]
+    pub fn with_max_frame_size(max_frame_size: usize) -> Self {
+        Self {
+            decoder: FrameDecoder::with_max_frame_size(max_frame_size),
+            value: Mutable::new(None),
+        }
+    }
+
+    #[inline]
+    pub fn read_only(&self) -> ReadOnlyMutable<Option<A>> {
+        self.value.read_only()
+    }
+}
+
+impl<A> SignalReplica<A> where A: DeserializeOwned {
+    /// Applies all of the complete frames in `bytes` (and any bytes which were buffered from earlier calls).
+    ///
+    /// If a frame is invalid then it is skipped and an error is returned. The frames before it are still
+    /// applied, and the frames after it are applied by the next call to `receive`.
+    pub fn receive(&mut self, bytes: &[u8]) -> Result<(), ReplicationError> {
+        let value = &self.value;
+        self.decoder.for_each(bytes, |new_value| {
+            value.set(Some(new_value));
+            Ok(())
+        })
+    }
+}
+
+impl<A> Replica for SignalReplica<A> where A: DeserializeOwned {
+    #[inline]
+    fn receive(&mut self, bytes: &[u8]) -> Result<(), ReplicationError> {
+        SignalReplica::receive(self, bytes)
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.decoder.clear();
+    }
+}
+
+impl<A> SignalReplica<A> where A: Clone {
+    #[inline]
+    pub fn signal_cloned(&self) -> MutableSignalCloned<Option<A>> {
+        self.value.signal_cloned()
+    }
+}
+
+impl<A> Default for SignalReplica<A> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+/// A read-only copy of a `SignalVec` which was sent with [`signal_vec_frames`](fn.signal_vec_frames.html).
+#[derive(Debug)]
+pub struct SignalVecReplica<A> {
+    decoder: FrameDecoder,
+    values: MutableVec<A>,
+}
+
+impl<A> SignalVecReplica<A> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_max_frame_size(DEFAULT_MAX_FRAME_SIZE)
+    }
+
+    /// Creates a replica which rejects frames which are longer than `max_frame_size` bytes.
+    #[inline]
+    pub fn with_max_frame_size(max_frame_size: usize) -> Self {
+        Self {
+            decoder: FrameDecoder::with_max_frame_size(max_frame_size),
+            values: MutableVec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn lock_ref(&self) -> MutableVecLockRef<'_, A> {
+        self.values.lock_ref()
+    }
+}
+
+impl<A> SignalVecReplica<A> where A: Clone + DeserializeOwned {
+    /// Applies all of the complete frames in `bytes` (and any bytes which were buffered from earlier calls).
+    ///
+    /// If a frame is invalid then it is skipped and an error is returned. The frames before it are still
+    /// applied, and the frames after it are applied by the next call to `receive`.
+    ///
+    /// A change with an index which is out of bounds is invalid, it doesn't panic.
+    pub fn receive(&mut self, bytes: &[u8]) -> Result<(), ReplicationError> {
+        fn check(index: usize, len: usize) -> Result<(), ReplicationError> {
+            if index < len {
+                Ok(())
+
+            } else {
+                Err(ReplicationError(ErrorKind::InvalidIndex { index, len }))
+            }
+        }
+
+        let values = &self.values;
+
+        self.decoder.for_each(bytes, |change| {
+            let mut lock = values.lock_mut();
+            let len = lock.len();
+
+            match change {
+                VecDiff::Replace { values } => lock.replace_cloned(values),
+                VecDiff::InsertAt { index, value } => {
+                    // It is valid to insert at the end
+                    check(index, len + 1)?;
+                    lock.insert_cloned(index, value);
+                },
+                VecDiff::UpdateAt { index, value } => {
+                    check(index, len)?;
+                    lock.set_cloned(index, value);
+                },
+                VecDiff::RemoveAt { index } => {
+                    check(index, len)?;
+                    lock.remove(index);
+                },
+                VecDiff::Move { old_index, new_index } => {
+                    check(old_index, len)?;
+                    check(new_index, len)?;
+                    lock.move_from_to(old_index, new_index);
+                },
+                VecDiff::Push { value } => lock.push_cloned(value),
+                VecDiff::Pop {} => {
+                    check(0, len)?;
+                    lock.pop();
+                },
+                VecDiff::Clear {} => lock.clear(),
+            }
+
+            Ok(())
+        })
+    }
+}
+
+impl<A> Replica for SignalVecReplica<A> where A: Clone + DeserializeOwned {
+    #[inline]
+    fn receive(&mut self, bytes: &[u8]) -> Result<(), ReplicationError> {
+        SignalVecReplica::receive(self, bytes)
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.decoder.clear();
+    }
+}
+
+impl<A> SignalVecReplica<A> where A: Clone {
+    #[inline]
+    pub fn signal_vec_cloned(&self) -> MutableSignalVec<A> {
+        self.values.signal_vec_cloned()
+    }
+}
+
+impl<A> Default for SignalVecReplica<A> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+/// A read-only copy of a `SignalMap` which was sent with [`signal_map_frames`](fn.signal_map_frames.html).
+#[derive(Debug)]
+pub struct SignalMapReplica<K, V> {
+    decoder: FrameDecoder,
+    values: MutableBTreeMap<K, V>,
+}
+
+impl<K, V> SignalMapReplica<K, V> where K: Ord {
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_max_frame_size(DEFAULT_MAX_FRAME_SIZE)
+    }
+
+    /// Creates a replica which rejects frames which are longer than `max_frame_size` bytes.
+    #[inline]
+    pub fn with_max_frame_size(max_frame_size: usize) -> Self {
+        Self {
+            decoder: FrameDecoder::with_max_frame_size(max_frame_size),
+            values: MutableBTreeMap::new(),
+        }
+    }
+}
+
+impl<K, V> SignalMapReplica<K, V> {
+    #[inline]
+    pub fn lock_ref(&self) -> MutableBTreeMapLockRef<'_, K, V> {
+        self.values.lock_ref()
+    }
+}
+
+impl<K, V> SignalMapReplica<K, V> where K: Ord + Clone + DeserializeOwned, V: Clone + DeserializeOwned {
+    /// Applies all of the complete frames in `bytes` (and any bytes which were buffered from earlier calls).
+    ///
+    /// If a frame is invalid then it is skipped and an error is returned. The frames before it are still
+    /// applied, and the frames after it are applied by the next call to `receive`.
+    pub fn receive(&mut self, bytes: &[u8]) -> Result<(), ReplicationError> {
+        let values = &self.values;
+
+        self.decoder.for_each(bytes, |change| {
+            let mut lock = values.lock_mut();
+
+            match change {
+                MapDiff::Replace { entries } => lock.replace_cloned(entries.into_iter().collect()),
+                MapDiff::Insert { key, value } | MapDiff::Update { key, value } => { lock.insert_cloned(key, value); },
+                MapDiff::Remove { key } => { lock.remove(&key); },
+                MapDiff::Clear {} => lock.clear(),
+            }
+
+            Ok(())
+        })
+    }
+
+    #[inline]
+    pub fn signal_map_cloned(&self) -> MutableSignalMap<K, V> {
+        self.values.signal_map_cloned()
+    }
+}
+
+impl<K, V> Replica for SignalMapReplica<K, V> where K: Ord + Clone + DeserializeOwned, V: Clone + DeserializeOwned {
+    #[inline]
+    fn receive(&mut self, bytes: &[u8]) -> Result<(), ReplicationError> {
+        SignalMapReplica::receive(self, bytes)
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.decoder.clear();
+    }
+}
+
+impl<K, V> Default for SignalMapReplica<K, V> where K: Ord {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+/// Writes all of the frames to `writer`.
+///
+/// It finishes when `frames` ends, or returns an error if writing fails.
+pub async fn send_frames<S, W>(frames: S, mut writer: W) -> io::Result<()>
+    where S: Stream<Item = Vec<u8>>,
+          W: AsyncWrite + Unpin {
+
+    futures_util::pin_mut!(frames);
+
+    while let Some(frame) = frames.next().await {
+        writer.write_all(&frame).await?;
+        writer.flush().await?;
+    }
+
+    Ok(())
+}
+
+/// Reads bytes from `reader` and passes them into `replica`.
+///
+/// It finishes when `reader` is closed, or returns an error if reading fails or if a frame is invalid.
+pub async fn receive_frames<R, A>(mut reader: R, replica: &mut A) -> io::Result<()>
+    where R: AsyncRead + Unpin,
+          A: Replica {
+
+    let mut buffer = vec![0; 4096];
+
+    loop {
+        let len = reader.read(&mut buffer).await?;
+
+        if len == 0 {
+            return Ok(());
+        }
+
+        replica.receive(&buffer[..len]).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    }
+}
+
+/// This is the same as [`send_frames`](fn.send_frames.html), except it reconnects if the connection is lost.
+///
+/// It calls `connect` to create a connection, and `frames` to create the frames for that connection.
+/// Because `frames` is called for every connection, each connection starts with a full snapshot.
+///
+/// If writing fails then it reconnects. It finishes when the frames end, or returns an error if `connect` fails.
+/// To retry failed connections, `connect` should do the retrying (e.g. with a delay between attempts).
+pub async fn send_frames_reconnect<C, CF, W, F, S>(mut connect: C, mut frames: F) -> io::Result<()>
+    where C: FnMut() -> CF,
+          CF: Future<Output = io::Result<W>>,
+          W: AsyncWrite + Unpin,
+          F: FnMut() -> S,
+          S: Stream<Item = Vec<u8>> {
+
+    loop {
+        let writer = connect().await?;
+
+        if send_frames(frames(), writer).await.is_ok() {
+            return Ok(());
+        }
+    }
+}
+
+/// This is the same as [`receive_frames`](fn.receive_frames.html), except it reconnects if the connection is lost.
+///
+/// It calls `connect` to create a connection. Before each connection it calls [`Replica::reset`](trait.Replica.html#tymethod.reset),
+/// and the first frame of each connection is a full snapshot (see [`send_frames_reconnect`](fn.send_frames_reconnect.html)),
+/// so after the first frame the replica matches the sender again.
+///
+/// If reading fails, or the connection is closed, then it reconnects. It finishes when `connect` returns `Ok(None)`,
+/// or returns an error if `connect` fails or if a frame is invalid.
+pub async fn receive_frames_reconnect<C, CF, R, A>(mut connect: C, replica: &mut A) -> io::Result<()>
+    where C: FnMut() -> CF,
+          CF: Future<Output = io::Result<Option<R>>>,
+          R: AsyncRead + Unpin,
+          A: Replica {
+
+    while let Some(reader) = connect().await? {
+        replica.reset();
+
+        match receive_frames(reader, replica).await {
+            Err(error) if error.kind() == io::ErrorKind::InvalidData => return Err(error),
+            Ok(()) | Err(_) => {},
+        }
+    }
+
+    Ok(())
+}
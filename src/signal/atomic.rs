@@ -0,0 +1,319 @@
+use super::Signal;
+use super::mutable::wake;
+use core::fmt;
+use core::pin::Pin;
+use core::ptr;
+use core::marker::{Unpin, PhantomData};
+use alloc::sync::Arc;
+use crate::sync::{AtomicBool, AtomicPtr, AtomicU64, AtomicUsize, Ordering};
+use core::task::{Poll, Context};
+use futures_util::task::AtomicWaker;
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+
+
+/// Values which can be stored in a [`MutableAtomic`](struct.MutableAtomic.html).
+///
+/// They are stored as a `u64`, so this is only implemented for primitive types
+/// which fit into a `u64`.
+// TODO Seal this
+pub trait AtomicPrimitive: Copy {
+    #[doc(hidden)]
+    fn into_u64(self) -> u64;
+
+    #[doc(hidden)]
+    fn from_u64(value: u64) -> Self;
+}
+
+macro_rules! atomic_primitive {
+    ($($t:ty),*) => {
+        $(
+            impl AtomicPrimitive for $t {
+                #[inline]
+                fn into_u64(self) -> u64 {
+                    self as u64
+                }
+
+                #[inline]
+                fn from_u64(value: u64) -> Self {
+                    value as $t
+                }
+            }
+        )*
+    };
+}
+
+atomic_primitive!(u8, u16, u32, u64, usize);
+
+impl AtomicPrimitive for bool {
+    #[inline]
+    fn into_u64(self) -> u64 {
+        self as u64
+    }
+
+    #[inline]
+    fn from_u64(value: u64) -> Self {
+        value != 0
+    }
+}
+
+
+// A slot in the MutableAtomic's list of receivers.
+//
+// The list is only ever pushed to, and a slot is reused by a new MutableAtomicSignal after the
+// old one is dropped, so `set` and `poll_change` never need to take a lock. The slots are only
+// freed when the MutableAtomic's state is dropped, so the list is as long as the most
+// MutableAtomicSignals which have existed at the same time.
+#[derive(Debug)]
+struct MutableAtomicReceiver {
+    in_use: AtomicBool,
+    waker: AtomicWaker,
+    // This is never changed after the receiver is pushed into the list
+    next: AtomicPtr<MutableAtomicReceiver>,
+}
+
+
+struct MutableAtomicState<A> {
+    value: AtomicU64,
+    // This is incremented after every change, so the signals can tell whether the value has changed
+    version: AtomicU64,
+    senders: AtomicUsize,
+    receivers: AtomicPtr<MutableAtomicReceiver>,
+    value_type: PhantomData<A>,
+}
+
+impl<A> MutableAtomicState<A> {
+    fn receivers(&self) -> impl Iterator<Item = &MutableAtomicReceiver> {
+        let mut next = self.receivers.load(Ordering::Acquire) as *const MutableAtomicReceiver;
+
+        core::iter::from_fn(move || {
+            // This is safe because the receivers aren't freed until the state is dropped
+            let receiver = unsafe { next.as_ref() }?;
+            next = receiver.next.load(Ordering::Relaxed);
+            Some(receiver)
+        })
+    }
+
+    fn add_receiver(&self) -> Arc<MutableAtomicReceiver> {
+        for receiver in self.receivers() {
+            if receiver.in_use.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_ok() {
+                let receiver = receiver as *const MutableAtomicReceiver;
+
+                // This is safe because the list owns a strong reference to every receiver
+                return unsafe {
+                    Arc::increment_strong_count(receiver);
+                    Arc::from_raw(receiver)
+                };
+            }
+        }
+
+        let receiver = Arc::new(MutableAtomicReceiver {
+            in_use: AtomicBool::new(true),
+            waker: AtomicWaker::new(),
+            next: AtomicPtr::new(ptr::null_mut()),
+        });
+
+        let new = Arc::into_raw(receiver.clone()) as *mut MutableAtomicReceiver;
+        let mut head = self.receivers.load(Ordering::Acquire);
+
+        loop {
+            receiver.next.store(head, Ordering::Relaxed);
+
+            match self.receivers.compare_exchange_weak(head, new, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => break receiver,
+                Err(old) => head = old,
+            }
+        }
+    }
+
+    fn notify(&self, has_changed: bool) {
+        if has_changed {
+            self.version.fetch_add(1, Ordering::AcqRel);
+        }
+
+        for receiver in self.receivers() {
+            if let Some(waker) = receiver.waker.take() {
+                wake(waker);
+            }
+        }
+    }
+}
+
+impl<A> Drop for MutableAtomicState<A> {
+    fn drop(&mut self) {
+        let mut next = self.receivers.load(Ordering::Acquire);
+
+        while !next.is_null() {
+            // This is safe because every receiver in the list was created with Arc::into_raw
+            let receiver = unsafe { Arc::from_raw(next as *const MutableAtomicReceiver) };
+            next = receiver.next.load(Ordering::Relaxed);
+        }
+    }
+}
+
+impl<A> fmt::Debug for MutableAtomicState<A> where A: AtomicPrimitive + fmt::Debug {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("MutableAtomicState")
+            .field("value", &A::from_u64(self.value.load(Ordering::Acquire)))
+            .field("version", &self.version)
+            .field("senders", &self.senders)
+            .field("receivers", &self.receivers().filter(|receiver| receiver.in_use.load(Ordering::Relaxed)).count())
+            .finish()
+    }
+}
+
+
+/// Like [`Mutable`](struct.Mutable.html), except it only works with primitive types
+/// (integers and `bool`), and it uses atomics instead of a lock.
+///
+/// This makes `get` and `set` very cheap, which is useful for values which change
+/// very frequently, like counters and flags. Polling a
+/// [`MutableAtomicSignal`](struct.MutableAtomicSignal.html) doesn't take a lock either.
+///
+/// The memory for each `MutableAtomicSignal` is reused by later signals, and it is only
+/// freed when the `MutableAtomic` (and all of its signals) are dropped.
+pub struct MutableAtomic<A>(Arc<MutableAtomicState<A>>);
+
+impl<A> MutableAtomic<A> where A: AtomicPrimitive {
+    pub fn new(value: A) -> Self {
+        MutableAtomic(Arc::new(MutableAtomicState {
+            value: AtomicU64::new(value.into_u64()),
+            version: AtomicU64::new(0),
+            senders: AtomicUsize::new(1),
+            receivers: AtomicPtr::new(ptr::null_mut()),
+            value_type: PhantomData,
+        }))
+    }
+
+    #[inline]
+    pub fn get(&self) -> A {
+        A::from_u64(self.0.value.load(Ordering::Acquire))
+    }
+
+    pub fn set(&self, value: A) {
+        self.0.value.store(value.into_u64(), Ordering::Release);
+        self.0.notify(true);
+    }
+
+    pub fn replace(&self, value: A) -> A {
+        let old = self.0.value.swap(value.into_u64(), Ordering::AcqRel);
+        self.0.notify(true);
+        A::from_u64(old)
+    }
+
+    /// Like `set`, except it only notifies if the new value is different from the old value.
+    pub fn set_neq(&self, value: A) {
+        let value = value.into_u64();
+        let old = self.0.value.swap(value, Ordering::AcqRel);
+
+        if old != value {
+            self.0.notify(true);
+        }
+    }
+
+    pub fn signal(&self) -> MutableAtomicSignal<A> {
+        MutableAtomicSignal {
+            version: None,
+            receiver: self.0.add_receiver(),
+            mutable_state: self.0.clone(),
+        }
+    }
+}
+
+impl<A> fmt::Debug for MutableAtomic<A> where A: AtomicPrimitive + fmt::Debug {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_tuple("MutableAtomic")
+            .field(&self.get())
+            .finish()
+    }
+}
+
+impl<A> Serialize for MutableAtomic<A> where A: AtomicPrimitive + Serialize {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        self.get().serialize(serializer)
+    }
+}
+
+impl<'de, A> Deserialize<'de> for MutableAtomic<A> where A: AtomicPrimitive + Deserialize<'de> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        A::deserialize(deserializer).map(MutableAtomic::new)
+    }
+}
+
+impl<A> Default for MutableAtomic<A> where A: AtomicPrimitive + Default {
+    #[inline]
+    fn default() -> Self {
+        MutableAtomic::new(Default::default())
+    }
+}
+
+impl<A> Clone for MutableAtomic<A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.0.senders.fetch_add(1, Ordering::Relaxed);
+        MutableAtomic(self.0.clone())
+    }
+}
+
+impl<A> Drop for MutableAtomic<A> {
+    #[inline]
+    fn drop(&mut self) {
+        // This was the last MutableAtomic
+        if self.0.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.0.notify(false);
+        }
+    }
+}
+
+
+#[must_use = "Signals do nothing unless polled"]
+pub struct MutableAtomicSignal<A> {
+    version: Option<u64>,
+    receiver: Arc<MutableAtomicReceiver>,
+    mutable_state: Arc<MutableAtomicState<A>>,
+}
+
+impl<A> Unpin for MutableAtomicSignal<A> {}
+
+impl<A> Signal for MutableAtomicSignal<A> where A: AtomicPrimitive {
+    type Item = A;
+
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        // The waker is registered before checking the version, so that a notify can't be missed
+        this.receiver.waker.register(cx.waker());
+
+        let version = this.mutable_state.version.load(Ordering::Acquire);
+
+        if this.version != Some(version) {
+            this.version = Some(version);
+            Poll::Ready(Some(A::from_u64(this.mutable_state.value.load(Ordering::Acquire))))
+
+        } else if this.mutable_state.senders.load(Ordering::Acquire) == 0 {
+            Poll::Ready(None)
+
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<A> Drop for MutableAtomicSignal<A> {
+    fn drop(&mut self) {
+        drop(self.receiver.waker.take());
+        // This allows a new MutableAtomicSignal to reuse the receiver
+        self.receiver.in_use.store(false, Ordering::Release);
+    }
+}
+
+impl<A> fmt::Debug for MutableAtomicSignal<A> where A: AtomicPrimitive + fmt::Debug {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("MutableAtomicSignal")
+            .field("version", &self.version)
+            .field("receiver", &self.receiver)
+            .field("mutable_state", &self.mutable_state)
+            .finish()
+    }
+}
@@ -1,20 +1,25 @@
 use super::Signal;
-use std::pin::Pin;
-use std::marker::Unpin;
-use std::sync::{Arc, Mutex, RwLock, Weak};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::task::{Poll, Waker, Context};
+use core::pin::Pin;
+use core::marker::Unpin;
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use alloc::sync::{Arc, Weak};
+use crate::sync::{Mutex, RwLock, AtomicBool, Ordering};
+use core::task::{Poll, Waker, Context};
 use futures_util::task::{self, ArcWake};
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
 
 
 #[derive(Debug)]
-struct BroadcasterStatus {
+pub(crate) struct BroadcasterStatus {
     is_changed: AtomicBool,
-    waker: Mutex<Option<Waker>>,
+    pub(crate) waker: Mutex<Option<Waker>>,
 }
 
 impl BroadcasterStatus {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
             is_changed: AtomicBool::new(true),
             waker: Mutex::new(None),
@@ -27,13 +32,13 @@ impl BroadcasterStatus {
 /// This is responsible for propagating a "wake" down to any pending tasks
 /// attached to broadcasted children.
 #[derive(Debug)]
-struct BroadcasterNotifier {
-    is_changed: AtomicBool,
-    targets: Mutex<Vec<Weak<BroadcasterStatus>>>,
+pub(crate) struct BroadcasterNotifier {
+    pub(crate) is_changed: AtomicBool,
+    pub(crate) targets: Mutex<Vec<Weak<BroadcasterStatus>>>,
 }
 
 impl BroadcasterNotifier {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
             is_changed: AtomicBool::new(true),
             targets: Mutex::new(vec![]),
@@ -41,19 +46,24 @@ impl BroadcasterNotifier {
     }
 
     fn notify(&self, is_changed: bool) {
-        let mut lock = self.targets.lock().unwrap();
+        let mut lock = self.targets.lock();
+
+        #[cfg(feature = "tracing")]
+        tracing::trace!(receivers = lock.len(), is_changed, "Broadcaster woken up");
 
+        // This is read without holding `targets`, so it needs Release
         if is_changed {
-            self.is_changed.store(true, Ordering::SeqCst);
+            self.is_changed.store(true, Ordering::Release);
         }
 
         // Take this opportunity to GC dead children
         lock.retain(|weak_child_state| {
             if let Some(child_status) = weak_child_state.upgrade() {
-                let mut lock = child_status.waker.lock().unwrap();
+                let mut lock = child_status.waker.lock();
 
+                // This is always accessed while the waker is locked
                 if is_changed {
-                    child_status.is_changed.store(true, Ordering::SeqCst);
+                    child_status.is_changed.store(true, Ordering::Relaxed);
                 }
 
                 if let Some(waker) = lock.take() {
@@ -96,7 +106,11 @@ impl<A> BroadcasterInnerState<A> where A: Signal {
 
     // Poll the underlying signal for changes, giving it a BroadcasterNotifier
     // to wake in the future if it is in Pending state.
+    #[allow(clippy::while_let_loop)]
     fn poll_underlying(&mut self, notifier: Arc<BroadcasterNotifier>) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("Broadcaster::poll_underlying", receivers = notifier.targets.lock().len()).entered();
+
         // TODO is this the best way to do this ?
         let waker = task::waker(notifier);
         let cx = &mut Context::from_waker(&waker);
@@ -108,6 +122,9 @@ impl<A> BroadcasterInnerState<A> where A: Signal {
                 Poll::Ready(value) => {
                     let done = value.is_none();
 
+                    #[cfg(feature = "tracing")]
+                    tracing::trace!(done, "underlying signal is ready");
+
                     self.value = value;
 
                     if done {
@@ -115,6 +132,9 @@ impl<A> BroadcasterInnerState<A> where A: Signal {
                     }
                 },
                 Poll::Pending => {
+                    #[cfg(feature = "tracing")]
+                    tracing::trace!("underlying signal is pending");
+
                     break;
                 },
             }
@@ -131,6 +151,8 @@ impl<A> Unpin for BroadcasterInnerState<A> where A: Signal {}
 struct BroadcasterSharedState<A> where A: Signal {
     inner: RwLock<BroadcasterInnerState<A>>,
     notifier: Arc<BroadcasterNotifier>,
+    #[cfg(feature = "graph")]
+    node: crate::graph::Node,
 }
 
 impl<A> BroadcasterSharedState<A> where A: Signal {
@@ -138,20 +160,26 @@ impl<A> BroadcasterSharedState<A> where A: Signal {
         Self {
             inner: RwLock::new(BroadcasterInnerState::new(signal)),
             notifier: Arc::new(BroadcasterNotifier::new()),
+            #[cfg(feature = "graph")]
+            node: crate::graph::Node::new("Broadcaster".into()),
         }
     }
 
     fn poll<B, F>(&self, f: F) -> B where F: FnOnce(&Option<A::Item>) -> B {
         // TODO is this correct ?
-        if self.notifier.is_changed.swap(false, Ordering::SeqCst) {
-            let mut lock = self.inner.write().unwrap();
+        if self.notifier.is_changed.swap(false, Ordering::AcqRel) {
+            let mut lock = self.inner.write();
+
+            #[cfg(feature = "graph")]
+            self.node.poll(|| lock.poll_underlying(self.notifier.clone()));
 
+            #[cfg(not(feature = "graph"))]
             lock.poll_underlying(self.notifier.clone());
 
             f(&lock.value)
 
         } else {
-            let lock = self.inner.read().unwrap();
+            let lock = self.inner.read();
 
             f(&lock.value)
         }
@@ -159,11 +187,11 @@ impl<A> BroadcasterSharedState<A> where A: Signal {
 }
 
 // TODO use derive
-impl<A> ::std::fmt::Debug for BroadcasterSharedState<A>
-    where A: ::std::fmt::Debug + Signal,
-          A::Item: ::std::fmt::Debug {
+impl<A> ::core::fmt::Debug for BroadcasterSharedState<A>
+    where A: ::core::fmt::Debug + Signal,
+          A::Item: ::core::fmt::Debug {
 
-    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         fmt.debug_struct("BroadcasterSharedState")
             .field("inner", &self.inner)
             .field("notifier", &self.notifier)
@@ -187,7 +215,7 @@ impl<A> BroadcasterState<A> where A: Signal {
         let new_status = Arc::new(BroadcasterStatus::new());
 
         {
-            let mut lock = shared_state.notifier.targets.lock().unwrap();
+            let mut lock = shared_state.notifier.targets.lock();
             lock.push(Arc::downgrade(&new_status));
         }
 
@@ -197,27 +225,35 @@ impl<A> BroadcasterState<A> where A: Signal {
         }
     }
 
-    fn poll_change<F>(&self, cx: &mut Context, f: F) -> Poll<Option<A::Item>> where F: FnOnce(&Option<A::Item>) -> Option<A::Item> {
+    fn poll_change<B, F>(&self, cx: &mut Context, f: F) -> Poll<Option<B>> where F: FnOnce(&Option<A::Item>) -> Option<B> {
+        #[cfg(feature = "graph")]
+        self.shared_state.node.polled();
+
+        // The notifier sets is_changed while holding this lock, so holding it here
+        // means a change can't happen between checking is_changed and saving the Waker
+        let mut waker = self.status.waker.lock();
+
         // If the poll just done (or a previous poll) has generated a new
         // value, we can report it. Use swap so only one thread will pick up
         // the change
-        if self.status.is_changed.swap(false, Ordering::SeqCst) {
+        if self.status.is_changed.swap(false, Ordering::Relaxed) {
+            drop(waker);
             Poll::Ready(self.shared_state.poll(f))
 
         } else {
             // Nothing new to report, save this task's Waker for later
-            *self.status.waker.lock().unwrap() = Some(cx.waker().clone());
+            *waker = Some(cx.waker().clone());
             Poll::Pending
         }
     }
 }
 
 // TODO use derive
-impl<A> ::std::fmt::Debug for BroadcasterState<A>
-    where A: ::std::fmt::Debug + Signal,
-          A::Item: ::std::fmt::Debug {
+impl<A> ::core::fmt::Debug for BroadcasterState<A>
+    where A: ::core::fmt::Debug + Signal,
+          A::Item: ::core::fmt::Debug {
 
-    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         fmt.debug_struct("BroadcasterState")
             .field("status", &self.status)
             .field("shared_state", &self.shared_state)
@@ -244,6 +280,18 @@ impl<A> Broadcaster<A> where A: Signal {
             shared_state: Arc::new(BroadcasterSharedState::new(signal)),
         }
     }
+
+    /// Create a new `Signal` which runs `f` with a reference to the values
+    /// from the `Signal` wrapped by the `Broadcaster`
+    ///
+    /// This can be used to get a part of the value without cloning all of it.
+    // TODO: use `impl Signal` for the return type
+    pub fn signal_ref<B, F>(&self, f: F) -> BroadcasterSignalRef<A, F> where F: FnMut(&A::Item) -> B {
+        BroadcasterSignalRef {
+            state: BroadcasterState::new(&self.shared_state),
+            callback: f,
+        }
+    }
 }
 
 impl<A> Broadcaster<A> where A: Signal, A::Item: Copy {
@@ -268,12 +316,24 @@ impl<A> Broadcaster<A> where A: Signal, A::Item: Clone {
     }
 }
 
+// This is implemented manually because derive would require `A: Clone`
+impl<A> Clone for Broadcaster<A> where A: Signal {
+    /// Creates a new handle to the same `Broadcaster`, both handles share the
+    /// underlying `Signal`.
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            shared_state: self.shared_state.clone(),
+        }
+    }
+}
+
 // TODO use derive
-impl<A> ::std::fmt::Debug for Broadcaster<A>
-    where A: ::std::fmt::Debug + Signal,
-          A::Item: ::std::fmt::Debug {
+impl<A> ::core::fmt::Debug for Broadcaster<A>
+    where A: ::core::fmt::Debug + Signal,
+          A::Item: ::core::fmt::Debug {
 
-    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         fmt.debug_struct("Broadcaster")
             .field("shared_state", &self.shared_state)
             .finish()
@@ -300,11 +360,11 @@ impl<A> Signal for BroadcasterSignal<A>
 }
 
 // TODO use derive
-impl<A> ::std::fmt::Debug for BroadcasterSignal<A>
-    where A: ::std::fmt::Debug + Signal,
-          A::Item: ::std::fmt::Debug {
+impl<A> ::core::fmt::Debug for BroadcasterSignal<A>
+    where A: ::core::fmt::Debug + Signal,
+          A::Item: ::core::fmt::Debug {
 
-    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         fmt.debug_struct("BroadcasterSignal")
             .field("state", &self.state)
             .finish()
@@ -331,13 +391,325 @@ impl<A> Signal for BroadcasterSignalCloned<A>
 }
 
 // TODO use derive
-impl<A> ::std::fmt::Debug for BroadcasterSignalCloned<A>
-    where A: ::std::fmt::Debug + Signal,
-          A::Item: ::std::fmt::Debug {
+impl<A> ::core::fmt::Debug for BroadcasterSignalCloned<A>
+    where A: ::core::fmt::Debug + Signal,
+          A::Item: ::core::fmt::Debug {
 
-    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         fmt.debug_struct("BroadcasterSignalCloned")
             .field("state", &self.state)
             .finish()
     }
 }
+
+// --------------------------------------------------------------------------
+
+#[must_use = "Signals do nothing unless polled"]
+pub struct BroadcasterSignalRef<A, F> where A: Signal {
+    state: BroadcasterState<A>,
+    callback: F,
+}
+
+impl<A, F> Unpin for BroadcasterSignalRef<A, F> where A: Signal {}
+
+impl<A, B, F> Signal for BroadcasterSignalRef<A, F>
+    where A: Signal,
+          F: FnMut(&A::Item) -> B {
+
+    type Item = B;
+
+    #[inline]
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let callback = &mut this.callback;
+        this.state.poll_change(cx, |value| value.as_ref().map(callback))
+    }
+}
+
+// TODO use derive
+impl<A, F> ::core::fmt::Debug for BroadcasterSignalRef<A, F>
+    where A: ::core::fmt::Debug + Signal,
+          A::Item: ::core::fmt::Debug {
+
+    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        fmt.debug_struct("BroadcasterSignalRef")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+// Shared state underpinning a Cloned set of local broadcasters
+//
+// The notifier still uses Arc, because it is used as a Waker.
+struct LocalBroadcasterSharedState<A> where A: Signal {
+    inner: RefCell<BroadcasterInnerState<A>>,
+    notifier: Arc<BroadcasterNotifier>,
+    #[cfg(feature = "graph")]
+    node: crate::graph::Node,
+}
+
+impl<A> LocalBroadcasterSharedState<A> where A: Signal {
+    fn new(signal: A) -> Self {
+        Self {
+            inner: RefCell::new(BroadcasterInnerState::new(signal)),
+            notifier: Arc::new(BroadcasterNotifier::new()),
+            #[cfg(feature = "graph")]
+            node: crate::graph::Node::new("LocalBroadcaster".into()),
+        }
+    }
+
+    fn poll<B, F>(&self, f: F) -> B where F: FnOnce(&Option<A::Item>) -> B {
+        if self.notifier.is_changed.swap(false, Ordering::AcqRel) {
+            let mut lock = self.inner.borrow_mut();
+
+            #[cfg(feature = "graph")]
+            self.node.poll(|| lock.poll_underlying(self.notifier.clone()));
+
+            #[cfg(not(feature = "graph"))]
+            lock.poll_underlying(self.notifier.clone());
+
+            f(&lock.value)
+
+        } else {
+            let lock = self.inner.borrow();
+
+            f(&lock.value)
+        }
+    }
+}
+
+// TODO use derive
+impl<A> ::core::fmt::Debug for LocalBroadcasterSharedState<A>
+    where A: ::core::fmt::Debug + Signal,
+          A::Item: ::core::fmt::Debug {
+
+    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        fmt.debug_struct("LocalBroadcasterSharedState")
+            .field("inner", &self.inner)
+            .field("notifier", &self.notifier)
+            .finish()
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+// State for a single local broadcaster instance.
+struct LocalBroadcasterState<A> where A: Signal {
+    status: Arc<BroadcasterStatus>,
+    shared_state: Rc<LocalBroadcasterSharedState<A>>,
+}
+
+impl<A> LocalBroadcasterState<A> where A: Signal {
+    fn new(shared_state: &Rc<LocalBroadcasterSharedState<A>>) -> Self {
+        let new_status = Arc::new(BroadcasterStatus::new());
+
+        {
+            let mut lock = shared_state.notifier.targets.lock();
+            lock.push(Arc::downgrade(&new_status));
+        }
+
+        Self {
+            status: new_status,
+            shared_state: shared_state.clone(),
+        }
+    }
+
+    fn poll_change<B, F>(&self, cx: &mut Context, f: F) -> Poll<Option<B>> where F: FnOnce(&Option<A::Item>) -> Option<B> {
+        #[cfg(feature = "graph")]
+        self.shared_state.node.polled();
+
+        // The underlying Signal can be woken up on another thread, so this has the same locking as BroadcasterState
+        let mut waker = self.status.waker.lock();
+
+        if self.status.is_changed.swap(false, Ordering::Relaxed) {
+            drop(waker);
+            Poll::Ready(self.shared_state.poll(f))
+
+        } else {
+            *waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+// TODO use derive
+impl<A> ::core::fmt::Debug for LocalBroadcasterState<A>
+    where A: ::core::fmt::Debug + Signal,
+          A::Item: ::core::fmt::Debug {
+
+    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        fmt.debug_struct("LocalBroadcasterState")
+            .field("status", &self.status)
+            .field("shared_state", &self.shared_state)
+            .finish()
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// A single-threaded version of [`Broadcaster`](struct.Broadcaster.html).
+///
+/// It has the same API as `Broadcaster`, but it uses `Rc<RefCell<...>>` for the
+/// state, so the `Signal` doesn't need to be `Send` or `Sync`.
+pub struct LocalBroadcaster<A> where A: Signal {
+    shared_state: Rc<LocalBroadcasterSharedState<A>>,
+}
+
+impl<A> LocalBroadcaster<A> where A: Signal {
+    /// Create a new `LocalBroadcaster`
+    pub fn new(signal: A) -> Self {
+        Self {
+            shared_state: Rc::new(LocalBroadcasterSharedState::new(signal)),
+        }
+    }
+
+    /// Create a new `Signal` which runs `f` with a reference to the values
+    /// from the `Signal` wrapped by the `LocalBroadcaster`
+    pub fn signal_ref<B, F>(&self, f: F) -> LocalBroadcasterSignalRef<A, F> where F: FnMut(&A::Item) -> B {
+        LocalBroadcasterSignalRef {
+            state: LocalBroadcasterState::new(&self.shared_state),
+            callback: f,
+        }
+    }
+}
+
+impl<A> LocalBroadcaster<A> where A: Signal, A::Item: Copy {
+    /// Create a new `Signal` which copies values from the `Signal` wrapped
+    /// by the `LocalBroadcaster`
+    pub fn signal(&self) -> LocalBroadcasterSignal<A> {
+        LocalBroadcasterSignal {
+            state: LocalBroadcasterState::new(&self.shared_state),
+        }
+    }
+}
+
+impl<A> LocalBroadcaster<A> where A: Signal, A::Item: Clone {
+    /// Create a new `Signal` which clones values from the `Signal` wrapped
+    /// by the `LocalBroadcaster`
+    pub fn signal_cloned(&self) -> LocalBroadcasterSignalCloned<A> {
+        LocalBroadcasterSignalCloned {
+            state: LocalBroadcasterState::new(&self.shared_state),
+        }
+    }
+}
+
+impl<A> Clone for LocalBroadcaster<A> where A: Signal {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            shared_state: self.shared_state.clone(),
+        }
+    }
+}
+
+// TODO use derive
+impl<A> ::core::fmt::Debug for LocalBroadcaster<A>
+    where A: ::core::fmt::Debug + Signal,
+          A::Item: ::core::fmt::Debug {
+
+    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        fmt.debug_struct("LocalBroadcaster")
+            .field("shared_state", &self.shared_state)
+            .finish()
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[must_use = "Signals do nothing unless polled"]
+pub struct LocalBroadcasterSignal<A> where A: Signal {
+    state: LocalBroadcasterState<A>,
+}
+
+impl<A> Signal for LocalBroadcasterSignal<A>
+    where A: Signal,
+          A::Item: Copy {
+
+    type Item = A::Item;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.state.poll_change(cx, |value| *value)
+    }
+}
+
+// TODO use derive
+impl<A> ::core::fmt::Debug for LocalBroadcasterSignal<A>
+    where A: ::core::fmt::Debug + Signal,
+          A::Item: ::core::fmt::Debug {
+
+    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        fmt.debug_struct("LocalBroadcasterSignal")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+// --------------------------------------------------------------------------
+
+#[must_use = "Signals do nothing unless polled"]
+pub struct LocalBroadcasterSignalCloned<A> where A: Signal {
+    state: LocalBroadcasterState<A>,
+}
+
+impl<A> Signal for LocalBroadcasterSignalCloned<A>
+    where A: Signal,
+          A::Item: Clone {
+
+    type Item = A::Item;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.state.poll_change(cx, |value| value.clone())
+    }
+}
+
+// TODO use derive
+impl<A> ::core::fmt::Debug for LocalBroadcasterSignalCloned<A>
+    where A: ::core::fmt::Debug + Signal,
+          A::Item: ::core::fmt::Debug {
+
+    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        fmt.debug_struct("LocalBroadcasterSignalCloned")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+// --------------------------------------------------------------------------
+
+#[must_use = "Signals do nothing unless polled"]
+pub struct LocalBroadcasterSignalRef<A, F> where A: Signal {
+    state: LocalBroadcasterState<A>,
+    callback: F,
+}
+
+impl<A, F> Unpin for LocalBroadcasterSignalRef<A, F> where A: Signal {}
+
+impl<A, B, F> Signal for LocalBroadcasterSignalRef<A, F>
+    where A: Signal,
+          F: FnMut(&A::Item) -> B {
+
+    type Item = B;
+
+    #[inline]
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let callback = &mut this.callback;
+        this.state.poll_change(cx, |value| value.as_ref().map(callback))
+    }
+}
+
+// TODO use derive
+impl<A, F> ::core::fmt::Debug for LocalBroadcasterSignalRef<A, F>
+    where A: ::core::fmt::Debug + Signal,
+          A::Item: ::core::fmt::Debug {
+
+    fn fmt(&self, fmt: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        fmt.debug_struct("LocalBroadcasterSignalRef")
+            .field("state", &self.state)
+            .finish()
+    }
+}
@@ -1,15 +1,19 @@
 use super::Signal;
-use std::pin::Pin;
-use std::marker::Unpin;
-// TODO use parking_lot ?
-use std::sync::{Arc, Weak, Mutex, MutexGuard};
-use std::task::{Poll, Context, Waker};
+use core::fmt;
+use core::pin::Pin;
+use core::marker::Unpin;
+use alloc::collections::VecDeque;
+use alloc::sync::{Arc, Weak};
+use crate::sync::{Mutex, MutexGuard};
+use core::task::{Poll, Context, Waker};
+use futures_sink::Sink;
 
 
 #[derive(Debug)]
 struct Inner<A> {
     value: Option<A>,
     waker: Option<Waker>,
+    senders: usize,
     dropped: bool,
 }
 
@@ -23,6 +27,10 @@ impl<A> Inner<A> {
 }
 
 
+/// The sending half of a [`channel`](fn.channel.html).
+///
+/// It can be cloned to send values from multiple places, the `Receiver` ends when
+/// every `Sender` has been dropped.
 #[derive(Debug)]
 pub struct Sender<A> {
     inner: Weak<Mutex<Inner<A>>>,
@@ -31,7 +39,7 @@ pub struct Sender<A> {
 impl<A> Sender<A> {
     pub fn send(&self, value: A) -> Result<(), A> {
         if let Some(inner) = self.inner.upgrade() {
-            let mut inner = inner.lock().unwrap();
+            let mut inner = inner.lock();
 
             inner.value = Some(value);
 
@@ -45,19 +53,112 @@ impl<A> Sender<A> {
     }
 }
 
+impl<A: PartialEq> Sender<A> {
+    /// Like [`send`](#method.send), except it does nothing if `value` is equal
+    /// to the value which is waiting to be received.
+    ///
+    /// This avoids waking up the `Receiver` when nothing changed. If the `Receiver`
+    /// has already received the previous value then it always sends.
+    pub fn send_if_changed(&self, value: A) -> Result<(), A> {
+        if let Some(inner) = self.inner.upgrade() {
+            let mut inner = inner.lock();
+
+            if inner.value.as_ref() != Some(&value) {
+                inner.value = Some(value);
+
+                Inner::notify(inner);
+            }
+
+            Ok(())
+
+        } else {
+            Err(value)
+        }
+    }
+}
+
+impl<A> Clone for Sender<A> {
+    fn clone(&self) -> Self {
+        if let Some(inner) = self.inner.upgrade() {
+            inner.lock().senders += 1;
+        }
+
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
 impl<A> Drop for Sender<A> {
     fn drop(&mut self) {
         if let Some(inner) = self.inner.upgrade() {
-            let mut inner = inner.lock().unwrap();
+            let mut inner = inner.lock();
 
-            inner.dropped = true;
+            inner.senders -= 1;
+
+            // The Receiver only ends when all of the Senders are dropped
+            if inner.senders == 0 {
+                inner.dropped = true;
+
+                Inner::notify(inner);
+            }
+        }
+    }
+}
+
+
+/// The sending half of a [`channel_with`](fn.channel_with.html).
+pub struct MergeSender<A, F> {
+    sender: Sender<A>,
+    merge: F,
+}
+
+impl<A, F> MergeSender<A, F> where F: Fn(&mut A, A) {
+    /// Sends the value, merging it into the previous value if the `Receiver`
+    /// hasn't received it yet.
+    pub fn send(&self, value: A) -> Result<(), A> {
+        if let Some(inner) = self.sender.inner.upgrade() {
+            let mut inner = inner.lock();
+
+            match inner.value {
+                Some(ref mut old) => (self.merge)(old, value),
+                None => inner.value = Some(value),
+            }
 
             Inner::notify(inner);
+
+            Ok(())
+
+        } else {
+            Err(value)
         }
     }
 }
 
+impl<A, F> Clone for MergeSender<A, F> where F: Clone {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            merge: self.merge.clone(),
+        }
+    }
+}
 
+impl<A, F> fmt::Debug for MergeSender<A, F> where A: fmt::Debug {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("MergeSender")
+            .field("sender", &self.sender)
+            .finish()
+    }
+}
+
+/// The receiving half of a [`channel`](fn.channel.html).
+///
+/// This is a `Signal`, so it only contains the most recent value. If you want a
+/// `Stream` then use [`to_stream`](trait.SignalExt.html#method.to_stream), but keep
+/// in mind that it is still lossy: values which are sent before the `Stream` is
+/// polled are skipped.
 #[derive(Debug)]
 #[must_use = "Signals do nothing unless polled"]
 pub struct Receiver<A> {
@@ -71,7 +172,7 @@ impl<A> Signal for Receiver<A> {
 
     #[inline]
     fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
-        let mut inner = self.inner.lock().unwrap();
+        let mut inner = self.inner.lock();
 
         // TODO is this correct ?
         match inner.value.take() {
@@ -88,10 +189,16 @@ impl<A> Signal for Receiver<A> {
     }
 }
 
+/// Creates a channel which contains only the most recent value.
+///
+/// Sending a value overwrites the previous value if the `Receiver` hasn't received it
+/// yet. If you need every value then use [`bounded_channel`](fn.bounded_channel.html)
+/// instead.
 pub fn channel<A>(initial_value: A) -> (Sender<A>, Receiver<A>) {
     let inner = Arc::new(Mutex::new(Inner {
         value: Some(initial_value),
         waker: None,
+        senders: 1,
         dropped: false,
     }));
 
@@ -105,3 +212,232 @@ pub fn channel<A>(initial_value: A) -> (Sender<A>, Receiver<A>) {
 
     (sender, receiver)
 }
+
+/// Like [`channel`](fn.channel.html), except if the `Receiver` hasn't received the
+/// previous value yet then the new value is merged into it with `merge`, instead of
+/// overwriting it.
+///
+/// This is still lossy (the `Receiver` doesn't see the individual values), but no
+/// information is lost, e.g. deltas can be summed or values can be collected into a batch:
+///
+/// ```rust
+/// use futures_signals::signal::channel_with;
+///
+/// let (sender, receiver) = channel_with(0, |old, new| *old += new);
+/// ```
+pub fn channel_with<A, F>(initial_value: A, merge: F) -> (MergeSender<A, F>, Receiver<A>) where F: Fn(&mut A, A) {
+    let (sender, receiver) = channel(initial_value);
+
+    let sender = MergeSender {
+        sender,
+        merge,
+    };
+
+    (sender, receiver)
+}
+
+
+#[derive(Debug)]
+struct BoundedInner<A> {
+    values: VecDeque<A>,
+    capacity: usize,
+    receiver_waker: Option<Waker>,
+    sender_waker: Option<Waker>,
+    dropped: bool,
+}
+
+impl<A> BoundedInner<A> {
+    fn notify_receiver(mut lock: MutexGuard<Self>) {
+        if let Some(waker) = lock.receiver_waker.take() {
+            drop(lock);
+            waker.wake();
+        }
+    }
+
+    fn notify_sender(mut lock: MutexGuard<Self>) {
+        if let Some(waker) = lock.sender_waker.take() {
+            drop(lock);
+            waker.wake();
+        }
+    }
+}
+
+
+/// The error which is returned when sending into a `BoundedSender` whose
+/// `BoundedReceiver` has been dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SendError;
+
+impl fmt::Display for SendError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("receiver was dropped")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SendError {}
+
+
+#[derive(Debug)]
+pub struct BoundedSender<A> {
+    inner: Weak<Mutex<BoundedInner<A>>>,
+}
+
+impl<A> BoundedSender<A> {
+    /// Sends the value if there is room in the buffer, otherwise it returns the value.
+    ///
+    /// It also returns the value if the `BoundedReceiver` was dropped.
+    pub fn try_send(&self, value: A) -> Result<(), A> {
+        if let Some(inner) = self.inner.upgrade() {
+            let mut inner = inner.lock();
+
+            if inner.values.len() < inner.capacity {
+                inner.values.push_back(value);
+
+                BoundedInner::notify_receiver(inner);
+
+                Ok(())
+
+            } else {
+                Err(value)
+            }
+
+        } else {
+            Err(value)
+        }
+    }
+}
+
+impl<A> Unpin for BoundedSender<A> {}
+
+impl<A> Sink<A> for BoundedSender<A> {
+    type Error = SendError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        if let Some(inner) = self.inner.upgrade() {
+            let mut inner = inner.lock();
+
+            if inner.values.len() < inner.capacity {
+                Poll::Ready(Ok(()))
+
+            } else {
+                inner.sender_waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+
+        } else {
+            Poll::Ready(Err(SendError))
+        }
+    }
+
+    fn start_send(self: Pin<&mut Self>, value: A) -> Result<(), Self::Error> {
+        if let Some(inner) = self.inner.upgrade() {
+            let mut inner = inner.lock();
+
+            // This can temporarily go over the capacity if poll_ready wasn't called first
+            inner.values.push_back(value);
+
+            BoundedInner::notify_receiver(inner);
+
+            Ok(())
+
+        } else {
+            Err(SendError)
+        }
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        if let Some(inner) = self.inner.upgrade() {
+            let mut inner = inner.lock();
+
+            inner.dropped = true;
+
+            BoundedInner::notify_receiver(inner);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<A> Drop for BoundedSender<A> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.upgrade() {
+            let mut inner = inner.lock();
+
+            inner.dropped = true;
+
+            BoundedInner::notify_receiver(inner);
+        }
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct BoundedReceiver<A> {
+    inner: Arc<Mutex<BoundedInner<A>>>,
+}
+
+impl<A> Unpin for BoundedReceiver<A> {}
+
+impl<A> Signal for BoundedReceiver<A> {
+    type Item = A;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut inner = self.inner.lock();
+
+        match inner.values.pop_front() {
+            None => if inner.dropped {
+                Poll::Ready(None)
+
+            } else {
+                inner.receiver_waker = Some(cx.waker().clone());
+                Poll::Pending
+            },
+
+            value => {
+                // There is now room in the buffer
+                BoundedInner::notify_sender(inner);
+                Poll::Ready(value)
+            },
+        }
+    }
+}
+
+/// Creates a channel which buffers up to `capacity` values.
+///
+/// Unlike [`channel`](fn.channel.html) this is not lossy: the `BoundedReceiver` returns
+/// every value in the order that they were sent, and the `BoundedSender` (which
+/// implements `Sink`) waits when the buffer is full.
+///
+/// There is no initial value, so the `BoundedReceiver` is `Pending` until a value is sent.
+///
+/// # Panics
+///
+/// This panics if `capacity` is `0`.
+pub fn bounded_channel<A>(capacity: usize) -> (BoundedSender<A>, BoundedReceiver<A>) {
+    assert!(capacity > 0, "capacity must be greater than 0");
+
+    let inner = Arc::new(Mutex::new(BoundedInner {
+        values: VecDeque::with_capacity(capacity),
+        capacity,
+        receiver_waker: None,
+        sender_waker: None,
+        dropped: false,
+    }));
+
+    let sender = BoundedSender {
+        inner: Arc::downgrade(&inner),
+    };
+
+    let receiver = BoundedReceiver {
+        inner,
+    };
+
+    (sender, receiver)
+}
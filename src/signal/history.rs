@@ -0,0 +1,182 @@
+use super::mutable::{Mutable, ReadOnlyMutable, MutableSignal};
+use core::fmt;
+use alloc::sync::Arc;
+use alloc::collections::VecDeque;
+use crate::sync::Mutex;
+use alloc::vec;
+use alloc::vec::Vec;
+
+
+#[derive(Debug)]
+struct Stacks<A> {
+    undo: VecDeque<A>,
+    redo: Vec<A>,
+}
+
+
+#[derive(Debug)]
+struct HistoryState<A> {
+    value: Mutable<A>,
+    stacks: Mutex<Stacks<A>>,
+    limit: Option<usize>,
+    can_undo: Mutable<bool>,
+    can_redo: Mutable<bool>,
+}
+
+impl<A> HistoryState<A> {
+    fn update_flags(&self, stacks: &Stacks<A>) {
+        self.can_undo.set_neq(!stacks.undo.is_empty());
+        self.can_redo.set_neq(!stacks.redo.is_empty());
+    }
+}
+
+
+/// A [`Mutable`](struct.Mutable.html) which remembers its previous values, so that changes can be undone and redone.
+///
+/// Every change saves a snapshot of the old value. If a limit is given, only that many snapshots
+/// are kept (the oldest snapshots are discarded first).
+///
+/// Changing the value (with `set` or `update`) discards all of the snapshots which can be redone.
+pub struct History<A>(Arc<HistoryState<A>>);
+
+impl<A> History<A> {
+    #[inline]
+    pub fn new(value: A) -> Self {
+        Self::new_with_limit(value, None)
+    }
+
+    /// Creates a `History` which only keeps the most recent `limit` snapshots.
+    #[inline]
+    pub fn with_limit(value: A, limit: usize) -> Self {
+        Self::new_with_limit(value, Some(limit))
+    }
+
+    fn new_with_limit(value: A, limit: Option<usize>) -> Self {
+        History(Arc::new(HistoryState {
+            value: Mutable::new(value),
+            stacks: Mutex::new(Stacks {
+                undo: VecDeque::new(),
+                redo: vec![],
+            }),
+            limit,
+            can_undo: Mutable::new(false),
+            can_redo: Mutable::new(false),
+        }))
+    }
+
+    /// Returns a read-only view of the current value, which can be used to get the value or create Signals.
+    #[inline]
+    pub fn read_only(&self) -> ReadOnlyMutable<A> {
+        self.0.value.read_only()
+    }
+
+    /// Sets the current value to `value`, saving the old value so that it can be undone.
+    pub fn set(&self, value: A) {
+        let mut stacks = self.0.stacks.lock();
+        self.set_locked(&mut stacks, value);
+    }
+
+    fn set_locked(&self, stacks: &mut Stacks<A>, value: A) {
+        let old = self.0.value.replace(value);
+
+        stacks.undo.push_back(old);
+
+        if let Some(limit) = self.0.limit {
+            while stacks.undo.len() > limit {
+                stacks.undo.pop_front();
+            }
+        }
+
+        stacks.redo.clear();
+
+        self.0.update_flags(stacks);
+    }
+
+    /// Changes the current value back to the value it had before the last change.
+    ///
+    /// Returns `false` if there is nothing to undo.
+    pub fn undo(&self) -> bool {
+        let mut stacks = self.0.stacks.lock();
+
+        match stacks.undo.pop_back() {
+            Some(value) => {
+                let old = self.0.value.replace(value);
+                stacks.redo.push(old);
+                self.0.update_flags(&stacks);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Redoes the last change which was undone.
+    ///
+    /// Returns `false` if there is nothing to redo.
+    pub fn redo(&self) -> bool {
+        let mut stacks = self.0.stacks.lock();
+
+        match stacks.redo.pop() {
+            Some(value) => {
+                let old = self.0.value.replace(value);
+                stacks.undo.push_back(old);
+                self.0.update_flags(&stacks);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Discards all of the saved snapshots, without changing the current value.
+    pub fn clear_history(&self) {
+        let mut stacks = self.0.stacks.lock();
+        stacks.undo.clear();
+        stacks.redo.clear();
+        self.0.update_flags(&stacks);
+    }
+
+    /// Returns a `Signal` which is `true` if there is something to undo.
+    #[inline]
+    pub fn can_undo(&self) -> MutableSignal<bool> {
+        self.0.can_undo.signal()
+    }
+
+    /// Returns a `Signal` which is `true` if there is something to redo.
+    #[inline]
+    pub fn can_redo(&self) -> MutableSignal<bool> {
+        self.0.can_redo.signal()
+    }
+}
+
+impl<A: Clone> History<A> {
+    /// Calls `f` with a mutable reference to a copy of the current value, and then sets the current value to it.
+    pub fn update<F>(&self, f: F) where F: FnOnce(&mut A) {
+        let mut stacks = self.0.stacks.lock();
+        let mut value = self.0.value.get_cloned();
+        f(&mut value);
+        self.set_locked(&mut stacks, value);
+    }
+}
+
+impl<A> core::ops::Deref for History<A> {
+    type Target = ReadOnlyMutable<A>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0.value
+    }
+}
+
+impl<A> Clone for History<A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        History(self.0.clone())
+    }
+}
+
+impl<A> fmt::Debug for History<A> where A: fmt::Debug {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_tuple("History")
+            .field(&self.0.value)
+            .finish()
+    }
+}
@@ -0,0 +1,649 @@
+use super::Signal;
+use super::mutable::{wake_with_priority, Receivers, ReceiverKey};
+use core::fmt;
+use core::pin::Pin;
+use core::marker::Unpin;
+use core::ops::{Deref, DerefMut};
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use core::cell::{Cell, OnceCell, RefCell, Ref, RefMut};
+use core::task::{Poll, Waker, Context};
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+
+
+#[derive(Debug)]
+struct LocalMutableReceiver {
+    has_changed: Cell<bool>,
+    waker: RefCell<Option<Waker>>,
+    priority: i32,
+}
+
+
+#[derive(Debug)]
+struct LocalMutableState<A> {
+    value: A,
+    senders: usize,
+    receivers: Rc<RefCell<Receivers<Rc<LocalMutableReceiver>>>>,
+    // This is the same as `MutableState::arc`
+    arc: OnceCell<Arc<A>>,
+}
+
+impl<A> LocalMutableState<A> {
+    fn notify(&mut self, has_changed: bool) {
+        if has_changed {
+            self.arc.take();
+        }
+
+        for (_, receiver) in self.receivers.borrow().iter() {
+            if has_changed {
+                receiver.has_changed.set(true);
+            }
+
+            // This is a separate statement so that the borrow ends before waking
+            let waker = receiver.waker.borrow_mut().take();
+
+            if let Some(waker) = waker {
+                wake_with_priority(waker, receiver.priority);
+            }
+        }
+    }
+}
+
+impl<A: Clone> LocalMutableState<A> {
+    fn arc(&self) -> Arc<A> {
+        self.arc.get_or_init(|| Arc::new(self.value.clone())).clone()
+    }
+}
+
+
+#[derive(Debug)]
+struct LocalMutableSignalState<A> {
+    key: ReceiverKey,
+    receiver: Rc<LocalMutableReceiver>,
+    receivers: Rc<RefCell<Receivers<Rc<LocalMutableReceiver>>>>,
+    state: Rc<RefCell<LocalMutableState<A>>>,
+}
+
+impl<A> LocalMutableSignalState<A> {
+    fn new(mutable_state: &Rc<RefCell<LocalMutableState<A>>>, priority: i32) -> Self {
+        let receivers = mutable_state.borrow().receivers.clone();
+
+        let receiver = Rc::new(LocalMutableReceiver {
+            has_changed: Cell::new(true),
+            waker: RefCell::new(None),
+            priority,
+        });
+
+        let key = receivers.borrow_mut().insert(priority, receiver.clone());
+
+        LocalMutableSignalState {
+            key,
+            receiver,
+            receivers,
+            state: mutable_state.clone(),
+        }
+    }
+
+    #[inline]
+    fn poll_change<B, F>(&self, cx: &mut Context, f: F) -> Poll<Option<B>> where F: FnOnce(&A) -> B {
+        self.poll_change_state(cx, |state| f(&state.value))
+    }
+
+    fn poll_change_state<B, F>(&self, cx: &mut Context, f: F) -> Poll<Option<B>> where F: FnOnce(&LocalMutableState<A>) -> B {
+        let lock = self.state.borrow();
+
+        if self.receiver.has_changed.replace(false) {
+            Poll::Ready(Some(f(&lock)))
+
+        } else if lock.senders == 0 {
+            Poll::Ready(None)
+
+        } else {
+            *self.receiver.waker.borrow_mut() = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl<A> Drop for LocalMutableSignalState<A> {
+    // This doesn't borrow the value, so it doesn't panic if the value is already borrowed
+    #[inline]
+    fn drop(&mut self) {
+        self.receivers.borrow_mut().remove(self.key);
+    }
+}
+
+
+#[derive(Debug)]
+pub struct LocalMutableLockMut<'a, A> where A: 'a {
+    mutated: bool,
+    lock: RefMut<'a, LocalMutableState<A>>,
+}
+
+impl<'a, A> Deref for LocalMutableLockMut<'a, A> {
+    type Target = A;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.lock.value
+    }
+}
+
+impl<'a, A> DerefMut for LocalMutableLockMut<'a, A> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.mutated = true;
+        &mut self.lock.value
+    }
+}
+
+impl<'a, A> Drop for LocalMutableLockMut<'a, A> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.mutated {
+            self.lock.notify(true);
+        }
+    }
+}
+
+
+#[derive(Debug)]
+pub struct LocalMutableLockRef<'a, A> where A: 'a {
+    lock: Ref<'a, LocalMutableState<A>>,
+}
+
+impl<'a, A> Deref for LocalMutableLockRef<'a, A> {
+    type Target = A;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.lock.value
+    }
+}
+
+
+/// A single-threaded version of [`ReadOnlyMutable`](struct.ReadOnlyMutable.html).
+///
+/// It is created with [`LocalMutable::read_only`](struct.LocalMutable.html#method.read_only).
+pub struct ReadOnlyLocalMutable<A>(Rc<RefCell<LocalMutableState<A>>>);
+
+impl<A> ReadOnlyLocalMutable<A> {
+    #[inline]
+    pub fn lock_ref(&self) -> LocalMutableLockRef<'_, A> {
+        LocalMutableLockRef {
+            lock: self.0.borrow(),
+        }
+    }
+
+    /// This is the same as [`ReadOnlyMutable::signal_ref`](struct.ReadOnlyMutable.html#method.signal_ref).
+    #[inline]
+    pub fn signal_ref<B, F>(&self, f: F) -> LocalMutableSignalRef<A, F> where F: FnMut(&A) -> B {
+        LocalMutableSignalRef(LocalMutableSignalState::new(&self.0, 0), f)
+    }
+
+    /// Returns the number of Signals which currently exist for this `LocalMutable`.
+    ///
+    /// Signals are removed as soon as they are dropped, so this never counts dead Signals.
+    #[inline]
+    pub fn receiver_count(&self) -> usize {
+        self.0.borrow().receivers.borrow().len()
+    }
+
+    /// Returns `true` if there are any Signals for this `LocalMutable`.
+    #[inline]
+    pub fn has_receivers(&self) -> bool {
+        self.receiver_count() != 0
+    }
+}
+
+impl<A: Copy> ReadOnlyLocalMutable<A> {
+    #[inline]
+    pub fn get(&self) -> A {
+        self.0.borrow().value
+    }
+
+    #[inline]
+    pub fn signal(&self) -> LocalMutableSignal<A> {
+        self.signal_with_priority(0)
+    }
+
+    /// This is the same as [`ReadOnlyMutable::signal_with_priority`](struct.ReadOnlyMutable.html#method.signal_with_priority).
+    #[inline]
+    pub fn signal_with_priority(&self, priority: i32) -> LocalMutableSignal<A> {
+        LocalMutableSignal(LocalMutableSignalState::new(&self.0, priority))
+    }
+}
+
+impl<A: Clone> ReadOnlyLocalMutable<A> {
+    #[inline]
+    pub fn get_cloned(&self) -> A {
+        self.0.borrow().value.clone()
+    }
+
+    #[inline]
+    pub fn signal_cloned(&self) -> LocalMutableSignalCloned<A> {
+        self.signal_cloned_with_priority(0)
+    }
+
+    #[inline]
+    pub fn signal_cloned_with_priority(&self, priority: i32) -> LocalMutableSignalCloned<A> {
+        LocalMutableSignalCloned(LocalMutableSignalState::new(&self.0, priority))
+    }
+
+    /// This is the same as [`ReadOnlyMutable::get_arc`](struct.ReadOnlyMutable.html#method.get_arc).
+    #[inline]
+    pub fn get_arc(&self) -> Arc<A> {
+        self.0.borrow().arc()
+    }
+
+    /// This is the same as [`ReadOnlyMutable::signal_arc`](struct.ReadOnlyMutable.html#method.signal_arc).
+    #[inline]
+    pub fn signal_arc(&self) -> LocalMutableSignalArc<A> {
+        LocalMutableSignalArc(LocalMutableSignalState::new(&self.0, 0))
+    }
+}
+
+impl<A> Clone for ReadOnlyLocalMutable<A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ReadOnlyLocalMutable(self.0.clone())
+    }
+}
+
+impl<A> fmt::Debug for ReadOnlyLocalMutable<A> where A: fmt::Debug {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let state = self.0.borrow();
+
+        fmt.debug_tuple("ReadOnlyLocalMutable")
+            .field(&state.value)
+            .finish()
+    }
+}
+
+
+/// A single-threaded version of [`Mutable`](struct.Mutable.html).
+///
+/// It has the same API as `Mutable`, but it uses `Rc<RefCell<...>>` instead of
+/// `Arc<RwLock<...>>`, so it is cheaper, but it isn't `Send` or `Sync`.
+///
+/// Holding a lock while one of its signals is polled will panic (with `Mutable`
+/// it would deadlock).
+pub struct LocalMutable<A>(ReadOnlyLocalMutable<A>);
+
+impl<A> LocalMutable<A> {
+    pub fn new(value: A) -> Self {
+        LocalMutable(ReadOnlyLocalMutable(Rc::new(RefCell::new(LocalMutableState {
+            value,
+            senders: 1,
+            receivers: Rc::new(RefCell::new(Receivers::new())),
+            arc: OnceCell::new(),
+        }))))
+    }
+
+    #[inline]
+    fn state(&self) -> &Rc<RefCell<LocalMutableState<A>>> {
+        &(self.0).0
+    }
+
+    #[inline]
+    pub fn read_only(&self) -> ReadOnlyLocalMutable<A> {
+        self.0.clone()
+    }
+
+    pub fn replace(&self, value: A) -> A {
+        let mut state = self.state().borrow_mut();
+
+        let value = core::mem::replace(&mut state.value, value);
+
+        state.notify(true);
+
+        value
+    }
+
+    pub fn replace_with<F>(&self, f: F) -> A where F: FnOnce(&mut A) -> A {
+        let mut state = self.state().borrow_mut();
+
+        let new_value = f(&mut state.value);
+        let value = core::mem::replace(&mut state.value, new_value);
+
+        state.notify(true);
+
+        value
+    }
+
+    pub fn swap(&self, other: &LocalMutable<A>) {
+        let mut state1 = self.state().borrow_mut();
+        let mut state2 = other.state().borrow_mut();
+
+        core::mem::swap(&mut state1.value, &mut state2.value);
+
+        state1.notify(true);
+        state2.notify(true);
+    }
+
+    pub fn set(&self, value: A) {
+        let mut state = self.state().borrow_mut();
+
+        state.value = value;
+
+        state.notify(true);
+    }
+
+    /// Calls `f` with the current value and `value` (while holding the write lock). If `f` returns
+    /// `true` then the current value is set to `value`.
+    ///
+    /// Use [`try_set_if`](#method.try_set_if) to find out whether the value was set.
+    #[inline]
+    pub fn set_if<F>(&self, value: A, f: F) where F: FnOnce(&A, &A) -> bool {
+        self.try_set_if(value, f);
+    }
+
+    /// This is the same as [`set_if`](#method.set_if), except it returns `true` if the value was set.
+    pub fn try_set_if<F>(&self, value: A, f: F) -> bool where F: FnOnce(&A, &A) -> bool {
+        let mut state = self.state().borrow_mut();
+
+        if f(&state.value, &value) {
+            state.value = value;
+            state.notify(true);
+            true
+
+        } else {
+            false
+        }
+    }
+
+    pub fn lock_mut(&self) -> LocalMutableLockMut<'_, A> {
+        LocalMutableLockMut {
+            mutated: false,
+            lock: self.state().borrow_mut(),
+        }
+    }
+
+    /// Runs `f` with mutable access to the value, and then notifies exactly once,
+    /// no matter how many changes `f` made.
+    pub fn transaction<B, F>(&self, f: F) -> B where F: FnOnce(&mut A) -> B {
+        let mut lock = self.lock_mut();
+        f(&mut lock)
+    }
+}
+
+impl<A> Deref for LocalMutable<A> {
+    type Target = ReadOnlyLocalMutable<A>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<A: PartialEq> LocalMutable<A> {
+    #[inline]
+    pub fn set_neq(&self, value: A) {
+        self.set_if(value, PartialEq::ne);
+    }
+
+    /// This is the same as [`Mutable::compare_and_set`](struct.Mutable.html#method.compare_and_set).
+    pub fn compare_and_set(&self, expected: &A, new: A) -> Result<A, A> where A: Clone {
+        let mut state = self.state().borrow_mut();
+
+        if state.value == *expected {
+            let old = core::mem::replace(&mut state.value, new);
+            state.notify(true);
+            Ok(old)
+
+        } else {
+            Err(state.value.clone())
+        }
+    }
+}
+
+impl<A> fmt::Debug for LocalMutable<A> where A: fmt::Debug {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let state = self.state().borrow();
+
+        fmt.debug_tuple("LocalMutable")
+            .field(&state.value)
+            .finish()
+    }
+}
+
+impl<T> Serialize for LocalMutable<T> where T: Serialize {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        self.state().borrow().value.serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for LocalMutable<T> where T: Deserialize<'de> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        T::deserialize(deserializer).map(LocalMutable::new)
+    }
+}
+
+impl<T: Default> Default for LocalMutable<T> {
+    #[inline]
+    fn default() -> Self {
+        LocalMutable::new(Default::default())
+    }
+}
+
+impl<A> Clone for LocalMutable<A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.state().borrow_mut().senders += 1;
+        LocalMutable(self.0.clone())
+    }
+}
+
+impl<A> Drop for LocalMutable<A> {
+    #[inline]
+    fn drop(&mut self) {
+        let mut state = self.state().borrow_mut();
+
+        state.senders -= 1;
+
+        if state.senders == 0 && state.receivers.borrow().len() != 0 {
+            state.notify(false);
+        }
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct LocalMutableSignal<A>(LocalMutableSignalState<A>);
+
+impl<A> Unpin for LocalMutableSignal<A> {}
+
+impl<A: Copy> Signal for LocalMutableSignal<A> {
+    type Item = A;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.0.poll_change(cx, |value| *value)
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct LocalMutableSignalRef<A, F>(LocalMutableSignalState<A>, F);
+
+impl<A, F> Unpin for LocalMutableSignalRef<A, F> {}
+
+impl<A, B, F> Signal for LocalMutableSignalRef<A, F> where F: FnMut(&A) -> B {
+    type Item = B;
+
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let state = &this.0;
+        let callback = &mut this.1;
+        state.poll_change(cx, callback)
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct LocalMutableSignalCloned<A>(LocalMutableSignalState<A>);
+
+impl<A> Unpin for LocalMutableSignalCloned<A> {}
+
+impl<A: Clone> Signal for LocalMutableSignalCloned<A> {
+    type Item = A;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.0.poll_change(cx, |value| value.clone())
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct LocalMutableSignalArc<A>(LocalMutableSignalState<A>);
+
+impl<A> Unpin for LocalMutableSignalArc<A> {}
+
+impl<A: Clone> Signal for LocalMutableSignalArc<A> {
+    type Item = Arc<A>;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.0.poll_change_state(cx, |state| state.arc())
+    }
+}
+
+
+/// Helpers for the common pattern of storing a large value inside of an `Arc`, so that it can be cheaply cloned.
+impl<A> ReadOnlyLocalMutable<Arc<A>> {
+    /// This is the same as [`ReadOnlyMutable::signal_deref`](struct.ReadOnlyMutable.html#method.signal_deref).
+    #[inline]
+    pub fn signal_deref<B, F>(&self, f: F) -> LocalMutableSignalDeref<A, F> where F: FnMut(&A) -> B {
+        LocalMutableSignalDeref(LocalMutableSignalState::new(&self.0, 0), f)
+    }
+}
+
+/// Helpers for the common pattern of storing a large value inside of an `Arc`, so that it can be cheaply cloned.
+impl<A> LocalMutable<Arc<A>> {
+    /// Sets the value to `Arc::new(value)`.
+    #[inline]
+    pub fn set_arc(&self, value: A) {
+        self.set(Arc::new(value));
+    }
+
+    /// This is the same as [`Mutable::make_mut`](struct.Mutable.html#method.make_mut).
+    pub fn make_mut<B, F>(&self, f: F) -> B where A: Clone, F: FnOnce(&mut A) -> B {
+        let mut lock = self.lock_mut();
+        f(Arc::make_mut(&mut lock))
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct LocalMutableSignalDeref<A, F>(LocalMutableSignalState<Arc<A>>, F);
+
+impl<A, F> Unpin for LocalMutableSignalDeref<A, F> {}
+
+impl<A, B, F> Signal for LocalMutableSignalDeref<A, F> where F: FnMut(&A) -> B {
+    type Item = B;
+
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let state = &this.0;
+        let callback = &mut this.1;
+        state.poll_change(cx, |value| callback(value))
+    }
+}
+
+
+/// A single-threaded version of [`MutableLens`](struct.MutableLens.html).
+///
+/// It is created with [`LocalMutable::lens`](struct.LocalMutable.html#method.lens).
+pub struct LocalMutableLens<A, G, S> {
+    mutable: LocalMutable<A>,
+    get: Rc<G>,
+    set: Rc<S>,
+}
+
+impl<A, B, G, S> LocalMutableLens<A, G, S>
+    where G: Fn(&A) -> B,
+          S: Fn(&mut A, B) {
+
+    /// Returns the current value of the part.
+    #[inline]
+    pub fn get(&self) -> B {
+        (self.get)(&self.mutable.lock_ref())
+    }
+
+    /// Changes the part of the parent `LocalMutable`, which notifies all of the parent's Signals.
+    #[inline]
+    pub fn set(&self, value: B) {
+        (self.set)(&mut self.mutable.lock_mut(), value)
+    }
+
+    /// Returns a `Signal` of the part.
+    ///
+    /// It changes whenever the parent `LocalMutable` changes, even if the part didn't change,
+    /// so you might want to use `dedupe` on it.
+    #[inline]
+    pub fn signal(&self) -> LocalMutableLensSignal<A, G> {
+        LocalMutableLensSignal(LocalMutableSignalState::new(self.mutable.state(), 0), self.get.clone())
+    }
+}
+
+impl<A> LocalMutable<A> {
+    /// This is the same as [`Mutable::lens`](struct.Mutable.html#method.lens).
+    #[inline]
+    pub fn lens<B, G, S>(&self, get: G, set: S) -> LocalMutableLens<A, G, S>
+        where G: Fn(&A) -> B,
+              S: Fn(&mut A, B) {
+        LocalMutableLens {
+            mutable: self.clone(),
+            get: Rc::new(get),
+            set: Rc::new(set),
+        }
+    }
+}
+
+impl<A, G, S> Clone for LocalMutableLens<A, G, S> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            mutable: self.mutable.clone(),
+            get: self.get.clone(),
+            set: self.set.clone(),
+        }
+    }
+}
+
+impl<A, G, S> fmt::Debug for LocalMutableLens<A, G, S> where A: fmt::Debug {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("LocalMutableLens")
+            .field("mutable", &self.mutable)
+            .finish()
+    }
+}
+
+
+#[must_use = "Signals do nothing unless polled"]
+pub struct LocalMutableLensSignal<A, G>(LocalMutableSignalState<A>, Rc<G>);
+
+impl<A, G> Unpin for LocalMutableLensSignal<A, G> {}
+
+impl<A, B, G> Signal for LocalMutableLensSignal<A, G> where G: Fn(&A) -> B {
+    type Item = B;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let get = &self.1;
+        self.0.poll_change(cx, |value| get(value))
+    }
+}
+
+impl<A, G> fmt::Debug for LocalMutableLensSignal<A, G> where A: fmt::Debug {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_tuple("LocalMutableLensSignal")
+            .field(&self.0)
+            .finish()
+    }
+}
@@ -408,6 +408,45 @@ macro_rules! map_ref {
 }
 
 
+/// Combines multiple Signals into a `Signal` of a tuple of their values.
+///
+/// This is the same as `map_ref!`, except that instead of a closure it always clones the values
+/// into a tuple:
+///
+/// ```rust
+/// use futures_signals::combine;
+/// use futures_signals::signal::Mutable;
+///
+/// let name = Mutable::new("foo".to_string());
+/// let age = Mutable::new(5);
+/// let admin = Mutable::new(false);
+///
+/// // Signal<Item = (String, u32, bool)>
+/// let combined = combine!(name.signal_cloned(), age.signal(), admin.signal());
+/// ```
+///
+/// It supports up to 12 Signals.
+#[macro_export]
+macro_rules! combine {
+    ($($signal:expr),+ $(,)?) => {
+        $crate::__internal_combine!((), (a b c d e f g h i j k l), $($signal,)+)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __internal_combine {
+    (($(let $name:ident = $signal:expr,)*), ($($names:ident)*),) => {
+        $crate::map_ref! {
+            $(let $name = $signal),* => ($($name.clone(),)*)
+        }
+    };
+    (($($lets:tt)*), ($name:ident $($names:ident)*), $signal:expr, $($rest:tt)*) => {
+        $crate::__internal_combine!(($($lets)* let $name = $signal,), ($($names)*), $($rest)*)
+    };
+}
+
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __internal_map_lets {
@@ -436,3 +475,72 @@ macro_rules! __internal_map_split {
         $crate::__internal_map_split!($macro, ($($before)* $t), $($after)*)
     };
 }
+
+
+/// Creates a [`MutableLens`](signal/struct.MutableLens.html) for a field (or nested field) of a `Mutable`.
+///
+/// `project!(mutable => .foo.bar)` is the same as
+/// `mutable.lens(|x| x.foo.bar.clone(), |x, value| x.foo.bar = value)`
+///
+/// ```rust
+/// use futures_signals::project;
+/// use futures_signals::signal::Mutable;
+///
+/// struct Address {
+///     city: String,
+/// }
+///
+/// struct User {
+///     name: String,
+///     address: Address,
+/// }
+///
+/// let user = Mutable::new(User {
+///     name: "foo".to_string(),
+///     address: Address { city: "bar".to_string() },
+/// });
+///
+/// let city = project!(user => .address.city);
+///
+/// city.set("qux".to_string());
+///
+/// assert_eq!(user.lock_ref().address.city, "qux");
+/// ```
+#[macro_export]
+macro_rules! project {
+    ($mutable:expr => $(. $field:tt)+) => {
+        $crate::signal::Mutable::lens(&$mutable, |x| x$(.$field)+.clone(), |x, value| x$(.$field)+ = value)
+    };
+}
+
+
+/// Polls a `Signal` with a waker which does nothing, and asserts that it returns exactly
+/// the expected sequence of `Poll`s.
+///
+/// The `Signal` is polled once for each expected `Poll` (and it stops early if the
+/// `Signal` ends), so it never blocks, even if the `Signal` is `Pending`.
+///
+/// This is only available with the `test-util` feature.
+///
+/// ```rust
+/// use std::task::Poll;
+/// use futures_signals::assert_signal_eq;
+/// use futures_signals::signal::{self, SignalExt};
+///
+/// assert_signal_eq!(signal::from_values(vec![1, 2]).map(|x| x * 10), [
+///     Poll::Ready(Some(10)),
+///     Poll::Ready(Some(20)),
+///     Poll::Ready(None),
+/// ]);
+/// ```
+#[cfg(feature = "test-util")]
+#[macro_export]
+macro_rules! assert_signal_eq {
+    ($signal:expr, [$($poll:expr),* $(,)?]) => {
+        {
+            let expected = vec![$($poll),*];
+            let actual = $crate::internal::get_signal_polls($signal, expected.len());
+            assert_eq!(actual, expected);
+        }
+    };
+}
@@ -1,7 +1,9 @@
 mod macros;
+// The macros are exported with #[macro_export], so this doesn't re-export anything right now
+#[allow(unused_imports, unreachable_pub)]
 pub use self::macros::*;
 
-mod broadcaster;
+pub(crate) mod broadcaster;
 pub use self::broadcaster::*;
 
 mod channel;
@@ -10,5 +12,23 @@ pub use self::channel::*;
 mod mutable;
 pub use self::mutable::*;
 
+mod atomic;
+pub use self::atomic::*;
+
+mod local_mutable;
+pub use self::local_mutable::*;
+
+mod history;
+pub use self::history::*;
+
+mod queued_mutable;
+pub use self::queued_mutable::*;
+
+#[allow(clippy::module_inception)]
 mod signal;
 pub use self::signal::*;
+
+#[cfg(feature = "tokio")]
+mod watch;
+#[cfg(feature = "tokio")]
+pub use self::watch::*;
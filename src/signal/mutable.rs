@@ -1,97 +1,345 @@
 use super::Signal;
-use std;
-use std::fmt;
-use std::pin::Pin;
-use std::marker::Unpin;
-use std::ops::{Deref, DerefMut};
-// TODO use parking_lot ?
-use std::sync::{Arc, Weak, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
-// TODO use parking_lot ?
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::task::{Poll, Waker, Context};
+use core::fmt;
+use core::pin::Pin;
+use core::marker::Unpin;
+use core::ops::{Deref, DerefMut};
+use core::cmp::Reverse;
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use slab::Slab;
+use crate::sync::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard, AtomicBool, OnceLock};
+use crate::sync::Ordering;
+use core::task::{Poll, Waker, Context};
 use serde::{Serialize, Deserialize, Serializer, Deserializer};
+#[cfg(feature = "std")]
+use core::cell::RefCell;
+#[cfg(feature = "std")]
+use alloc::vec;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+
+
+#[cfg(all(feature = "std", not(loom)))]
+std::thread_local! {
+    // This is Some while inside of a `batch`
+    static BATCH: RefCell<Option<Vec<(i32, Waker)>>> = const { RefCell::new(None) };
+}
+
+// Each loom thread runs on the same OS thread, so it needs loom's thread_local
+#[cfg(loom)]
+loom::thread_local! {
+    static BATCH: RefCell<Option<Vec<(i32, Waker)>>> = RefCell::new(None);
+}
+
+// This is used by the Mutables instead of calling `wake` directly, so that `batch` can delay it
+#[inline]
+pub(crate) fn wake(waker: Waker) {
+    wake_with_priority(waker, 0);
+}
+
+// Without `std` there aren't any thread locals, so `batch` can't delay the wakeups
+#[cfg(not(feature = "std"))]
+#[inline]
+pub(crate) fn wake_with_priority(waker: Waker, _priority: i32) {
+    waker.wake();
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn wake_with_priority(waker: Waker, priority: i32) {
+    let mut waker = Some(waker);
+
+    // This uses try_with because a Mutable can be set or dropped while the thread locals are being
+    // destroyed, in which case it isn't inside of a batch and the task is woken up immediately
+    let _ = BATCH.try_with(|batch| {
+        if let Some(ref mut wakers) = *batch.borrow_mut() {
+            let waker = waker.take().unwrap();
+
+            // The same task is often waiting on multiple Mutables, so this only wakes it once.
+            //
+            // This is a linear scan, so a batch which wakes up N different tasks costs O(N^2).
+            // Wakers can't be hashed, and batches are usually small, so that's fine in practice.
+            match wakers.iter_mut().find(|(_, x)| x.will_wake(&waker)) {
+                Some((old_priority, _)) => {
+                    *old_priority = (*old_priority).max(priority);
+                },
+                None => {
+                    wakers.push((priority, waker));
+                },
+            }
+        }
+    });
+
+    if let Some(waker) = waker {
+        waker.wake();
+    }
+}
+
+#[cfg(feature = "std")]
+struct Batch;
+
+#[cfg(feature = "std")]
+impl Batch {
+    // This returns None when it is inside of another batch, so only the outer batch wakes up the tasks
+    fn start() -> Option<Self> {
+        BATCH.try_with(|batch| {
+            let mut batch = batch.borrow_mut();
+
+            if batch.is_none() {
+                *batch = Some(vec![]);
+                Some(Batch)
+
+            } else {
+                None
+            }
+        }).ok().flatten()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for Batch {
+    fn drop(&mut self) {
+        // This is done in Drop so that the tasks are still woken up if there is a panic
+        if let Some(mut wakers) = BATCH.try_with(|batch| batch.borrow_mut().take()).ok().flatten() {
+            // This is a stable sort, so tasks with the same priority are woken up in the order they were notified
+            wakers.sort_by_key(|(priority, _)| core::cmp::Reverse(*priority));
+
+            for (_, waker) in wakers {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Runs `f`, and delays waking up any tasks which are waiting on a `Mutable` (or
+/// `LocalMutable` or `MutableAtomic`) until `f` is finished.
+///
+/// This is useful when changing several `Mutable`s at once: each task is only
+/// woken up once, after all of the changes have been made, rather than once per change.
+///
+/// This only delays the *wakeups*, it doesn't make the changes atomic. Each change is
+/// visible as soon as it is made, so a task which is polled during the `batch` for
+/// some other reason (such as being woken up by something else, or running on another
+/// thread) can still see a state where only some of the `Mutable`s have changed.
+///
+/// The tasks are woken up in a stable order: the tasks with the highest priority
+/// (see [`signal_with_priority`](struct.ReadOnlyMutable.html#method.signal_with_priority))
+/// are woken up first, and tasks with the same priority are woken up in the order
+/// that they were first notified. A task which is waiting on multiple `Mutable`s uses
+/// its highest priority.
+///
+/// Calling `batch` inside of another `batch` does nothing, the tasks are woken up
+/// when the outer `batch` is finished.
+///
+/// This only applies to changes made on the current thread, changes made on other
+/// threads wake up their tasks immediately.
+///
+/// Without the `std` feature this just calls `f` and the tasks are woken up immediately,
+/// because there aren't any thread locals to store the tasks in.
+pub fn batch<A, F>(f: F) -> A where F: FnOnce() -> A {
+    #[cfg(feature = "std")]
+    let _batch = Batch::start();
+
+    f()
+}
+
+
+// Identifies a receiver in `Receivers`
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ReceiverKey {
+    pub(crate) index: usize,
+    order: (Reverse<i32>, u64),
+}
+
+
+// The receivers of a Mutable (or LocalMutable), in the order that they are notified.
+//
+// This is separate from the value's lock, so that a Signal can be dropped while the value is locked.
+#[derive(Debug)]
+pub(crate) struct Receivers<R> {
+    // Each Signal removes itself when it is dropped, so this never contains dead receivers
+    slab: Slab<R>,
+    // The keys of the receivers in the order that they are notified: highest priority first, and
+    // then in the order that they were created. The Slab can't be used for this because it reuses keys,
+    // so each receiver gets a sequence number instead.
+    order: BTreeMap<(Reverse<i32>, u64), usize>,
+    next_sequence: u64,
+}
+
+impl<R> Receivers<R> {
+    pub(crate) fn new() -> Self {
+        Self {
+            slab: Slab::new(),
+            order: BTreeMap::new(),
+            next_sequence: 0,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.slab.len()
+    }
+
+    pub(crate) fn insert(&mut self, priority: i32, receiver: R) -> ReceiverKey {
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+
+        let index = self.slab.insert(receiver);
+
+        // It goes after all of the receivers with the same (or higher) priority
+        let order = (Reverse(priority), sequence);
+        self.order.insert(order, index);
+
+        ReceiverKey { index, order }
+    }
+
+    pub(crate) fn remove(&mut self, key: ReceiverKey) {
+        self.slab.remove(key.index);
+        self.order.remove(&key.order);
+    }
+
+    // Returns the receivers (and their index) in the order that they should be notified
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (usize, &R)> {
+        self.order.values().map(move |&index| (index, &self.slab[index]))
+    }
+}
+
+
+#[derive(Debug)]
+struct MutableReceiver {
+    has_changed: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+    priority: i32,
+}
 
 
 #[derive(Debug)]
 struct MutableState<A> {
     value: A,
     senders: usize,
-    // TODO use HashMap or BTreeMap instead ?
-    receivers: Vec<Weak<MutableSignalState<A>>>,
+    receivers: Arc<Mutex<Receivers<Arc<MutableReceiver>>>>,
+    // This is shared by all of the `signal_arc` Signals, it is cleared whenever the value changes.
+    // It is only filled in by `signal_arc` and `get_arc`, so the other Mutables don't pay for it.
+    arc: OnceLock<Arc<A>>,
+    #[cfg(feature = "graph")]
+    node: crate::graph::Node,
 }
 
 impl<A> MutableState<A> {
+    // The key is only used by tracing
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
     fn notify(&mut self, has_changed: bool) {
-        self.receivers.retain(|receiver| {
-            if let Some(receiver) = receiver.upgrade() {
-                let mut lock = receiver.waker.lock().unwrap();
+        let receivers = self.receivers.lock();
 
-                if has_changed {
-                    // TODO verify that this is correct
-                    receiver.has_changed.store(true, Ordering::SeqCst);
-                }
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("Mutable::notify", receivers = receivers.len(), has_changed).entered();
 
-                if let Some(waker) = lock.take() {
-                    drop(lock);
-                    waker.wake();
-                }
+        if has_changed {
+            // This has exclusive access, so it doesn't need to lock
+            self.arc.take();
+        }
 
-                true
+        for (key, receiver) in receivers.iter() {
+            let mut lock = receiver.waker.lock();
 
-            } else {
-                false
+            if has_changed {
+                // This is always accessed while the RwLock is held, so it doesn't need to be stronger than Relaxed
+                receiver.has_changed.store(true, Ordering::Relaxed);
             }
-        });
+
+            if let Some(waker) = lock.take() {
+                drop(lock);
+
+                #[cfg(feature = "tracing")]
+                tracing::trace!(receiver = key, "waking receiver");
+
+                wake_with_priority(waker, receiver.priority);
+            }
+        }
+    }
+}
+
+
+impl<A: Clone> MutableState<A> {
+    fn arc(&self) -> Arc<A> {
+        self.arc.get_or_init(|| Arc::new(self.value.clone())).clone()
     }
 }
 
 
 #[derive(Debug)]
 struct MutableSignalState<A> {
-    has_changed: AtomicBool,
-    waker: Mutex<Option<Waker>>,
-    // TODO change this to Weak ?
+    key: ReceiverKey,
+    receiver: Arc<MutableReceiver>,
+    receivers: Arc<Mutex<Receivers<Arc<MutableReceiver>>>>,
     state: Arc<RwLock<MutableState<A>>>,
 }
 
 impl<A> MutableSignalState<A> {
-    fn new(mutable_state: &Arc<RwLock<MutableState<A>>>) -> Arc<Self> {
-        let state = Arc::new(MutableSignalState {
+    fn new(mutable_state: &Arc<RwLock<MutableState<A>>>, priority: i32) -> Self {
+        let receivers = mutable_state.read().receivers.clone();
+
+        let receiver = Arc::new(MutableReceiver {
             has_changed: AtomicBool::new(true),
             waker: Mutex::new(None),
-            state: mutable_state.clone(),
+            priority,
         });
 
-        {
-            let mut lock = mutable_state.write().unwrap();
+        let key = receivers.lock().insert(priority, receiver.clone());
 
-            if lock.senders != 0 {
-                lock.receivers.push(Arc::downgrade(&state));
-            }
+        MutableSignalState {
+            key,
+            receiver,
+            receivers,
+            state: mutable_state.clone(),
         }
-
-        state
     }
 
+    #[inline]
     fn poll_change<B, F>(&self, cx: &mut Context, f: F) -> Poll<Option<B>> where F: FnOnce(&A) -> B {
+        self.poll_change_state(cx, |state| f(&state.value))
+    }
+
+    fn poll_change_state<B, F>(&self, cx: &mut Context, f: F) -> Poll<Option<B>> where F: FnOnce(&MutableState<A>) -> B {
         // TODO is this correct ?
-        let lock = self.state.read().unwrap();
+        let lock = self.state.read();
+
+        #[cfg(feature = "graph")]
+        lock.node.polled();
 
-        // TODO verify that this is correct
-        if self.has_changed.swap(false, Ordering::SeqCst) {
-            Poll::Ready(Some(f(&lock.value)))
+        let receiver = &self.receiver;
+
+        // The RwLock is held, so this is ordered after the store in `notify`
+        if receiver.has_changed.swap(false, Ordering::Relaxed) {
+            #[cfg(feature = "tracing")]
+            tracing::trace!(receiver = self.key.index, "MutableSignal::poll_change is ready");
+
+            Poll::Ready(Some(f(&lock)))
 
         } else if lock.senders == 0 {
+            #[cfg(feature = "tracing")]
+            tracing::trace!(receiver = self.key.index, "MutableSignal::poll_change has ended");
+
             Poll::Ready(None)
 
         } else {
+            #[cfg(feature = "tracing")]
+            tracing::trace!(receiver = self.key.index, "MutableSignal::poll_change is pending");
+
             // TODO is this correct ?
-            *self.waker.lock().unwrap() = Some(cx.waker().clone());
+            *receiver.waker.lock() = Some(cx.waker().clone());
             Poll::Pending
         }
     }
 }
 
+impl<A> Drop for MutableSignalState<A> {
+    // This doesn't lock the value, so it doesn't deadlock if the value is already locked
+    #[inline]
+    fn drop(&mut self) {
+        self.receivers.lock().remove(self.key);
+    }
+}
+
 
 #[derive(Debug)]
 pub struct MutableLockMut<'a, A> where A: 'a {
@@ -146,39 +394,117 @@ pub struct ReadOnlyMutable<A>(Arc<RwLock<MutableState<A>>>);
 impl<A> ReadOnlyMutable<A> {
     // TODO return Result ?
     #[inline]
-    pub fn lock_ref(&self) -> MutableLockRef<A> {
+    pub fn lock_ref(&self) -> MutableLockRef<'_, A> {
         MutableLockRef {
-            lock: self.0.read().unwrap(),
+            lock: self.0.read(),
         }
     }
 
+    /// Returns a `Signal` which calls `f` with a reference to the current value (while holding the read
+    /// lock), and outputs whatever `f` returns.
+    ///
+    /// This doesn't require `A` to be `Copy` or `Clone`, so it is the efficient way to get a small part
+    /// of a large value:
+    ///
+    /// ```rust
+    /// use futures_signals::signal::Mutable;
+    ///
+    /// let names = Mutable::new(vec!["foo".to_string(), "bar".to_string()]);
+    ///
+    /// let len = names.signal_ref(|names| names.len());
+    /// ```
     #[inline]
     pub fn signal_ref<B, F>(&self, f: F) -> MutableSignalRef<A, F> where F: FnMut(&A) -> B {
-        MutableSignalRef(MutableSignalState::new(&self.0), f)
+        self.signal_ref_with_priority(0, f)
+    }
+
+    // This is used by QueuedMutable
+    #[inline]
+    pub(crate) fn signal_ref_with_priority<B, F>(&self, priority: i32, f: F) -> MutableSignalRef<A, F> where F: FnMut(&A) -> B {
+        MutableSignalRef(MutableSignalState::new(&self.0, priority), f)
+    }
+
+    /// Returns the number of Signals which currently exist for this `Mutable`.
+    ///
+    /// Signals are removed as soon as they are dropped, so this never counts dead Signals.
+    #[inline]
+    pub fn receiver_count(&self) -> usize {
+        self.0.read().receivers.lock().len()
+    }
+
+    /// Returns `true` if there are any Signals for this `Mutable`.
+    ///
+    /// This is useful to avoid calculating an expensive value when nobody is listening.
+    #[inline]
+    pub fn has_receivers(&self) -> bool {
+        self.receiver_count() != 0
     }
 }
 
 impl<A: Copy> ReadOnlyMutable<A> {
     #[inline]
     pub fn get(&self) -> A {
-        self.0.read().unwrap().value
+        self.0.read().value
     }
 
+    /// Returns a `Signal` which outputs the current value, and then the new value whenever it changes.
+    ///
+    /// When the value changes, the Signals are notified in the order that they were created. Use
+    /// [`signal_with_priority`](#method.signal_with_priority) to notify some Signals before others.
     #[inline]
     pub fn signal(&self) -> MutableSignal<A> {
-        MutableSignal(MutableSignalState::new(&self.0))
+        self.signal_with_priority(0)
+    }
+
+    /// This is the same as [`signal`](#method.signal), except the `Signal` has a `priority`.
+    ///
+    /// When the value changes, the Signals with a higher priority are notified first. Signals with
+    /// the same priority are notified in the order that they were created. The Signals created by
+    /// [`signal`](#method.signal) have a priority of `0`.
+    ///
+    /// This only decides the order in which the tasks are *woken up*, the order in which the tasks
+    /// actually run is decided by the executor (most executors run them in the order they were woken up).
+    #[inline]
+    pub fn signal_with_priority(&self, priority: i32) -> MutableSignal<A> {
+        MutableSignal(MutableSignalState::new(&self.0, priority))
     }
 }
 
 impl<A: Clone> ReadOnlyMutable<A> {
     #[inline]
     pub fn get_cloned(&self) -> A {
-        self.0.read().unwrap().value.clone()
+        self.0.read().value.clone()
     }
 
+    /// This is the same as [`signal`](#method.signal), except it clones the value.
     #[inline]
     pub fn signal_cloned(&self) -> MutableSignalCloned<A> {
-        MutableSignalCloned(MutableSignalState::new(&self.0))
+        self.signal_cloned_with_priority(0)
+    }
+
+    /// This is the same as [`signal_with_priority`](#method.signal_with_priority), except it clones the value.
+    #[inline]
+    pub fn signal_cloned_with_priority(&self, priority: i32) -> MutableSignalCloned<A> {
+        MutableSignalCloned(MutableSignalState::new(&self.0, priority))
+    }
+
+    /// Returns the current value inside of an `Arc`.
+    ///
+    /// The value is only cloned once per change: until the value changes again, this returns
+    /// the same `Arc` (and so do the Signals returned by [`signal_arc`](#method.signal_arc)).
+    #[inline]
+    pub fn get_arc(&self) -> Arc<A> {
+        self.0.read().arc()
+    }
+
+    /// Returns a `Signal` which outputs the value inside of an `Arc`.
+    ///
+    /// The value is only cloned once per change, and the `Arc` is shared by all of the
+    /// `signal_arc` Signals. So this is much faster than [`signal_cloned`](#method.signal_cloned)
+    /// when the value is large and there are multiple Signals.
+    #[inline]
+    pub fn signal_arc(&self) -> MutableSignalArc<A> {
+        MutableSignalArc(MutableSignalState::new(&self.0, 0))
     }
 }
 
@@ -191,7 +517,7 @@ impl<A> Clone for ReadOnlyMutable<A> {
 
 impl<A> fmt::Debug for ReadOnlyMutable<A> where A: fmt::Debug {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        let state = self.0.read().unwrap();
+        let state = self.0.read();
 
         fmt.debug_tuple("ReadOnlyMutable")
             .field(&state.value)
@@ -208,7 +534,10 @@ impl<A> Mutable<A> {
         Mutable(ReadOnlyMutable(Arc::new(RwLock::new(MutableState {
             value,
             senders: 1,
-            receivers: vec![],
+            receivers: Arc::new(Mutex::new(Receivers::new())),
+            arc: OnceLock::new(),
+            #[cfg(feature = "graph")]
+            node: crate::graph::Node::new(alloc::format!("Mutable<{}>", core::any::type_name::<A>())),
         }))))
     }
 
@@ -223,9 +552,9 @@ impl<A> Mutable<A> {
     }
 
     pub fn replace(&self, value: A) -> A {
-        let mut state = self.state().write().unwrap();
+        let mut state = self.state().write();
 
-        let value = std::mem::replace(&mut state.value, value);
+        let value = core::mem::replace(&mut state.value, value);
 
         state.notify(true);
 
@@ -233,10 +562,10 @@ impl<A> Mutable<A> {
     }
 
     pub fn replace_with<F>(&self, f: F) -> A where F: FnOnce(&mut A) -> A {
-        let mut state = self.state().write().unwrap();
+        let mut state = self.state().write();
 
         let new_value = f(&mut state.value);
-        let value = std::mem::replace(&mut state.value, new_value);
+        let value = core::mem::replace(&mut state.value, new_value);
 
         state.notify(true);
 
@@ -245,44 +574,65 @@ impl<A> Mutable<A> {
 
     pub fn swap(&self, other: &Mutable<A>) {
         // TODO can this dead lock ?
-        let mut state1 = self.state().write().unwrap();
-        let mut state2 = other.state().write().unwrap();
+        let mut state1 = self.state().write();
+        let mut state2 = other.state().write();
 
-        std::mem::swap(&mut state1.value, &mut state2.value);
+        core::mem::swap(&mut state1.value, &mut state2.value);
 
         state1.notify(true);
         state2.notify(true);
     }
 
     pub fn set(&self, value: A) {
-        let mut state = self.state().write().unwrap();
+        let mut state = self.state().write();
 
         state.value = value;
 
         state.notify(true);
     }
 
+    /// Calls `f` with the current value and `value` (while holding the write lock). If `f` returns
+    /// `true` then the current value is set to `value`.
+    ///
+    /// Use [`try_set_if`](#method.try_set_if) to find out whether the value was set.
+    #[inline]
     pub fn set_if<F>(&self, value: A, f: F) where F: FnOnce(&A, &A) -> bool {
-        let mut state = self.state().write().unwrap();
+        self.try_set_if(value, f);
+    }
+
+    /// This is the same as [`set_if`](#method.set_if), except it returns `true` if the value was set.
+    pub fn try_set_if<F>(&self, value: A, f: F) -> bool where F: FnOnce(&A, &A) -> bool {
+        let mut state = self.state().write();
 
         if f(&state.value, &value) {
             state.value = value;
             state.notify(true);
+            true
+
+        } else {
+            false
         }
     }
 
     // TODO lots of unit tests to verify that it only notifies when the object is mutated
     // TODO return Result ?
     // TODO should this inline ?
-    pub fn lock_mut(&self) -> MutableLockMut<A> {
+    pub fn lock_mut(&self) -> MutableLockMut<'_, A> {
         MutableLockMut {
             mutated: false,
-            lock: self.state().write().unwrap(),
+            lock: self.state().write(),
         }
     }
+
+    /// Runs `f` with mutable access to the value, and then notifies exactly once,
+    /// no matter how many changes `f` made.
+    pub fn transaction<B, F>(&self, f: F) -> B where F: FnOnce(&mut A) -> B {
+        let mut lock = self.lock_mut();
+        f(&mut lock)
+    }
 }
 
-impl<A> ::std::ops::Deref for Mutable<A> {
+impl<A> ::core::ops::Deref for Mutable<A> {
     type Target = ReadOnlyMutable<A>;
 
     #[inline]
@@ -296,11 +646,31 @@ impl<A: PartialEq> Mutable<A> {
     pub fn set_neq(&self, value: A) {
         self.set_if(value, PartialEq::ne);
     }
+
+    /// If the current value is equal to `expected`, then it is replaced with `new` and
+    /// the old value is returned as `Ok`.
+    ///
+    /// Otherwise the current value isn't changed, and a clone of it is returned as `Err`.
+    ///
+    /// This is done while holding the write lock, so nothing can change the value between
+    /// the comparison and the update.
+    pub fn compare_and_set(&self, expected: &A, new: A) -> Result<A, A> where A: Clone {
+        let mut state = self.state().write();
+
+        if state.value == *expected {
+            let old = core::mem::replace(&mut state.value, new);
+            state.notify(true);
+            Ok(old)
+
+        } else {
+            Err(state.value.clone())
+        }
+    }
 }
 
 impl<A> fmt::Debug for Mutable<A> where A: fmt::Debug {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        let state = self.state().read().unwrap();
+        let state = self.state().read();
 
         fmt.debug_tuple("Mutable")
             .field(&state.value)
@@ -311,7 +681,7 @@ impl<A> fmt::Debug for Mutable<A> where A: fmt::Debug {
 impl<T> Serialize for Mutable<T> where T: Serialize {
     #[inline]
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
-        self.state().read().unwrap().value.serialize(serializer)
+        self.state().read().value.serialize(serializer)
     }
 }
 
@@ -333,31 +703,29 @@ impl<T: Default> Default for Mutable<T> {
 impl<A> Clone for Mutable<A> {
     #[inline]
     fn clone(&self) -> Self {
-        self.state().write().unwrap().senders += 1;
+        self.state().write().senders += 1;
         Mutable(self.0.clone())
     }
 }
 
 impl<A> Drop for Mutable<A> {
     #[inline]
+    #[allow(clippy::len_zero)]
     fn drop(&mut self) {
-        let mut state = self.state().write().unwrap();
+        let mut state = self.state().write();
 
         state.senders -= 1;
 
-        if state.senders == 0 && state.receivers.len() > 0 {
+        if state.senders == 0 && state.receivers.lock().len() > 0 {
             state.notify(false);
-            // TODO is this necessary ?
-            state.receivers = vec![];
         }
     }
 }
 
 
-// TODO remove it from receivers when it's dropped
 #[derive(Debug)]
 #[must_use = "Signals do nothing unless polled"]
-pub struct MutableSignal<A>(Arc<MutableSignalState<A>>);
+pub struct MutableSignal<A>(MutableSignalState<A>);
 
 impl<A> Unpin for MutableSignal<A> {}
 
@@ -370,10 +738,9 @@ impl<A: Copy> Signal for MutableSignal<A> {
 }
 
 
-// TODO remove it from receivers when it's dropped
 #[derive(Debug)]
 #[must_use = "Signals do nothing unless polled"]
-pub struct MutableSignalRef<A, F>(Arc<MutableSignalState<A>>, F);
+pub struct MutableSignalRef<A, F>(MutableSignalState<A>, F);
 
 impl<A, F> Unpin for MutableSignalRef<A, F> {}
 
@@ -390,10 +757,9 @@ impl<A, B, F> Signal for MutableSignalRef<A, F> where F: FnMut(&A) -> B {
 
 
 // TODO it should have a single MutableSignal implementation for both Copy and Clone
-// TODO remove it from receivers when it's dropped
 #[derive(Debug)]
 #[must_use = "Signals do nothing unless polled"]
-pub struct MutableSignalCloned<A>(Arc<MutableSignalState<A>>);
+pub struct MutableSignalCloned<A>(MutableSignalState<A>);
 
 impl<A> Unpin for MutableSignalCloned<A> {}
 
@@ -405,3 +771,179 @@ impl<A: Clone> Signal for MutableSignalCloned<A> {
         self.0.poll_change(cx, |value| value.clone())
     }
 }
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct MutableSignalArc<A>(MutableSignalState<A>);
+
+impl<A> Unpin for MutableSignalArc<A> {}
+
+impl<A: Clone> Signal for MutableSignalArc<A> {
+    type Item = Arc<A>;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.0.poll_change_state(cx, |state| state.arc())
+    }
+}
+
+
+/// Helpers for the common pattern of storing a large value inside of an `Arc`, so that it can be cheaply cloned.
+impl<A> ReadOnlyMutable<Arc<A>> {
+    /// This is the same as [`signal_ref`](#method.signal_ref), except `f` is called with a reference to the value
+    /// which is inside of the `Arc`.
+    #[inline]
+    pub fn signal_deref<B, F>(&self, f: F) -> MutableSignalDeref<A, F> where F: FnMut(&A) -> B {
+        MutableSignalDeref(MutableSignalState::new(&self.0, 0), f)
+    }
+}
+
+/// Helpers for the common pattern of storing a large value inside of an `Arc`, so that it can be cheaply cloned.
+impl<A> Mutable<Arc<A>> {
+    /// Sets the value to `Arc::new(value)`.
+    #[inline]
+    pub fn set_arc(&self, value: A) {
+        self.set(Arc::new(value));
+    }
+
+    /// Calls `f` with a mutable reference to the value which is inside of the `Arc`, and then notifies.
+    ///
+    /// This uses `Arc::make_mut`, so the value is only cloned if the `Arc` is shared (e.g. if a Signal
+    /// still has the old `Arc`).
+    pub fn make_mut<B, F>(&self, f: F) -> B where A: Clone, F: FnOnce(&mut A) -> B {
+        let mut lock = self.lock_mut();
+        f(Arc::make_mut(&mut lock))
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct MutableSignalDeref<A, F>(MutableSignalState<Arc<A>>, F);
+
+impl<A, F> Unpin for MutableSignalDeref<A, F> {}
+
+impl<A, B, F> Signal for MutableSignalDeref<A, F> where F: FnMut(&A) -> B {
+    type Item = B;
+
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let state = &this.0;
+        let callback = &mut this.1;
+        state.poll_change(cx, |value| callback(value))
+    }
+}
+
+
+/// A two-way view into a part of a [`Mutable`](struct.Mutable.html).
+///
+/// It is created with [`Mutable::lens`](struct.Mutable.html#method.lens).
+pub struct MutableLens<A, G, S> {
+    mutable: Mutable<A>,
+    get: Arc<G>,
+    set: Arc<S>,
+}
+
+impl<A, B, G, S> MutableLens<A, G, S>
+    where G: Fn(&A) -> B,
+          S: Fn(&mut A, B) {
+
+    /// Returns the current value of the part.
+    #[inline]
+    pub fn get(&self) -> B {
+        (self.get)(&self.mutable.lock_ref())
+    }
+
+    /// Changes the part of the parent `Mutable`, which notifies all of the parent's Signals.
+    #[inline]
+    pub fn set(&self, value: B) {
+        (self.set)(&mut self.mutable.lock_mut(), value)
+    }
+
+    /// Returns a `Signal` of the part.
+    ///
+    /// It changes whenever the parent `Mutable` changes, even if the part didn't change,
+    /// so you might want to use `dedupe` on it.
+    #[inline]
+    pub fn signal(&self) -> MutableLensSignal<A, G> {
+        MutableLensSignal(MutableSignalState::new(self.mutable.state(), 0), self.get.clone())
+    }
+}
+
+impl<A> Mutable<A> {
+    /// Creates a [`MutableLens`](struct.MutableLens.html), which is a view into a part of `self`.
+    ///
+    /// `get` returns the part from the parent value, and `set` updates the parent value with a new part.
+    ///
+    /// ```rust
+    /// use futures_signals::signal::Mutable;
+    ///
+    /// struct State {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let state = Mutable::new(State { name: "foo".to_string(), age: 5 });
+    ///
+    /// let age = state.lens(|state| state.age, |state, age| state.age = age);
+    ///
+    /// age.set(10);
+    ///
+    /// assert_eq!(age.get(), 10);
+    /// assert_eq!(state.lock_ref().age, 10);
+    /// ```
+    #[inline]
+    pub fn lens<B, G, S>(&self, get: G, set: S) -> MutableLens<A, G, S>
+        where G: Fn(&A) -> B,
+              S: Fn(&mut A, B) {
+        MutableLens {
+            mutable: self.clone(),
+            get: Arc::new(get),
+            set: Arc::new(set),
+        }
+    }
+}
+
+impl<A, G, S> Clone for MutableLens<A, G, S> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            mutable: self.mutable.clone(),
+            get: self.get.clone(),
+            set: self.set.clone(),
+        }
+    }
+}
+
+impl<A, G, S> fmt::Debug for MutableLens<A, G, S> where A: fmt::Debug {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("MutableLens")
+            .field("mutable", &self.mutable)
+            .finish()
+    }
+}
+
+
+#[must_use = "Signals do nothing unless polled"]
+pub struct MutableLensSignal<A, G>(MutableSignalState<A>, Arc<G>);
+
+impl<A, G> Unpin for MutableLensSignal<A, G> {}
+
+impl<A, B, G> Signal for MutableLensSignal<A, G> where G: Fn(&A) -> B {
+    type Item = B;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let get = &self.1;
+        self.0.poll_change(cx, |value| get(value))
+    }
+}
+
+// TODO use derive
+impl<A, G> fmt::Debug for MutableLensSignal<A, G> where A: fmt::Debug {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_tuple("MutableLensSignal")
+            .field(&self.0)
+            .finish()
+    }
+}
@@ -0,0 +1,203 @@
+use super::Signal;
+use super::mutable::{Mutable, ReadOnlyMutable, MutableSignalRef};
+use core::fmt;
+use core::pin::Pin;
+use core::marker::Unpin;
+use alloc::collections::VecDeque;
+use core::task::{Poll, Context};
+use slab::Slab;
+use crate::sync::Mutex;
+
+
+#[derive(Debug)]
+struct QueuedMutableState<A> {
+    value: A,
+    capacity: Option<usize>,
+    // Each QueuedMutableSignal removes its queue when it is dropped, so this never contains dead queues.
+    // This is a Mutex so that the Signals can pop from their queue without notifying the other Signals.
+    queues: Mutex<Slab<VecDeque<A>>>,
+}
+
+impl<A: Clone> QueuedMutableState<A> {
+    fn push(&mut self, value: A) {
+        let capacity = self.capacity;
+
+        for (_key, queue) in self.queues.lock().iter_mut() {
+            if let Some(capacity) = capacity {
+                while queue.len() >= capacity {
+                    queue.pop_front();
+                }
+            }
+
+            queue.push_back(value.clone());
+        }
+
+        self.value = value;
+    }
+}
+
+
+/// This is like [`Mutable`](struct.Mutable.html), except its Signals output *every* change,
+/// in order, rather than only the most recent value.
+///
+/// Each Signal has its own queue of changes. The queue starts with the current value, and
+/// every call to `set` adds the new value to the end of every queue.
+///
+/// This is useful for things like a stream of commands, where skipping a value would be incorrect.
+///
+/// By default the queues have no limit, so if a Signal is not polled then its queue keeps growing.
+/// Use [`with_capacity`](#method.with_capacity) to limit the size of the queues.
+///
+/// It is built on top of a `Mutable`, so the Signals are woken up in the same way as a `Mutable`'s
+/// Signals (including [`batch`](fn.batch.html) and priorities).
+pub struct QueuedMutable<A>(Mutable<QueuedMutableState<A>>);
+
+impl<A> QueuedMutable<A> {
+    #[inline]
+    pub fn new(value: A) -> Self {
+        Self::new_with_capacity(value, None)
+    }
+
+    /// Creates a `QueuedMutable` where each Signal's queue contains at most `capacity` values.
+    ///
+    /// If a queue is full then the oldest value is discarded.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `capacity` is `0`.
+    #[inline]
+    pub fn with_capacity(value: A, capacity: usize) -> Self {
+        assert!(capacity > 0, "QueuedMutable capacity must be greater than 0");
+        Self::new_with_capacity(value, Some(capacity))
+    }
+
+    fn new_with_capacity(value: A, capacity: Option<usize>) -> Self {
+        QueuedMutable(Mutable::new(QueuedMutableState {
+            value,
+            capacity,
+            queues: Mutex::new(Slab::new()),
+        }))
+    }
+}
+
+impl<A: Clone> QueuedMutable<A> {
+    /// Sets the current value, and adds it to the end of every Signal's queue.
+    #[inline]
+    pub fn set(&self, value: A) {
+        self.0.lock_mut().push(value);
+    }
+
+    #[inline]
+    pub fn get_cloned(&self) -> A {
+        self.0.lock_ref().value.clone()
+    }
+
+    /// Returns a `Signal` which outputs the current value, and then every change after that.
+    #[inline]
+    pub fn signal_cloned(&self) -> QueuedMutableSignal<A> {
+        self.signal_cloned_with_priority(0)
+    }
+
+    /// This is the same as [`signal_cloned`](#method.signal_cloned), except the `Signal` has a `priority`.
+    ///
+    /// See [`Mutable::signal_with_priority`](struct.ReadOnlyMutable.html#method.signal_with_priority)
+    /// for more details.
+    pub fn signal_cloned_with_priority(&self, priority: i32) -> QueuedMutableSignal<A> {
+        let key = {
+            let state = self.0.lock_ref();
+
+            let mut queue = VecDeque::new();
+            queue.push_back(state.value.clone());
+
+            let mut queues = state.queues.lock();
+            queues.insert(queue)
+        };
+
+        QueuedMutableSignal {
+            key,
+            changed: self.0.signal_ref_with_priority(priority, ignore as fn(&QueuedMutableState<A>)),
+            state: self.0.read_only(),
+        }
+    }
+}
+
+impl<A: Copy> QueuedMutable<A> {
+    #[inline]
+    pub fn get(&self) -> A {
+        self.0.lock_ref().value
+    }
+
+    /// Returns a `Signal` which outputs the current value, and then every change after that.
+    #[inline]
+    pub fn signal(&self) -> QueuedMutableSignal<A> {
+        self.signal_cloned()
+    }
+
+    /// This is the same as [`signal`](#method.signal), except the `Signal` has a `priority`.
+    #[inline]
+    pub fn signal_with_priority(&self, priority: i32) -> QueuedMutableSignal<A> {
+        self.signal_cloned_with_priority(priority)
+    }
+}
+
+impl<A> Clone for QueuedMutable<A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        QueuedMutable(self.0.clone())
+    }
+}
+
+impl<A> fmt::Debug for QueuedMutable<A> where A: fmt::Debug {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let state = self.0.lock_ref();
+
+        fmt.debug_tuple("QueuedMutable")
+            .field(&state.value)
+            .finish()
+    }
+}
+
+
+#[inline]
+fn ignore<A>(_: &A) {}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct QueuedMutableSignal<A> {
+    key: usize,
+    // This is only used to find out when the QueuedMutable has changed (or has been dropped)
+    changed: MutableSignalRef<QueuedMutableState<A>, fn(&QueuedMutableState<A>)>,
+    state: ReadOnlyMutable<QueuedMutableState<A>>,
+}
+
+impl<A> Unpin for QueuedMutableSignal<A> {}
+
+impl<A> Signal for QueuedMutableSignal<A> {
+    type Item = A;
+
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        loop {
+            let value = self.state.lock_ref().queues.lock()[self.key].pop_front();
+
+            return match value {
+                Some(value) => Poll::Ready(Some(value)),
+                // The queue is empty, so wait for the QueuedMutable to change
+                None => match Pin::new(&mut self.changed).poll_change(cx) {
+                    // The value was pushed to the queue before it notified, so try again
+                    Poll::Ready(Some(())) => continue,
+                    // The queue is empty and there can't be any more changes
+                    Poll::Ready(None) => Poll::Ready(None),
+                    Poll::Pending => Poll::Pending,
+                },
+            };
+        }
+    }
+}
+
+impl<A> Drop for QueuedMutableSignal<A> {
+    #[inline]
+    fn drop(&mut self) {
+        self.state.lock_ref().queues.lock().remove(self.key);
+    }
+}
@@ -1,12 +1,24 @@
-use std::pin::Pin;
-use std::marker::Unpin;
-use std::future::Future;
-use std::task::{Context, Poll};
+use core::pin::Pin;
+use core::fmt::Debug;
+#[cfg(feature = "std")]
+use core::panic::Location;
+use core::marker::Unpin;
+use core::future::Future;
+use core::time::Duration;
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use core::task::{Context, Poll, Waker};
 use futures_core::stream::Stream;
 use futures_util::stream;
 use futures_util::stream::StreamExt;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
 
 use crate::internal::Map2;
+use crate::sync::Mutex;
+use crate::timer::Timer;
+use crate::signal::{Mutable, MutableSignal};
 use crate::signal_vec::{VecDiff, SignalVec};
 
 
@@ -20,6 +32,7 @@ pub trait Signal {
 
 
 // Copied from Future in the Rust stdlib
+#[allow(clippy::needless_lifetimes)]
 impl<'a, A> Signal for &'a mut A where A: ?Sized + Signal + Unpin {
     type Item = A::Item;
 
@@ -41,9 +54,9 @@ impl<A> Signal for Box<A> where A: ?Sized + Signal + Unpin {
 
 // Copied from Future in the Rust stdlib
 impl<A> Signal for Pin<A>
-    where A: Unpin + ::std::ops::DerefMut,
+    where A: Unpin + ::core::ops::DerefMut,
           A::Target: Signal {
-    type Item = <<A as ::std::ops::Deref>::Target as Signal>::Item;
+    type Item = <<A as ::core::ops::Deref>::Target as Signal>::Item;
 
     #[inline]
     fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
@@ -78,6 +91,50 @@ pub trait SignalExt: Signal {
         }
     }
 
+    /// Converts the `Signal` into a `Stream` which keeps every value in a queue until the
+    /// `Stream` is polled, rather than only keeping the most recent value.
+    ///
+    /// It returns a `Future` and a `Stream`. The `Future` must be spawned: it eagerly polls
+    /// `self` and puts every value into the queue, even if the `Stream` isn't being polled.
+    /// The `Stream` returns the values from the queue, oldest first.
+    ///
+    /// The queue can contain up to `capacity` values. If it is full (because the `Stream` is
+    /// polled slower than `self` changes) then `overflow` decides what happens to the new
+    /// value (see [`Overflow`](enum.Overflow.html)). With `Overflow::Panic` the `Future` panics.
+    ///
+    /// The `Future` stops when `self` ends, or when the `Stream` is dropped. The `Stream`
+    /// ends after it has returned all of the values, when `self` ends or the `Future` is dropped.
+    ///
+    /// Note that many Signals (such as `Mutable::signal`) already skip intermediate values
+    /// before they reach this queue: it can only keep the values which `self` outputs.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `capacity` is `0`.
+    #[inline]
+    fn buffered_stream(self, capacity: usize, overflow: Overflow) -> (BufferedStreamPump<Self>, BufferedStream<Self::Item>)
+        where Self: Sized {
+        assert!(capacity > 0, "buffered_stream capacity must be greater than 0");
+
+        let state = Arc::new(Mutex::new(BufferState {
+            queue: VecDeque::new(),
+            len: 0,
+            capacity,
+            overflow,
+            ended: false,
+            closed: false,
+            stream_waker: None,
+            pump_waker: None,
+        }));
+
+        let pump = BufferedStreamPump {
+            signal: self,
+            state: state.clone(),
+        };
+
+        (pump, BufferedStream { state })
+    }
+
     // TODO maybe remove this ?
     #[inline]
     fn to_future(self) -> SignalFuture<Self>
@@ -152,6 +209,11 @@ pub trait SignalExt: Signal {
         }
     }
 
+    /// Calls `callback` with a reference to every value of `self`, without changing it.
+    ///
+    /// This is useful for debugging, or for running side effects.
+    ///
+    /// It isn't called when `self` ends, use [`inspect_end`](#method.inspect_end) for that.
     #[inline]
     fn inspect<A>(self, callback: A) -> Inspect<Self, A>
         where A: FnMut(&Self::Item),
@@ -162,6 +224,189 @@ pub trait SignalExt: Signal {
         }
     }
 
+    /// Calls `callback` once when `self` ends, without changing any of the values.
+    ///
+    /// It can be combined with [`inspect`](#method.inspect) to see every value and the end.
+    #[inline]
+    fn inspect_end<A>(self, callback: A) -> InspectEnd<Self, A>
+        where A: FnOnce(),
+              Self: Sized {
+        InspectEnd {
+            signal: self,
+            callback: Some(callback),
+        }
+    }
+
+    /// Pairs every value of `self` with a version number, which starts at `0` and increases by `1`
+    /// every time `self` changes.
+    ///
+    /// The version is only increased when this `Signal` outputs a value, so it doesn't count any
+    /// values which were skipped by `self`.
+    #[inline]
+    fn versioned(self) -> Versioned<Self> where Self: Sized {
+        Versioned {
+            signal: self,
+            version: 0,
+        }
+    }
+
+    /// Calls `callback` with the previous value and the current value of `self`, and outputs whatever
+    /// `callback` returns.
+    ///
+    /// The previous value is `None` for the first value.
+    ///
+    /// ```rust
+    /// # use futures_signals::signal::{always, SignalExt};
+    /// # let input = always(5);
+    /// let delta = input.diff(|old, new| new - old.unwrap_or(new));
+    /// ```
+    #[inline]
+    fn diff<A, F>(self, callback: F) -> Diff<Self, F>
+        where F: FnMut(Option<&Self::Item>, &Self::Item) -> A,
+              Self: Sized {
+        Diff {
+            signal: self,
+            old: None,
+            callback,
+        }
+    }
+
+    /// Outputs the previous value and the current value of `self`.
+    ///
+    /// The previous value is `None` for the first value.
+    #[inline]
+    fn pairwise(self) -> Pairwise<Self>
+        where Self::Item: Clone,
+              Self: Sized {
+        Pairwise {
+            signal: self,
+            old: None,
+        }
+    }
+
+    /// Outputs the most recent `size` values of `self` (oldest first), every time `self` changes.
+    ///
+    /// Until `self` has changed `size` times, it contains fewer than `size` values.
+    ///
+    /// # Panics
+    ///
+    /// If `size` is `0`.
+    #[inline]
+    fn window(self, size: usize) -> Window<Self>
+        where Self::Item: Clone,
+              Self: Sized {
+        assert!(size > 0, "window size must be greater than 0");
+
+        Window {
+            signal: self,
+            values: VecDeque::with_capacity(size),
+            size,
+        }
+    }
+
+    /// Ignores the first `amount` values of `self`, and then outputs the rest of the values.
+    ///
+    /// This is commonly used with `skip(1)` to ignore the initial value of a `Mutable`.
+    #[inline]
+    fn skip(self, amount: usize) -> Skip<Self> where Self: Sized {
+        Skip {
+            signal: self,
+            amount,
+        }
+    }
+
+    /// Ignores values of `self` while `callback` returns `true`. After `callback` returns `false`,
+    /// it outputs the rest of the values (without calling `callback` again).
+    #[inline]
+    fn skip_while<F>(self, callback: F) -> SkipWhile<Self, F>
+        where F: FnMut(&Self::Item) -> bool,
+              Self: Sized {
+        SkipWhile {
+            signal: self,
+            callback: Some(callback),
+        }
+    }
+
+    /// Outputs the first `amount` values of `self`, and then ends.
+    #[inline]
+    fn take(self, amount: usize) -> Take<Self> where Self: Sized {
+        Take {
+            signal: if amount == 0 { None } else { Some(self) },
+            amount,
+        }
+    }
+
+    /// Outputs values of `self` while `callback` returns `true`. When `callback` returns `false`,
+    /// it ends (without outputting that value).
+    #[inline]
+    fn take_while<F>(self, callback: F) -> TakeWhile<Self, F>
+        where F: FnMut(&Self::Item) -> bool,
+              Self: Sized {
+        TakeWhile {
+            signal: Some(self),
+            callback,
+        }
+    }
+
+    /// Outputs values of `self` until `future` completes, and then ends.
+    ///
+    /// This is useful for stopping a `Signal` when a shutdown notification is received.
+    #[inline]
+    fn take_until<F>(self, future: F) -> TakeUntil<Self, F>
+        where F: Future,
+              Self: Sized {
+        TakeUntil {
+            signal: Some(self),
+            future: Some(future),
+        }
+    }
+
+    /// Outputs values of `self` until `callback` returns `true`. It outputs the value for which
+    /// `callback` returned `true`, and then it ends.
+    ///
+    /// This is useful for watching a `Signal` until it reaches a final state.
+    #[inline]
+    fn stop_if<F>(self, callback: F) -> StopIf<Self, F>
+        where F: FnMut(&Self::Item) -> bool,
+              Self: Sized {
+        StopIf {
+            signal: Some(self),
+            callback,
+        }
+    }
+
+    /// Adds `self` to the [`graph`](../graph/index.html) as a node called `name`.
+    ///
+    /// The node is removed from the graph when the returned `Signal` is dropped.
+    #[cfg(feature = "graph")]
+    #[inline]
+    fn named<A>(self, name: A) -> Named<Self>
+        where A: Into<alloc::string::String>,
+              Self: Sized {
+        Named {
+            signal: self,
+            node: crate::graph::Node::new(name.into()),
+        }
+    }
+
+    /// Prints every value of `self` (and when `self` ends) to stderr, along with the
+    /// file and line where `debug` was called. The values are not changed.
+    ///
+    /// This is useful for figuring out why a `Signal` isn't changing.
+    ///
+    /// This is only available with the `std` feature.
+    #[cfg(feature = "std")]
+    #[inline]
+    #[track_caller]
+    fn debug(self) -> SignalDebug<Self>
+        where Self::Item: Debug,
+              Self: Sized {
+        SignalDebug {
+            signal: self,
+            location: Location::caller(),
+        }
+    }
+
     /// Creates a `Signal` which uses a closure to transform the value.
     ///
     /// This is exactly the same as `map`, except:
@@ -271,6 +516,62 @@ pub trait SignalExt: Signal {
         }
     }
 
+    /// This is similar to [`map_future`](#method.map_future), except it outputs an
+    /// [`AsyncState`](enum.AsyncState.html) which describes the `Future` which is currently running.
+    ///
+    /// When the input changes, the old `Future` is always dropped *before* `callback` is called to
+    /// create the new `Future`, so the two `Future`s never exist at the same time.
+    ///
+    /// The output is:
+    ///
+    /// * `AsyncState::Pending` when a new `Future` starts running.
+    ///
+    /// * `AsyncState::Cancelled` when a `Future` is dropped because the input changed before it finished.
+    ///   This is immediately followed by `AsyncState::Pending` for the new `Future`.
+    ///
+    /// * `AsyncState::Ready(value)` when a `Future` finishes.
+    ///
+    /// If the input changes multiple times while a `Future` is running, only the most recent value is used.
+    #[inline]
+    fn map_async_latest<A, B>(self, callback: B) -> MapAsyncLatest<Self, A, B>
+        where A: Future,
+              B: FnMut(Self::Item) -> A,
+              Self: Sized {
+        MapAsyncLatest {
+            signal: Some(self),
+            future: None,
+            callback,
+            next: None,
+        }
+    }
+
+    /// This is similar to [`map_future`](#method.map_future), except the `Future`s are never cancelled.
+    ///
+    /// Every value is passed to `callback`, and the `Future`s are run one at a time, in the same order as
+    /// the values. If the input changes while a `Future` is running, the new value waits until the `Future`
+    /// has finished.
+    ///
+    /// This is useful for side effects (such as saving every change) where cancelling a `Future` would lose data.
+    ///
+    /// The output is `None` until the first `Future` finishes, and then it is `Some` with the output of the
+    /// most recent `Future` which finished.
+    ///
+    /// Because values are never skipped, the queue can grow without limit if the input changes faster
+    /// than the `Future`s can finish.
+    #[inline]
+    fn map_async_queued<A, B>(self, callback: B) -> MapAsyncQueued<Self, A, B>
+        where A: Future,
+              B: FnMut(Self::Item) -> A,
+              Self: Sized {
+        MapAsyncQueued {
+            signal: Some(self),
+            future: None,
+            callback,
+            queue: VecDeque::new(),
+            first: true,
+        }
+    }
+
     /// Creates a `Signal` which uses a closure to filter and transform the value.
     ///
     /// When the output `Signal` is spawned:
@@ -332,6 +633,58 @@ pub trait SignalExt: Signal {
         }
     }
 
+    /// Only outputs values while `gate` is `true`.
+    ///
+    /// While `gate` is `false` the changes to `self` are held back, and when `gate` becomes `true` again
+    /// the most recent value which was held back is output.
+    ///
+    /// Until `gate` has a value it is treated as `false`. If `gate` ends while it is `false`, then the output `Signal` ends.
+    ///
+    /// # Examples
+    ///
+    /// Don't update the page while a modal dialog is open:
+    ///
+    /// ```rust
+    /// # use futures_signals::signal::{always, Mutable, SignalExt, SignalBoolExt};
+    /// # let input = always(1);
+    /// let is_modal_open = Mutable::new(false);
+    ///
+    /// let output = input.filter_by(is_modal_open.signal().not());
+    /// ```
+    #[inline]
+    fn filter_by<A>(self, gate: A) -> FilterBy<Self, A>
+        where A: Signal<Item = bool>,
+              Self: Sized {
+        FilterBy {
+            signal: Some(self),
+            gate: Some(gate),
+            is_open: false,
+            value: None,
+        }
+    }
+
+    /// Returns a `Signal` which can be paused and resumed by using the returned [`PauseHandle`](struct.PauseHandle.html).
+    ///
+    /// While it is paused, the changes to `self` are held back, and when it is resumed the most recent
+    /// change is output. It starts out resumed.
+    ///
+    /// If all of the `PauseHandle`s are dropped while it is paused, then the output `Signal` ends.
+    ///
+    /// This is useful for Signals which shouldn't update while they're hidden (such as a background tab).
+    #[inline]
+    fn pausable(self) -> (Pausable<Self>, PauseHandle)
+        where Self: Sized {
+        let handle = PauseHandle {
+            is_paused: Mutable::new(false),
+        };
+
+        let signal = Pausable {
+            signal: self.filter_by(handle.is_paused.signal().not()),
+        };
+
+        (signal, handle)
+    }
+
     /// Creates a `Signal` which flattens `self`.
     ///
     /// When the output `Signal` is spawned:
@@ -364,6 +717,21 @@ pub trait SignalExt: Signal {
         }
     }
 
+    /// Calls `callback` with each value of `self`, and outputs the values of the `Signal` which
+    /// `callback` returns.
+    ///
+    /// When `self` changes, the old inner `Signal` is dropped and it switches to the new inner
+    /// `Signal`, so it always follows the inner `Signal` for the most recent value of `self`.
+    ///
+    /// This is the same as `.map(callback).flatten()`, and it is similar to `and_then` in other
+    /// libraries:
+    ///
+    /// ```rust
+    /// # use futures_signals::signal::{Mutable, SignalExt};
+    /// # let user_id = Mutable::new(0);
+    /// # let users = vec![Mutable::new("foo")];
+    /// let user_name = user_id.signal().switch(move |id| users[id].signal());
+    /// ```
     #[inline]
     fn switch<A, B>(self, callback: B) -> Switch<Self, A, B>
         where A: Signal,
@@ -411,6 +779,7 @@ pub trait SignalExt: Signal {
     }
 
     #[inline]
+    #[allow(clippy::redundant_field_names)]
     fn wait_for(self, value: Self::Item) -> WaitFor<Self>
         where Self::Item: PartialEq,
               Self: Sized {
@@ -427,399 +796,2201 @@ pub trait SignalExt: Signal {
         }
     }
 
+    /// Smoothly transitions between the values of `self`.
+    ///
+    /// The first value is output immediately. After that, whenever `self` changes, it
+    /// outputs values which are interpolated from the current output to the new value
+    /// over `duration`, calling `interpolate(from, to, t)` with `t` going from `0.0` to `1.0`.
+    ///
+    /// The time comes from `timer`, which is only used while it is transitioning. While it is
+    /// transitioning it outputs a new value every 16 milliseconds (roughly 60 frames per second).
+    #[inline]
+    fn tween<T, F>(self, timer: T, duration: Duration, interpolate: F) -> Tween<Self, T, F>
+        where T: Timer,
+              F: FnMut(&Self::Item, &Self::Item, f64) -> Self::Item,
+              Self::Item: Clone,
+              Self: Sized {
+        Tween {
+            signal: Some(self),
+            timer,
+            sleep: None,
+            duration,
+            interpolate,
+            from: None,
+            current: None,
+            target: None,
+            start_time: None,
+        }
+    }
+
+    /// Outputs at most `max` changes within any `interval`.
+    ///
+    /// Changes are output immediately until the limit is reached. After that, the changes are held back
+    /// until the oldest change is more than `interval` old, and then the most recent change is output.
+    /// The most recent value is always output eventually, but the intermediate values might be skipped.
+    ///
+    /// The time comes from `timer`.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `max` is `0`.
+    #[inline]
+    fn rate_limit<T>(self, timer: T, max: usize, interval: Duration) -> RateLimit<Self, T>
+        where T: Timer,
+              Self: Sized {
+        assert!(max > 0, "rate_limit max must be greater than 0");
+
+        RateLimit {
+            signal: Some(self),
+            sleep: None,
+            timer,
+            max,
+            interval,
+            sent: VecDeque::new(),
+            value: None,
+        }
+    }
+
     /// A convenience for calling `Signal::poll_change` on `Unpin` types.
     #[inline]
     fn poll_change_unpin(&mut self, cx: &mut Context) -> Poll<Option<Self::Item>> where Self: Unpin + Sized {
         Pin::new(self).poll_change(cx)
     }
+
+    /// Polls `self` once with a waker which does nothing, without blocking.
+    ///
+    /// * `None` means that there is no new value.
+    /// * `Some(Some(value))` means that there is a new value.
+    /// * `Some(None)` means that `self` has ended.
+    ///
+    /// Because the waker does nothing, `self` must be polled again to check for changes. This is
+    /// useful for immediate-mode render loops which poll every frame. If you have a `Context`,
+    /// use `poll_change_unpin` instead.
+    #[inline]
+    fn poll_now(&mut self) -> Option<Option<Self::Item>> where Self: Unpin + Sized {
+        let cx = &mut Context::from_waker(futures_util::task::noop_waker_ref());
+
+        match self.poll_change_unpin(cx) {
+            Poll::Ready(value) => Some(value),
+            Poll::Pending => None,
+        }
+    }
 }
 
 // TODO why is this ?Sized
 impl<T: ?Sized> SignalExt for T where T: Signal {}
 
 
-// TODO make this into a method later
-#[inline]
-pub fn not<A>(signal: A) -> impl Signal<Item = bool>
-    where A: Signal<Item = bool> {
-    signal.map(|x| !x)
-}
+/// Methods for Signals which contain a `Result`.
+pub trait SignalResultExt<T, E>: Signal<Item = Result<T, E>> {
+    /// Calls `callback` with the `Ok` values, and leaves `Err` values unchanged.
+    #[inline]
+    fn map_ok<A, F>(self, callback: F) -> MapOk<Self, F>
+        where F: FnMut(T) -> A,
+              Self: Sized {
+        MapOk {
+            signal: self,
+            callback,
+        }
+    }
 
-// TODO make this into a method later
-// TODO use short-circuiting if the left signal returns false ?
-#[inline]
-pub fn and<A, B>(left: A, right: B) -> impl Signal<Item = bool>
-    where A: Signal<Item = bool>,
-          B: Signal<Item = bool> {
-    Map2::new(left, right, |a, b| *a && *b)
-}
+    /// Calls `callback` with the `Err` values, and leaves `Ok` values unchanged.
+    #[inline]
+    fn map_err<A, F>(self, callback: F) -> MapErr<Self, F>
+        where F: FnMut(E) -> A,
+              Self: Sized {
+        MapErr {
+            signal: self,
+            callback,
+        }
+    }
 
-// TODO make this into a method later
-// TODO use short-circuiting if the left signal returns true ?
-#[inline]
-pub fn or<A, B>(left: A, right: B) -> impl Signal<Item = bool>
-    where A: Signal<Item = bool>,
-          B: Signal<Item = bool> {
-    Map2::new(left, right, |a, b| *a || *b)
+    /// Ignores `Ok` values for which `callback` returns `false`. `Err` values are always output.
+    ///
+    /// If a value is ignored, then the output `Signal` keeps its old value. If the first value is
+    /// ignored, then the output `Signal` has no value until a value isn't ignored.
+    #[inline]
+    fn try_filter<F>(self, callback: F) -> TryFilter<Self, F>
+        where F: FnMut(&T) -> bool,
+              Self: Sized {
+        TryFilter {
+            signal: self,
+            callback,
+        }
+    }
 }
 
+impl<A: ?Sized, T, E> SignalResultExt<T, E> for A where A: Signal<Item = Result<T, E>> {}
 
-#[derive(Debug)]
-#[must_use = "Signals do nothing unless polled"]
-pub struct FromFuture<A> {
-    // TODO is this valid with pinned types ?
-    future: Option<A>,
-    first: bool,
-}
 
-impl<A> Unpin for FromFuture<A> where A: Unpin {}
+/// Methods for Signals which contain an `Option`.
+pub trait SignalOptionExt<T>: Signal<Item = Option<T>> {
+    /// Calls `callback` with the `Some` values, and leaves `None` values unchanged.
+    #[inline]
+    fn map_some<A, F>(self, callback: F) -> MapSome<Self, F>
+        where F: FnMut(T) -> A,
+              Self: Sized {
+        MapSome {
+            signal: self,
+            callback,
+        }
+    }
 
-impl<A> Signal for FromFuture<A> where A: Future {
-    type Item = Option<A::Output>;
+    /// Outputs `default` whenever the value is `None`.
+    #[inline]
+    fn unwrap_or(self, default: T) -> UnwrapOr<Self, T>
+        where T: Clone,
+              Self: Sized {
+        UnwrapOr {
+            signal: self,
+            default,
+        }
+    }
 
-    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
-        unsafe_project!(self => {
+    /// Converts `Some(Some(value))` into `Some(value)`, and both `Some(None)` and `None` into `None`.
+    #[inline]
+    fn flatten_option(self) -> FlattenOption<Self>
+        where Self: Sized {
+        FlattenOption {
+            signal: self,
+        }
+    }
+}
+
+impl<A: ?Sized, T> SignalOptionExt<T> for A where A: Signal<Item = Option<T>> {}
+
+
+/// Methods for Signals which contain a `bool`.
+///
+/// ```rust
+/// use futures_signals::signal::{Mutable, SignalBoolExt};
+///
+/// let is_hidden = Mutable::new(false);
+/// let is_disabled = Mutable::new(false);
+///
+/// let is_visible = is_hidden.signal().not();
+/// let is_inactive = is_hidden.signal().or(is_disabled.signal());
+/// ```
+pub trait SignalBoolExt: Signal<Item = bool> {
+    /// Outputs `true` when the value is `false`, and `false` when the value is `true`.
+    #[inline]
+    fn not(self) -> Not<Self>
+        where Self: Sized {
+        Not {
+            signal: self,
+        }
+    }
+
+    /// Outputs `true` when both `self` and `other` are `true`.
+    ///
+    /// It waits until both Signals have a value, and then it changes whenever either Signal changes.
+    // TODO use short-circuiting if the left signal returns false ?
+    #[inline]
+    fn and<B>(self, other: B) -> And<Self, B>
+        where B: Signal<Item = bool>,
+              Self: Sized {
+        And {
+            inner: Map2::new(self, other, (|a, b| *a && *b) as fn(&mut bool, &mut bool) -> bool),
+        }
+    }
+
+    /// Outputs `true` when either `self` or `other` is `true`.
+    ///
+    /// It waits until both Signals have a value, and then it changes whenever either Signal changes.
+    // TODO use short-circuiting if the left signal returns true ?
+    #[inline]
+    fn or<B>(self, other: B) -> Or<Self, B>
+        where B: Signal<Item = bool>,
+              Self: Sized {
+        Or {
+            inner: Map2::new(self, other, (|a, b| *a || *b) as fn(&mut bool, &mut bool) -> bool),
+        }
+    }
+}
+
+impl<A: ?Sized> SignalBoolExt for A where A: Signal<Item = bool> {}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Not<A> {
+    signal: A,
+}
+
+impl<A> Unpin for Not<A> where A: Unpin {}
+
+impl<A> Signal for Not<A> where A: Signal<Item = bool> {
+    type Item = bool;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+        });
+
+        signal.poll_change(cx).map(|opt| opt.map(|value| !value))
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct And<A, B> where A: Signal<Item = bool>, B: Signal<Item = bool> {
+    inner: Map2<A, B, fn(&mut bool, &mut bool) -> bool>,
+}
+
+impl<A, B> Unpin for And<A, B> where A: Signal<Item = bool> + Unpin, B: Signal<Item = bool> + Unpin {}
+
+impl<A, B> Signal for And<A, B>
+    where A: Signal<Item = bool>,
+          B: Signal<Item = bool> {
+    type Item = bool;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin inner,
+        });
+
+        inner.poll_change(cx)
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Or<A, B> where A: Signal<Item = bool>, B: Signal<Item = bool> {
+    inner: Map2<A, B, fn(&mut bool, &mut bool) -> bool>,
+}
+
+impl<A, B> Unpin for Or<A, B> where A: Signal<Item = bool> + Unpin, B: Signal<Item = bool> + Unpin {}
+
+impl<A, B> Signal for Or<A, B>
+    where A: Signal<Item = bool>,
+          B: Signal<Item = bool> {
+    type Item = bool;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin inner,
+        });
+
+        inner.poll_change(cx)
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct MapSome<A, F> {
+    signal: A,
+    callback: F,
+}
+
+impl<A, F> Unpin for MapSome<A, F> where A: Unpin {}
+
+impl<A, B, T, F> Signal for MapSome<A, F>
+    where A: Signal<Item = Option<T>>,
+          F: FnMut(T) -> B {
+    type Item = Option<B>;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut callback,
+        });
+
+        signal.poll_change(cx).map(|opt| opt.map(|value| value.map(&mut *callback)))
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct UnwrapOr<A, T> {
+    signal: A,
+    default: T,
+}
+
+impl<A, T> Unpin for UnwrapOr<A, T> where A: Unpin {}
+
+impl<A, T> Signal for UnwrapOr<A, T>
+    where A: Signal<Item = Option<T>>,
+          T: Clone {
+    type Item = T;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut default,
+        });
+
+        signal.poll_change(cx).map(|opt| opt.map(|value| value.unwrap_or_else(|| default.clone())))
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct FlattenOption<A> {
+    signal: A,
+}
+
+impl<A> Unpin for FlattenOption<A> where A: Unpin {}
+
+impl<A, T> Signal for FlattenOption<A>
+    where A: Signal<Item = Option<Option<T>>> {
+    type Item = Option<T>;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+        });
+
+        signal.poll_change(cx).map(|opt| opt.map(|value| value.flatten()))
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct MapOk<A, F> {
+    signal: A,
+    callback: F,
+}
+
+impl<A, F> Unpin for MapOk<A, F> where A: Unpin {}
+
+impl<A, B, T, E, F> Signal for MapOk<A, F>
+    where A: Signal<Item = Result<T, E>>,
+          F: FnMut(T) -> B {
+    type Item = Result<B, E>;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut callback,
+        });
+
+        signal.poll_change(cx).map(|opt| opt.map(|value| value.map(&mut *callback)))
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct MapErr<A, F> {
+    signal: A,
+    callback: F,
+}
+
+impl<A, F> Unpin for MapErr<A, F> where A: Unpin {}
+
+impl<A, B, T, E, F> Signal for MapErr<A, F>
+    where A: Signal<Item = Result<T, E>>,
+          F: FnMut(E) -> B {
+    type Item = Result<T, B>;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut callback,
+        });
+
+        signal.poll_change(cx).map(|opt| opt.map(|value| value.map_err(&mut *callback)))
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct TryFilter<A, F> {
+    signal: A,
+    callback: F,
+}
+
+impl<A, F> Unpin for TryFilter<A, F> where A: Unpin {}
+
+impl<A, T, E, F> Signal for TryFilter<A, F>
+    where A: Signal<Item = Result<T, E>>,
+          F: FnMut(&T) -> bool {
+    type Item = Result<T, E>;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut callback,
+        });
+
+        loop {
+            return match signal.as_mut().poll_change(cx) {
+                Poll::Ready(Some(Ok(value))) => {
+                    if callback(&value) {
+                        Poll::Ready(Some(Ok(value)))
+
+                    } else {
+                        continue;
+                    }
+                },
+                Poll::Ready(Some(Err(error))) => Poll::Ready(Some(Err(error))),
+                Poll::Ready(None) => Poll::Ready(None),
+                Poll::Pending => Poll::Pending,
+            }
+        }
+    }
+}
+
+
+/// Polls `signal` once and returns its value, or `None` if the value isn't available yet
+/// (or if `signal` has ended).
+///
+/// This does not actually block, and it does not need an executor: the `signal` is polled with
+/// a waker which does nothing, so it can't be notified of future changes.
+///
+/// This is useful for getting the current value of a `Signal` in synchronous code (such as
+/// `Display` impls). It works well with Signals which are always immediately ready when they are
+/// first polled (such as `Mutable::signal`), but a `Signal` which is asynchronous (such as
+/// `map_future`) will usually return `None`.
+pub fn block_on_first<A>(signal: A) -> Option<A::Item> where A: Signal {
+    futures_util::pin_mut!(signal);
+    signal.poll_now().flatten()
+}
+
+
+/// This is the same as [`SignalBoolExt::not`](trait.SignalBoolExt.html#method.not).
+#[inline]
+pub fn not<A>(signal: A) -> impl Signal<Item = bool>
+    where A: Signal<Item = bool> {
+    signal.not()
+}
+
+/// This is the same as [`SignalBoolExt::and`](trait.SignalBoolExt.html#method.and).
+#[inline]
+pub fn and<A, B>(left: A, right: B) -> impl Signal<Item = bool>
+    where A: Signal<Item = bool>,
+          B: Signal<Item = bool> {
+    left.and(right)
+}
+
+/// This is the same as [`SignalBoolExt::or`](trait.SignalBoolExt.html#method.or).
+#[inline]
+pub fn or<A, B>(left: A, right: B) -> impl Signal<Item = bool>
+    where A: Signal<Item = bool>,
+          B: Signal<Item = bool> {
+    left.or(right)
+}
+
+
+/// Combines two Signals into a `Signal` of a tuple of their values.
+///
+/// It waits until both Signals have a value, and then it changes whenever either Signal changes.
+/// It ends when both Signals have ended.
+///
+/// More than two Signals can be combined by nesting `zip`, or by using the `combine!` macro.
+///
+/// If you want to calculate something from the values, `map_ref!` is more efficient, because it
+/// doesn't need to clone the values.
+#[inline]
+pub fn zip<A, B>(left: A, right: B) -> impl Signal<Item = (A::Item, B::Item)>
+    where A: Signal,
+          B: Signal,
+          A::Item: Clone,
+          B::Item: Clone {
+    Map2::new(left, right, |a, b| (a.clone(), b.clone()))
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Merge<A, B> {
+    left: Option<A>,
+    right: Option<B>,
+    prefer_left: bool,
+}
+
+impl<A, B> Unpin for Merge<A, B> where A: Unpin, B: Unpin {}
+
+impl<A, B> Signal for Merge<A, B>
+    where A: Signal,
+          B: Signal<Item = A::Item> {
+    type Item = A::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin left,
+            pin right,
+            mut prefer_left,
+        });
+
+        let mut left_value = None;
+        let mut right_value = None;
+
+        let left_done = match left.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+            None => true,
+            Some(Poll::Ready(None)) => {
+                left.set(None);
+                true
+            },
+            Some(Poll::Ready(a)) => {
+                left_value = a;
+                false
+            },
+            Some(Poll::Pending) => false,
+        };
+
+        let right_done = match right.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+            None => true,
+            Some(Poll::Ready(None)) => {
+                right.set(None);
+                true
+            },
+            Some(Poll::Ready(a)) => {
+                right_value = a;
+                false
+            },
+            Some(Poll::Pending) => false,
+        };
+
+        match (left_value, right_value) {
+            // If both changed at the same time, it alternates between them, so that one Signal can't starve the other
+            (Some(left), Some(right)) => {
+                let value = if *prefer_left { left } else { right };
+                *prefer_left = !*prefer_left;
+                Poll::Ready(Some(value))
+            },
+            (Some(value), None) | (None, Some(value)) => Poll::Ready(Some(value)),
+            (None, None) => {
+                if left_done && right_done {
+                    Poll::Ready(None)
+
+                } else {
+                    Poll::Pending
+                }
+            },
+        }
+    }
+}
+
+/// Creates a `Signal` which contains the value of whichever of `left` or `right` changed most recently.
+///
+/// If both of them change at the same time, it alternates between them.
+///
+/// It ends when both `left` and `right` have ended.
+#[inline]
+pub fn merge<A, B>(left: A, right: B) -> Merge<A, B>
+    where A: Signal,
+          B: Signal<Item = A::Item> {
+    Merge {
+        left: Some(left),
+        right: Some(right),
+        prefer_left: false,
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct FromFuture<A> {
+    // TODO is this valid with pinned types ?
+    future: Option<A>,
+    first: bool,
+}
+
+impl<A> Unpin for FromFuture<A> where A: Unpin {}
+
+impl<A> Signal for FromFuture<A> where A: Future {
+    type Item = Option<A::Output>;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
             pin future,
             mut first,
         });
 
-        // TODO is this valid with pinned types ?
-        match future.as_mut().as_pin_mut().map(|future| future.poll(cx)) {
-            None => {
+        // TODO is this valid with pinned types ?
+        match future.as_mut().as_pin_mut().map(|future| future.poll(cx)) {
+            None => {
+                Poll::Ready(None)
+            },
+
+            Some(Poll::Ready(value)) => {
+                future.set(None);
+                Poll::Ready(Some(Some(value)))
+            },
+
+            Some(Poll::Pending) => {
+                if *first {
+                    *first = false;
+                    Poll::Ready(Some(None))
+
+                } else {
+                    Poll::Pending
+                }
+            },
+        }
+    }
+}
+
+/// Converts a `Future` into a `Signal`.
+///
+/// The `Signal` is `None` while the `Future` is running, and it changes to `Some(value)` when the
+/// `Future` finishes. After that it never changes again.
+///
+/// This is useful for displaying a loading indicator while a one-shot asynchronous operation is running:
+///
+/// ```rust
+/// # let fetch_user = || async { "Bob".to_string() };
+/// use futures_signals::signal::{self, SignalExt};
+///
+/// let text = signal::from_future(fetch_user()).map(|user| {
+///     match user {
+///         Some(user) => user,
+///         None => "Loading...".to_string(),
+///     }
+/// });
+/// ```
+#[inline]
+pub fn from_future<A>(future: A) -> FromFuture<A> where A: Future {
+    FromFuture { future: Some(future), first: true }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct FromStream<A> {
+    stream: A,
+    first: bool,
+}
+
+impl<A> Unpin for FromStream<A> where A: Unpin {}
+
+impl<A> Signal for FromStream<A> where A: Stream {
+    type Item = Option<A::Item>;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin stream,
+            mut first,
+        });
+
+        match stream.poll_next(cx) {
+            Poll::Ready(None) => {
+                Poll::Ready(None)
+            },
+
+            Poll::Ready(Some(value)) => {
+                Poll::Ready(Some(Some(value)))
+            },
+
+            Poll::Pending => {
+                if *first {
+                    *first = false;
+                    Poll::Ready(Some(None))
+
+                } else {
+                    Poll::Pending
+                }
+            },
+        }
+    }
+}
+
+#[inline]
+pub fn from_stream<A>(stream: A) -> FromStream<A> where A: Stream {
+    FromStream { stream, first: true }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct FromStreamWithInitial<A> where A: Stream {
+    stream: A,
+    initial: Option<A::Item>,
+}
+
+impl<A> Unpin for FromStreamWithInitial<A> where A: Unpin + Stream {}
+
+impl<A> Signal for FromStreamWithInitial<A> where A: Stream {
+    type Item = A::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin stream,
+            mut initial,
+        });
+
+        match stream.poll_next(cx) {
+            Poll::Ready(Some(value)) => {
+                // The Stream already has a newer value, so the initial value is skipped
+                *initial = None;
+                Poll::Ready(Some(value))
+            },
+
+            Poll::Ready(None) => {
+                Poll::Ready(initial.take())
+            },
+
+            Poll::Pending => {
+                match initial.take() {
+                    Some(value) => Poll::Ready(Some(value)),
+                    None => Poll::Pending,
+                }
+            },
+        }
+    }
+}
+
+/// Like [`from_stream`](fn.from_stream.html), except it starts with `initial`
+/// instead of `None`, so it doesn't need to wrap the values in `Option`.
+#[inline]
+pub fn from_stream_with_initial<A>(initial: A::Item, stream: A) -> FromStreamWithInitial<A> where A: Stream {
+    FromStreamWithInitial { stream, initial: Some(initial) }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Always<A> {
+    value: Option<A>,
+}
+
+impl<A> Unpin for Always<A> {}
+
+impl<A> Signal for Always<A> {
+    type Item = A;
+
+    #[inline]
+    fn poll_change(mut self: Pin<&mut Self>, _: &mut Context) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.value.take())
+    }
+}
+
+#[inline]
+pub fn always<A>(value: A) -> Always<A> {
+    Always {
+        value: Some(value),
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct FromPolls<A> {
+    polls: alloc::vec::IntoIter<Poll<A>>,
+}
+
+impl<A> Unpin for FromPolls<A> {}
+
+impl<A> Signal for FromPolls<A> {
+    type Item = A;
+
+    #[inline]
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        match self.polls.next() {
+            Some(Poll::Ready(value)) => Poll::Ready(Some(value)),
+            Some(Poll::Pending) => {
+                // Wake up immediately, so that the next step runs even when it is spawned
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            },
+            None => Poll::Ready(None),
+        }
+    }
+}
+
+/// Creates a `Signal` which returns each of `values`, one per poll, and then ends.
+///
+/// This is mostly useful for testing.
+#[inline]
+pub fn from_values<A, B>(values: A) -> FromPolls<B> where A: IntoIterator<Item = B> {
+    from_polls(values.into_iter().map(Poll::Ready))
+}
+
+/// Creates a `Signal` which returns each of `polls`, one per poll, and then ends.
+///
+/// A `Poll::Pending` step returns `Poll::Pending` (and immediately wakes up the task),
+/// which makes it possible to test how a `Signal` behaves when its input is not ready.
+///
+/// This is mostly useful for testing.
+#[inline]
+pub fn from_polls<A, B>(polls: A) -> FromPolls<B> where A: IntoIterator<Item = Poll<B>> {
+    FromPolls {
+        polls: polls.into_iter().collect::<Vec<_>>().into_iter(),
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct OptionSignal<A> {
+    signal: Option<A>,
+    first: bool,
+}
+
+impl<A> Unpin for OptionSignal<A> where A: Unpin {}
+
+impl<A> Signal for OptionSignal<A> where A: Signal {
+    type Item = Option<A::Item>;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut first,
+        });
+
+        match signal.as_pin_mut() {
+            Some(signal) => {
+                *first = false;
+                signal.poll_change(cx).map(|opt| opt.map(Some))
+            },
+
+            None => {
+                if *first {
+                    *first = false;
+                    Poll::Ready(Some(None))
+
+                } else {
+                    Poll::Ready(None)
+                }
+            },
+        }
+    }
+}
+
+/// Converts an optional `Signal` into a `Signal` of `Option`.
+///
+/// If `value` is `Some`, then the output `Signal` contains the values of the inner `Signal`
+/// wrapped in `Some`.
+///
+/// If `value` is `None`, then the output `Signal` immediately outputs `None` and then ends.
+///
+/// # Performance
+///
+/// This is ***extremely*** efficient: it is *guaranteed* constant time, and it does not do
+/// any heap allocation.
+#[inline]
+pub fn option<A>(value: Option<A>) -> OptionSignal<A> where A: Signal {
+    OptionSignal {
+        signal: value,
+        first: true,
+    }
+}
+
+/// This is the same as [`option`](fn.option.html), it is named to match [`from_future`](fn.from_future.html)
+/// and [`from_stream`](fn.from_stream.html).
+///
+/// ```rust
+/// use futures_signals::signal::{self, SignalExt};
+///
+/// let maybe_signal = Some(signal::always(5));
+///
+/// let signal = signal::from_option(maybe_signal).map(|value| value.unwrap_or(0));
+/// ```
+#[inline]
+pub fn from_option<A>(value: Option<A>) -> OptionSignal<A> where A: Signal {
+    option(value)
+}
+
+impl<A> From<Option<A>> for OptionSignal<A> where A: Signal {
+    #[inline]
+    fn from(value: Option<A>) -> Self {
+        option(value)
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct First<A> {
+    signal: Option<A>,
+}
+
+impl<A> Unpin for First<A> where A: Unpin {}
+
+impl<A> Signal for First<A> where A: Signal {
+    type Item = A::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+        });
+
+        // TODO maybe it's safe to replace this with take ?
+        if let Some(poll) = signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+            signal.set(None);
+            poll
+
+        } else {
+            Poll::Ready(None)
+        }
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Switch<A, B, C> where A: Signal, C: FnMut(A::Item) -> B {
+    inner: Flatten<Map<A, C>>,
+}
+
+// TODO is this correct ?
+impl<A, B, C> Unpin for Switch<A, B, C> where A: Signal + Unpin, B: Unpin, C: FnMut(A::Item) -> B {}
+
+impl<A, B, C> Signal for Switch<A, B, C>
+    where A: Signal,
+          B: Signal,
+          C: FnMut(A::Item) -> B {
+    type Item = B::Item;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin inner,
+        });
+
+        inner.poll_change(cx)
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Futures do nothing unless polled"]
+pub struct ForEach<A, B, C> {
+    inner: stream::ForEach<SignalStream<A>, B, C>,
+}
+
+impl<A, B, C> Unpin for ForEach<A, B, C> where A: Signal + Unpin, B: Future<Output = ()> + Unpin, C: FnMut(A::Item) -> B {}
+
+impl<A, B, C> Future for ForEach<A, B, C>
+    where A: Signal,
+          B: Future<Output = ()>,
+          C: FnMut(A::Item) -> B {
+    type Output = ();
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        unsafe_project!(self => {
+            pin inner,
+        });
+
+        inner.poll(cx)
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Streams do nothing unless polled"]
+pub struct SignalStream<A> {
+    signal: A,
+}
+
+impl<A> Unpin for SignalStream<A> where A: Unpin {}
+
+impl<A: Signal> Stream for SignalStream<A> {
+    type Item = A::Item;
+
+    #[inline]
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+        });
+
+        signal.poll_change(cx)
+    }
+}
+
+
+/// What [`buffered_stream`](trait.SignalExt.html#method.buffered_stream) does with a
+/// new value when its queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// The new value is discarded, and the `Stream` returns `Err(BufferOverflow)` (with the
+    /// number of discarded values) in the place where the values were discarded.
+    Error,
+
+    /// The oldest value in the queue is discarded, and the new value is added to the queue.
+    DropOldest,
+
+    /// The new value is discarded.
+    DropNewest,
+
+    /// The `Future` which fills the queue panics.
+    Panic,
+}
+
+
+/// The error which is returned by [`buffered_stream`](trait.SignalExt.html#method.buffered_stream)
+/// when values were discarded because the queue was full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferOverflow {
+    /// The number of values which were discarded.
+    pub dropped: usize,
+}
+
+impl core::fmt::Display for BufferOverflow {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(fmt, "buffer overflowed, {} values were discarded", self.dropped)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BufferOverflow {}
+
+
+#[derive(Debug)]
+struct BufferState<A> {
+    queue: VecDeque<Result<A, BufferOverflow>>,
+    // The number of values in the queue, not including errors
+    len: usize,
+    capacity: usize,
+    overflow: Overflow,
+    // This is true when the Signal has ended (or the BufferedStreamPump was dropped)
+    ended: bool,
+    // This is true when the BufferedStream was dropped, so the BufferedStreamPump can stop
+    closed: bool,
+    stream_waker: Option<Waker>,
+    pump_waker: Option<Waker>,
+}
+
+impl<A> BufferState<A> {
+    // Returns true if it overflowed and the overflow policy is Overflow::Panic
+    fn push(&mut self, value: A) -> bool {
+        if self.len < self.capacity {
+            self.queue.push_back(Ok(value));
+            self.len += 1;
+
+        } else {
+            match self.overflow {
+                Overflow::Error => {
+                    match self.queue.back_mut() {
+                        Some(Err(error)) => {
+                            error.dropped += 1;
+                        },
+                        _ => {
+                            self.queue.push_back(Err(BufferOverflow { dropped: 1 }));
+                        },
+                    }
+                },
+                Overflow::DropOldest => {
+                    // The queue is full, so it must contain at least one value
+                    let index = self.queue.iter().position(|x| x.is_ok()).unwrap();
+                    self.queue.remove(index);
+                    self.queue.push_back(Ok(value));
+                },
+                Overflow::DropNewest => {},
+                Overflow::Panic => {
+                    return true;
+                },
+            }
+        }
+
+        false
+    }
+
+    fn wake_stream(&mut self) {
+        if let Some(waker) = self.stream_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+
+/// The `Future` which is returned by [`buffered_stream`](trait.SignalExt.html#method.buffered_stream).
+///
+/// It polls the `Signal` and puts its values into the queue of the [`BufferedStream`](struct.BufferedStream.html).
+/// It stops when the `Signal` ends, or when the `BufferedStream` is dropped.
+#[derive(Debug)]
+#[must_use = "Futures do nothing unless polled"]
+pub struct BufferedStreamPump<A> where A: Signal {
+    signal: A,
+    state: Arc<Mutex<BufferState<A::Item>>>,
+}
+
+impl<A> Unpin for BufferedStreamPump<A> where A: Signal + Unpin {}
+
+impl<A> Future for BufferedStreamPump<A> where A: Signal {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        unsafe_project!(self => {
+            pin signal,
+            mut state,
+        });
+
+        let mut changed = false;
+
+        let output = loop {
+            if state.lock().closed {
+                break Poll::Ready(());
+            }
+
+            match signal.as_mut().poll_change(cx) {
+                Poll::Ready(Some(value)) => {
+                    let overflowed = state.lock().push(value);
+
+                    // This is done after unlocking, so that the BufferedStream can still be used
+                    if overflowed {
+                        let capacity = state.lock().capacity;
+                        panic!("buffered_stream overflowed its capacity of {}", capacity);
+                    }
+
+                    changed = true;
+                },
+                Poll::Ready(None) => {
+                    state.lock().ended = true;
+                    changed = true;
+                    break Poll::Ready(());
+                },
+                Poll::Pending => {
+                    state.lock().pump_waker = Some(cx.waker().clone());
+                    break Poll::Pending;
+                },
+            }
+        };
+
+        if changed {
+            state.lock().wake_stream();
+        }
+
+        output
+    }
+}
+
+impl<A> Drop for BufferedStreamPump<A> where A: Signal {
+    fn drop(&mut self) {
+        let mut state = self.state.lock();
+
+        // If the pump is dropped then there can't be any more values
+        if !state.ended {
+            state.ended = true;
+            state.wake_stream();
+        }
+    }
+}
+
+
+/// The `Stream` which is returned by [`buffered_stream`](trait.SignalExt.html#method.buffered_stream).
+#[derive(Debug)]
+#[must_use = "Streams do nothing unless polled"]
+pub struct BufferedStream<A> {
+    state: Arc<Mutex<BufferState<A>>>,
+}
+
+impl<A> Unpin for BufferedStream<A> {}
+
+impl<A> Stream for BufferedStream<A> {
+    type Item = Result<A, BufferOverflow>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut state = self.state.lock();
+
+        if let Some(value) = state.queue.pop_front() {
+            if value.is_ok() {
+                state.len -= 1;
+            }
+
+            Poll::Ready(Some(value))
+
+        } else if state.ended {
+            Poll::Ready(None)
+
+        } else {
+            state.stream_waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl<A> Drop for BufferedStream<A> {
+    fn drop(&mut self) {
+        let mut state = self.state.lock();
+
+        state.closed = true;
+
+        if let Some(waker) = state.pump_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+
+// TODO maybe remove this ?
+#[derive(Debug)]
+#[must_use = "Futures do nothing unless polled"]
+pub struct SignalFuture<A> where A: Signal {
+    signal: A,
+    value: Option<A::Item>,
+}
+
+impl<A> Unpin for SignalFuture<A> where A: Unpin + Signal {}
+
+impl<A> Future for SignalFuture<A> where A: Signal {
+    type Output = A::Item;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        unsafe_project!(self => {
+            pin signal,
+            mut value,
+        });
+
+        loop {
+            return match signal.as_mut().poll_change(cx) {
+                Poll::Ready(None) => {
+                    Poll::Ready(value.take().unwrap())
+                },
+                Poll::Ready(Some(new_value)) => {
+                    *value = Some(new_value);
+                    continue;
+                },
+                Poll::Pending => {
+                    Poll::Pending
+                },
+            }
+        }
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Map<A, B> {
+    signal: A,
+    callback: B,
+}
+
+impl<A, B> Unpin for Map<A, B> where A: Unpin {}
+
+impl<A, B, C> Signal for Map<A, B>
+    where A: Signal,
+          B: FnMut(A::Item) -> C {
+    type Item = C;
+
+    #[inline]
+    #[allow(clippy::redundant_closure)]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut callback,
+        });
+
+        signal.poll_change(cx).map(|opt| opt.map(|value| callback(value)))
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Inspect<A, B> {
+    signal: A,
+    callback: B,
+}
+
+impl<A, B> Unpin for Inspect<A, B> where A: Unpin {}
+
+impl<A, B> Signal for Inspect<A, B>
+    where A: Signal,
+          B: FnMut(&A::Item) {
+    type Item = A::Item;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut callback,
+        });
+
+        let poll = signal.poll_change(cx);
+
+        if let Poll::Ready(Some(ref value)) = poll {
+            callback(value);
+        }
+
+        poll
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct InspectEnd<A, B> {
+    signal: A,
+    // This is None after it has been called
+    callback: Option<B>,
+}
+
+impl<A, B> Unpin for InspectEnd<A, B> where A: Unpin {}
+
+impl<A, B> Signal for InspectEnd<A, B>
+    where A: Signal,
+          B: FnOnce() {
+    type Item = A::Item;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut callback,
+        });
+
+        let poll = signal.poll_change(cx);
+
+        if let Poll::Ready(None) = poll {
+            if let Some(callback) = callback.take() {
+                callback();
+            }
+        }
+
+        poll
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Diff<A, F> where A: Signal {
+    signal: A,
+    old: Option<A::Item>,
+    callback: F,
+}
+
+impl<A, F> Unpin for Diff<A, F> where A: Unpin + Signal {}
+
+impl<A, B, F> Signal for Diff<A, F>
+    where A: Signal,
+          F: FnMut(Option<&A::Item>, &A::Item) -> B {
+    type Item = B;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut old,
+            mut callback,
+        });
+
+        signal.poll_change(cx).map(|value| value.map(|value| {
+            let output = callback(old.as_ref(), &value);
+            *old = Some(value);
+            output
+        }))
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Pairwise<A> where A: Signal {
+    signal: A,
+    old: Option<A::Item>,
+}
+
+impl<A> Unpin for Pairwise<A> where A: Unpin + Signal {}
+
+impl<A> Signal for Pairwise<A>
+    where A: Signal,
+          A::Item: Clone {
+    type Item = (Option<A::Item>, A::Item);
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut old,
+        });
+
+        signal.poll_change(cx).map(|value| value.map(|value| {
+            (old.replace(value.clone()), value)
+        }))
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Window<A> where A: Signal {
+    signal: A,
+    values: VecDeque<A::Item>,
+    size: usize,
+}
+
+impl<A> Unpin for Window<A> where A: Unpin + Signal {}
+
+impl<A> Signal for Window<A>
+    where A: Signal,
+          A::Item: Clone {
+    type Item = VecDeque<A::Item>;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut values,
+            mut size,
+        });
+
+        signal.poll_change(cx).map(|value| value.map(|value| {
+            if values.len() == *size {
+                values.pop_front();
+            }
+
+            values.push_back(value);
+            values.clone()
+        }))
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Skip<A> {
+    signal: A,
+    amount: usize,
+}
+
+impl<A> Unpin for Skip<A> where A: Unpin {}
+
+impl<A> Signal for Skip<A> where A: Signal {
+    type Item = A::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut amount,
+        });
+
+        loop {
+            return match signal.as_mut().poll_change(cx) {
+                Poll::Ready(Some(value)) => {
+                    if *amount > 0 {
+                        *amount -= 1;
+                        continue;
+
+                    } else {
+                        Poll::Ready(Some(value))
+                    }
+                },
+                Poll::Ready(None) => Poll::Ready(None),
+                Poll::Pending => Poll::Pending,
+            }
+        }
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct SkipWhile<A, F> {
+    signal: A,
+    callback: Option<F>,
+}
+
+impl<A, F> Unpin for SkipWhile<A, F> where A: Unpin {}
+
+impl<A, F> Signal for SkipWhile<A, F>
+    where A: Signal,
+          F: FnMut(&A::Item) -> bool {
+    type Item = A::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut callback,
+        });
+
+        loop {
+            return match signal.as_mut().poll_change(cx) {
+                Poll::Ready(Some(value)) => {
+                    if let Some(f) = callback {
+                        if f(&value) {
+                            continue;
+
+                        } else {
+                            *callback = None;
+                        }
+                    }
+
+                    Poll::Ready(Some(value))
+                },
+                Poll::Ready(None) => Poll::Ready(None),
+                Poll::Pending => Poll::Pending,
+            }
+        }
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Take<A> {
+    signal: Option<A>,
+    amount: usize,
+}
+
+impl<A> Unpin for Take<A> where A: Unpin {}
+
+impl<A> Signal for Take<A> where A: Signal {
+    type Item = A::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut amount,
+        });
+
+        match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+            Some(Poll::Ready(Some(value))) => {
+                *amount -= 1;
+
+                // This drops the Signal as soon as possible
+                if *amount == 0 {
+                    signal.set(None);
+                }
+
+                Poll::Ready(Some(value))
+            },
+            Some(Poll::Ready(None)) => {
+                signal.set(None);
                 Poll::Ready(None)
             },
+            Some(Poll::Pending) => Poll::Pending,
+            None => Poll::Ready(None),
+        }
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct TakeWhile<A, F> {
+    signal: Option<A>,
+    callback: F,
+}
+
+impl<A, F> Unpin for TakeWhile<A, F> where A: Unpin {}
+
+impl<A, F> Signal for TakeWhile<A, F>
+    where A: Signal,
+          F: FnMut(&A::Item) -> bool {
+    type Item = A::Item;
 
-            Some(Poll::Ready(value)) => {
-                future.set(None);
-                Poll::Ready(Some(Some(value)))
-            },
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut callback,
+        });
 
-            Some(Poll::Pending) => {
-                if *first {
-                    *first = false;
-                    Poll::Ready(Some(None))
+        match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+            Some(Poll::Ready(Some(value))) => {
+                if callback(&value) {
+                    Poll::Ready(Some(value))
 
                 } else {
-                    Poll::Pending
+                    signal.set(None);
+                    Poll::Ready(None)
                 }
             },
+            Some(Poll::Ready(None)) => {
+                signal.set(None);
+                Poll::Ready(None)
+            },
+            Some(Poll::Pending) => Poll::Pending,
+            None => Poll::Ready(None),
         }
     }
 }
 
-#[inline]
-pub fn from_future<A>(future: A) -> FromFuture<A> where A: Future {
-    FromFuture { future: Some(future), first: true }
-}
-
 
 #[derive(Debug)]
 #[must_use = "Signals do nothing unless polled"]
-pub struct FromStream<A> {
-    stream: A,
-    first: bool,
+pub struct TakeUntil<A, B> {
+    signal: Option<A>,
+    future: Option<B>,
 }
 
-impl<A> Unpin for FromStream<A> where A: Unpin {}
+impl<A, B> Unpin for TakeUntil<A, B> where A: Unpin, B: Unpin {}
 
-impl<A> Signal for FromStream<A> where A: Stream {
-    type Item = Option<A::Item>;
+impl<A, B> Signal for TakeUntil<A, B>
+    where A: Signal,
+          B: Future {
+    type Item = A::Item;
 
     fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
         unsafe_project!(self => {
-            pin stream,
-            mut first,
+            pin signal,
+            pin future,
         });
 
-        match stream.poll_next(cx) {
-            Poll::Ready(None) => {
+        if let Some(Poll::Ready(_)) = future.as_mut().as_pin_mut().map(|future| future.poll(cx)) {
+            future.set(None);
+            signal.set(None);
+        }
+
+        match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+            Some(Poll::Ready(None)) => {
+                signal.set(None);
+                future.set(None);
                 Poll::Ready(None)
             },
+            Some(poll) => poll,
+            None => Poll::Ready(None),
+        }
+    }
+}
 
-            Poll::Ready(Some(value)) => {
-                Poll::Ready(Some(Some(value)))
-            },
 
-            Poll::Pending => {
-                if *first {
-                    *first = false;
-                    Poll::Ready(Some(None))
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct StopIf<A, F> {
+    signal: Option<A>,
+    callback: F,
+}
 
-                } else {
-                    Poll::Pending
+impl<A, F> Unpin for StopIf<A, F> where A: Unpin {}
+
+impl<A, F> Signal for StopIf<A, F>
+    where A: Signal,
+          F: FnMut(&A::Item) -> bool {
+    type Item = A::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut callback,
+        });
+
+        match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+            Some(Poll::Ready(Some(value))) => {
+                if callback(&value) {
+                    signal.set(None);
                 }
+
+                Poll::Ready(Some(value))
+            },
+            Some(Poll::Ready(None)) => {
+                signal.set(None);
+                Poll::Ready(None)
             },
+            Some(Poll::Pending) => Poll::Pending,
+            None => Poll::Ready(None),
         }
     }
 }
 
-#[inline]
-pub fn from_stream<A>(stream: A) -> FromStream<A> where A: Stream {
-    FromStream { stream, first: true }
-}
-
 
 #[derive(Debug)]
 #[must_use = "Signals do nothing unless polled"]
-pub struct Always<A> {
-    value: Option<A>,
+pub struct Versioned<A> {
+    signal: A,
+    version: u64,
 }
 
-impl<A> Unpin for Always<A> {}
+impl<A> Unpin for Versioned<A> where A: Unpin {}
 
-impl<A> Signal for Always<A> {
-    type Item = A;
+impl<A> Signal for Versioned<A> where A: Signal {
+    type Item = (u64, A::Item);
 
     #[inline]
-    fn poll_change(mut self: Pin<&mut Self>, _: &mut Context) -> Poll<Option<Self::Item>> {
-        Poll::Ready(self.value.take())
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut version,
+        });
+
+        signal.poll_change(cx).map(|value| value.map(|value| {
+            let current = *version;
+            *version += 1;
+            (current, value)
+        }))
     }
 }
 
-#[inline]
-pub fn always<A>(value: A) -> Always<A> {
-    Always {
-        value: Some(value),
+
+#[cfg(feature = "graph")]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Named<A> {
+    signal: A,
+    node: crate::graph::Node,
+}
+
+#[cfg(feature = "graph")]
+impl<A> Unpin for Named<A> where A: Unpin {}
+
+#[cfg(feature = "graph")]
+impl<A> Signal for Named<A> where A: Signal {
+    type Item = A::Item;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut node,
+        });
+
+        node.polled();
+        node.poll(|| signal.poll_change(cx))
     }
 }
 
 
+#[cfg(feature = "std")]
 #[derive(Debug)]
 #[must_use = "Signals do nothing unless polled"]
-pub struct First<A> {
-    signal: Option<A>,
+pub struct SignalDebug<A> {
+    signal: A,
+    location: &'static Location<'static>,
 }
 
-impl<A> Unpin for First<A> where A: Unpin {}
+#[cfg(feature = "std")]
+impl<A> Unpin for SignalDebug<A> where A: Unpin {}
 
-impl<A> Signal for First<A> where A: Signal {
+#[cfg(feature = "std")]
+impl<A> Signal for SignalDebug<A>
+    where A: Signal,
+          A::Item: Debug {
     type Item = A::Item;
 
     fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
         unsafe_project!(self => {
             pin signal,
+            mut location,
         });
 
-        // TODO maybe it's safe to replace this with take ?
-        if let Some(poll) = signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
-            signal.set(None);
-            poll
+        let poll = signal.poll_change(cx);
 
-        } else {
-            Poll::Ready(None)
+        match poll {
+            Poll::Ready(Some(ref value)) => {
+                std::eprintln!("[{}] {:#?}", location, value);
+            },
+            Poll::Ready(None) => {
+                std::eprintln!("[{}] Signal ended", location);
+            },
+            Poll::Pending => {},
         }
+
+        poll
     }
 }
 
 
 #[derive(Debug)]
 #[must_use = "Signals do nothing unless polled"]
-pub struct Switch<A, B, C> where A: Signal, C: FnMut(A::Item) -> B {
-    inner: Flatten<Map<A, C>>,
+pub struct MapFuture<A, B, C> {
+    signal: Option<A>,
+    future: Option<B>,
+    callback: C,
+    first: bool,
 }
 
-// TODO is this correct ?
-impl<A, B, C> Unpin for Switch<A, B, C> where A: Signal + Unpin, B: Unpin, C: FnMut(A::Item) -> B {}
+impl<A, B, C> Unpin for MapFuture<A, B, C> where A: Unpin, B: Unpin {}
 
-impl<A, B, C> Signal for Switch<A, B, C>
+impl<A, B, C> Signal for MapFuture<A, B, C>
     where A: Signal,
-          B: Signal,
+          B: Future,
           C: FnMut(A::Item) -> B {
-    type Item = B::Item;
+    type Item = Option<B::Output>;
 
-    #[inline]
     fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
         unsafe_project!(self => {
-            pin inner,
+            pin signal,
+            pin future,
+            mut callback,
+            mut first,
         });
 
-        inner.poll_change(cx)
+        let mut done = false;
+
+        loop {
+            match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                None => {
+                    done = true;
+                },
+                Some(Poll::Ready(None)) => {
+                    signal.set(None);
+                    done = true;
+                },
+                Some(Poll::Ready(Some(value))) => {
+                    let value = Some(callback(value));
+                    future.set(value);
+                    continue;
+                },
+                Some(Poll::Pending) => {},
+            }
+            break;
+        }
+
+        match future.as_mut().as_pin_mut().map(|future| future.poll(cx)) {
+            None => {},
+            Some(Poll::Ready(value)) => {
+                future.set(None);
+                *first = false;
+                return Poll::Ready(Some(Some(value)));
+            },
+            Some(Poll::Pending) => {
+                done = false;
+            },
+        }
+
+        if *first {
+            *first = false;
+            Poll::Ready(Some(None))
+
+        } else if done {
+            Poll::Ready(None)
+
+        } else {
+            Poll::Pending
+        }
     }
 }
 
 
+/// The state of the `Future` which is running inside of
+/// [`map_async_latest`](trait.SignalExt.html#method.map_async_latest).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsyncState<A> {
+    /// The `Future` is running.
+    Pending,
+
+    /// The `Future` has finished.
+    Ready(A),
+
+    /// The `Future` was dropped before it finished, because the input changed.
+    Cancelled,
+}
+
+
 #[derive(Debug)]
-#[must_use = "Futures do nothing unless polled"]
-pub struct ForEach<A, B, C> {
-    inner: stream::ForEach<SignalStream<A>, B, C>,
+#[must_use = "Signals do nothing unless polled"]
+pub struct MapAsyncLatest<A, B, C> where A: Signal {
+    signal: Option<A>,
+    future: Option<B>,
+    callback: C,
+    // The input which will be used after the old Future was cancelled
+    next: Option<A::Item>,
 }
 
-impl<A, B, C> Unpin for ForEach<A, B, C> where A: Signal + Unpin, B: Future<Output = ()> + Unpin, C: FnMut(A::Item) -> B {}
+impl<A, B, C> Unpin for MapAsyncLatest<A, B, C> where A: Signal + Unpin, B: Unpin {}
 
-impl<A, B, C> Future for ForEach<A, B, C>
+impl<A, B, C> Signal for MapAsyncLatest<A, B, C>
     where A: Signal,
-          B: Future<Output = ()>,
+          B: Future,
           C: FnMut(A::Item) -> B {
-    type Output = ();
+    type Item = AsyncState<B::Output>;
 
-    #[inline]
-    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
         unsafe_project!(self => {
-            pin inner,
+            pin signal,
+            pin future,
+            mut callback,
+            mut next,
         });
 
-        inner.poll(cx)
+        let mut latest = next.take();
+
+        loop {
+            match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                None => {},
+                Some(Poll::Ready(None)) => {
+                    signal.set(None);
+                },
+                Some(Poll::Ready(Some(value))) => {
+                    latest = Some(value);
+                    continue;
+                },
+                Some(Poll::Pending) => {},
+            }
+            break;
+        }
+
+        let mut started = false;
+
+        if let Some(value) = latest {
+            if future.is_some() {
+                // The old Future must be dropped before the new Future is created
+                future.set(None);
+                *next = Some(value);
+                cx.waker().wake_by_ref();
+                return Poll::Ready(Some(AsyncState::Cancelled));
+            }
+
+            let value = callback(value);
+            future.set(Some(value));
+            started = true;
+        }
+
+        match future.as_mut().as_pin_mut().map(|future| future.poll(cx)) {
+            Some(Poll::Ready(value)) => {
+                future.set(None);
+                return Poll::Ready(Some(AsyncState::Ready(value)));
+            },
+            Some(Poll::Pending) if started => {
+                return Poll::Ready(Some(AsyncState::Pending));
+            },
+            _ => {},
+        }
+
+        if signal.is_none() && future.is_none() {
+            Poll::Ready(None)
+
+        } else {
+            Poll::Pending
+        }
     }
 }
 
 
 #[derive(Debug)]
-#[must_use = "Streams do nothing unless polled"]
-pub struct SignalStream<A> {
-    signal: A,
+#[must_use = "Signals do nothing unless polled"]
+pub struct MapAsyncQueued<A, B, C> where A: Signal {
+    signal: Option<A>,
+    future: Option<B>,
+    callback: C,
+    queue: VecDeque<A::Item>,
+    first: bool,
 }
 
-impl<A> Unpin for SignalStream<A> where A: Unpin {}
+impl<A, B, C> Unpin for MapAsyncQueued<A, B, C> where A: Signal + Unpin, B: Unpin {}
 
-impl<A: Signal> Stream for SignalStream<A> {
-    type Item = A::Item;
+impl<A, B, C> Signal for MapAsyncQueued<A, B, C>
+    where A: Signal,
+          B: Future,
+          C: FnMut(A::Item) -> B {
+    type Item = Option<B::Output>;
 
-    #[inline]
-    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
         unsafe_project!(self => {
             pin signal,
+            pin future,
+            mut callback,
+            mut queue,
+            mut first,
         });
 
-        signal.poll_change(cx)
+        loop {
+            match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                None => {},
+                Some(Poll::Ready(None)) => {
+                    signal.set(None);
+                },
+                Some(Poll::Ready(Some(value))) => {
+                    queue.push_back(value);
+                    continue;
+                },
+                Some(Poll::Pending) => {},
+            }
+            break;
+        }
+
+        if future.is_none() {
+            if let Some(value) = queue.pop_front() {
+                let value = callback(value);
+                future.set(Some(value));
+            }
+        }
+
+        if let Some(Poll::Ready(value)) = future.as_mut().as_pin_mut().map(|future| future.poll(cx)) {
+            future.set(None);
+            *first = false;
+
+            // The next Future will be started when this is polled again
+            if !queue.is_empty() {
+                cx.waker().wake_by_ref();
+            }
+
+            return Poll::Ready(Some(Some(value)));
+        }
+
+        if *first {
+            *first = false;
+            Poll::Ready(Some(None))
+
+        } else if signal.is_none() && future.is_none() && queue.is_empty() {
+            Poll::Ready(None)
+
+        } else {
+            Poll::Pending
+        }
     }
 }
 
 
-// TODO maybe remove this ?
 #[derive(Debug)]
-#[must_use = "Futures do nothing unless polled"]
-pub struct SignalFuture<A> where A: Signal {
-    signal: A,
+#[must_use = "Signals do nothing unless polled"]
+pub struct FilterBy<A, B> where A: Signal {
+    signal: Option<A>,
+    gate: Option<B>,
+    is_open: bool,
+    // The most recent value which hasn't been output yet
     value: Option<A::Item>,
 }
 
-impl<A> Unpin for SignalFuture<A> where A: Unpin + Signal {}
+impl<A, B> Unpin for FilterBy<A, B> where A: Signal + Unpin, B: Unpin {}
 
-impl<A> Future for SignalFuture<A> where A: Signal {
-    type Output = A::Item;
+impl<A, B> Signal for FilterBy<A, B>
+    where A: Signal,
+          B: Signal<Item = bool> {
+    type Item = A::Item;
 
-    #[inline]
-    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
         unsafe_project!(self => {
             pin signal,
+            pin gate,
+            mut is_open,
             mut value,
         });
 
         loop {
-            return match signal.as_mut().poll_change(cx) {
-                Poll::Ready(None) => {
-                    Poll::Ready(value.take().unwrap())
+            match gate.as_mut().as_pin_mut().map(|gate| gate.poll_change(cx)) {
+                None => {},
+                Some(Poll::Ready(None)) => {
+                    gate.set(None);
                 },
-                Poll::Ready(Some(new_value)) => {
-                    *value = Some(new_value);
+                Some(Poll::Ready(Some(open))) => {
+                    *is_open = open;
                     continue;
                 },
-                Poll::Pending => {
-                    Poll::Pending
+                Some(Poll::Pending) => {},
+            }
+            break;
+        }
+
+        loop {
+            match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                None => {},
+                Some(Poll::Ready(None)) => {
+                    signal.set(None);
+                },
+                Some(Poll::Ready(Some(new_value))) => {
+                    *value = Some(new_value);
+                    continue;
                 },
+                Some(Poll::Pending) => {},
+            }
+            break;
+        }
+
+        if *is_open {
+            if let Some(value) = value.take() {
+                return Poll::Ready(Some(value));
             }
         }
+
+        // If the gate is closed forever then nothing will ever be output
+        if (gate.is_none() && !*is_open) || (signal.is_none() && value.is_none()) {
+            Poll::Ready(None)
+
+        } else {
+            Poll::Pending
+        }
     }
 }
 
 
-#[derive(Debug)]
-#[must_use = "Signals do nothing unless polled"]
-pub struct Map<A, B> {
-    signal: A,
-    callback: B,
+/// Pauses and resumes the `Signal` which was created by [`pausable`](trait.SignalExt.html#method.pausable).
+///
+/// It can be cloned, all of the clones control the same `Signal`.
+#[derive(Debug, Clone)]
+pub struct PauseHandle {
+    is_paused: Mutable<bool>,
 }
 
-impl<A, B> Unpin for Map<A, B> where A: Unpin {}
-
-impl<A, B, C> Signal for Map<A, B>
-    where A: Signal,
-          B: FnMut(A::Item) -> C {
-    type Item = C;
+impl PauseHandle {
+    /// Holds back the changes until `resume` is called.
+    #[inline]
+    pub fn pause(&self) {
+        self.is_paused.set_neq(true);
+    }
 
+    /// Outputs the most recent change (if any were held back), and stops holding back changes.
     #[inline]
-    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
-        unsafe_project!(self => {
-            pin signal,
-            mut callback,
-        });
+    pub fn resume(&self) {
+        self.is_paused.set_neq(false);
+    }
 
-        signal.poll_change(cx).map(|opt| opt.map(|value| callback(value)))
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.is_paused.get()
     }
 }
 
 
-#[derive(Debug)]
 #[must_use = "Signals do nothing unless polled"]
-pub struct Inspect<A, B> {
-    signal: A,
-    callback: B,
+pub struct Pausable<A> where A: Signal {
+    signal: FilterBy<A, Not<MutableSignal<bool>>>,
 }
 
-impl<A, B> Unpin for Inspect<A, B> where A: Unpin {}
+impl<A> core::fmt::Debug for Pausable<A> where A: Signal + Debug, A::Item: Debug {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        fmt.debug_struct("Pausable")
+            .field("signal", &self.signal)
+            .finish()
+    }
+}
 
-impl<A, B> Signal for Inspect<A, B>
-    where A: Signal,
-          B: FnMut(&A::Item) {
+impl<A> Unpin for Pausable<A> where A: Signal + Unpin {}
+
+impl<A> Signal for Pausable<A> where A: Signal {
     type Item = A::Item;
 
     #[inline]
     fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
         unsafe_project!(self => {
             pin signal,
-            mut callback,
         });
 
-        let poll = signal.poll_change(cx);
-
-        if let Poll::Ready(Some(ref value)) = poll {
-            callback(value);
-        }
-
-        poll
+        signal.poll_change(cx)
     }
 }
 
 
 #[derive(Debug)]
 #[must_use = "Signals do nothing unless polled"]
-pub struct MapFuture<A, B, C> {
+pub struct RateLimit<A, T> where A: Signal, T: Timer {
     signal: Option<A>,
-    future: Option<B>,
-    callback: C,
-    first: bool,
+    sleep: Option<T::Sleep>,
+    timer: T,
+    max: usize,
+    interval: Duration,
+    // The times when the recent values were output
+    sent: VecDeque<f64>,
+    // The most recent value which hasn't been output yet
+    value: Option<A::Item>,
 }
 
-impl<A, B, C> Unpin for MapFuture<A, B, C> where A: Unpin, B: Unpin {}
+impl<A, T> Unpin for RateLimit<A, T> where A: Signal + Unpin, T: Timer, T::Sleep: Unpin {}
 
-impl<A, B, C> Signal for MapFuture<A, B, C>
+impl<A, T> Signal for RateLimit<A, T>
     where A: Signal,
-          B: Future,
-          C: FnMut(A::Item) -> B {
-    type Item = Option<B::Output>;
+          T: Timer {
+    type Item = A::Item;
 
     fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
         unsafe_project!(self => {
             pin signal,
-            pin future,
-            mut callback,
-            mut first,
+            pin sleep,
+            mut timer,
+            mut max,
+            mut interval,
+            mut sent,
+            mut value,
         });
 
-        let mut done = false;
-
         loop {
             match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
-                None => {
-                    done = true;
-                },
+                None => {},
                 Some(Poll::Ready(None)) => {
                     signal.set(None);
-                    done = true;
                 },
-                Some(Poll::Ready(Some(value))) => {
-                    let value = Some(callback(value));
-                    future.set(value);
+                Some(Poll::Ready(Some(new_value))) => {
+                    *value = Some(new_value);
                     continue;
                 },
                 Some(Poll::Pending) => {},
@@ -827,23 +2998,63 @@ impl<A, B, C> Signal for MapFuture<A, B, C>
             break;
         }
 
-        match future.as_mut().as_pin_mut().map(|future| future.poll(cx)) {
-            None => {},
-            Some(Poll::Ready(value)) => {
-                future.set(None);
-                *first = false;
-                return Poll::Ready(Some(Some(value)));
-            },
-            Some(Poll::Pending) => {
-                done = false;
-            },
-        }
+        if value.is_some() {
+            let interval = interval.as_secs_f64() * 1000.0;
+            let mut slept = false;
 
-        if *first {
-            *first = false;
-            Poll::Ready(Some(None))
+            loop {
+                let now = timer.now();
 
-        } else if done {
+                while let Some(time) = sent.front() {
+                    if *time + interval <= now {
+                        sent.pop_front();
+
+                    } else {
+                        break;
+                    }
+                }
+
+                if sent.len() < *max {
+                    sent.push_back(now);
+                    sleep.set(None);
+                    return Poll::Ready(value.take());
+                }
+
+                if sleep.is_none() {
+                    let wait = sent.front().unwrap() + interval - now;
+
+                    // This is rounded up to whole milliseconds, so it doesn't create lots of tiny sleeps
+                    // (it doesn't use `f64::ceil` because that needs std)
+                    let mut millis = wait as u64;
+
+                    if (millis as f64) < wait {
+                        millis += 1;
+                    }
+
+                    let value = timer.sleep(Duration::from_millis(millis));
+                    sleep.set(Some(value));
+                }
+
+                if let Some(Poll::Ready(())) = sleep.as_mut().as_pin_mut().map(|sleep| sleep.poll(cx)) {
+                    sleep.set(None);
+
+                    // The Timer might fire early, so this loops and lets `now` decide whether the interval
+                    // has passed. If it is still too early then it sleeps again.
+                    if slept {
+                        // The new sleep fired immediately, so this yields to the executor instead of spinning
+                        cx.waker().wake_by_ref();
+
+                    } else {
+                        slept = true;
+                        continue;
+                    }
+                }
+
+                break;
+            }
+        }
+
+        if signal.is_none() && value.is_none() {
             Poll::Ready(None)
 
         } else {
@@ -1281,3 +3492,117 @@ impl<A, B, C> SignalVec for SwitchSignalVec<A, B, C>
         }
     }
 }
+
+
+// How often `tween` outputs a new value while it is transitioning
+const TWEEN_FRAME: Duration = Duration::from_millis(16);
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Tween<A, T, F> where A: Signal, T: Timer {
+    signal: Option<A>,
+    timer: T,
+    // This is Some while it is waiting for the next frame
+    sleep: Option<T::Sleep>,
+    duration: Duration,
+    interpolate: F,
+    from: Option<A::Item>,
+    current: Option<A::Item>,
+    // This is Some while it is transitioning
+    target: Option<A::Item>,
+    start_time: Option<f64>,
+}
+
+impl<A, T, F> Unpin for Tween<A, T, F> where A: Unpin + Signal, T: Timer, T::Sleep: Unpin {}
+
+impl<A, T, F> Signal for Tween<A, T, F>
+    where A: Signal,
+          T: Timer,
+          F: FnMut(&A::Item, &A::Item, f64) -> A::Item,
+          A::Item: Clone {
+    type Item = A::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut timer,
+            pin sleep,
+            mut duration,
+            mut interpolate,
+            mut from,
+            mut current,
+            mut target,
+            mut start_time,
+        });
+
+        let mut changed = false;
+
+        let signal_done = loop {
+            break match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                None => true,
+                Some(Poll::Ready(None)) => {
+                    signal.set(None);
+                    true
+                },
+                Some(Poll::Ready(Some(value))) => {
+                    if current.is_none() {
+                        *current = Some(value);
+                        changed = true;
+
+                    } else {
+                        // It transitions from the current output, even if the previous transition isn't finished
+                        *from = current.clone();
+                        *target = Some(value);
+                        *start_time = None;
+                    }
+
+                    continue;
+                },
+                Some(Poll::Pending) => false,
+            };
+        };
+
+        while target.is_some() {
+            match sleep.as_mut().as_pin_mut().map(|sleep| sleep.poll(cx)) {
+                Some(Poll::Pending) => {
+                    break;
+                },
+                Some(Poll::Ready(())) | None => {
+                    let now = timer.now();
+                    let start = *start_time.get_or_insert(now);
+
+                    let duration = duration.as_secs_f64() * 1000.0;
+
+                    let t = if duration <= 0.0 {
+                        1.0
+
+                    } else {
+                        (now - start) / duration
+                    };
+
+                    if t >= 1.0 {
+                        *current = target.take();
+                        // The next transition starts on the next poll, rather than waiting for a stale frame
+                        sleep.set(None);
+
+                    } else {
+                        *current = Some(interpolate(from.as_ref().unwrap(), target.as_ref().unwrap(), t));
+                        sleep.set(Some(timer.sleep(TWEEN_FRAME)));
+                    }
+
+                    changed = true;
+                },
+            }
+        }
+
+        if changed {
+            Poll::Ready(current.clone())
+
+        } else if signal_done && target.is_none() {
+            Poll::Ready(None)
+
+        } else {
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use super::{Signal, SignalExt};
+use std::fmt;
+use std::pin::Pin;
+use std::marker::Unpin;
+use std::task::{Poll, Context};
+use futures_core::Stream;
+use futures_util::stream;
+use tokio::sync::watch;
+use tokio::runtime::Handle;
+use std::boxed::Box;
+
+
+#[must_use = "Signals do nothing unless polled"]
+pub struct FromWatch<A> {
+    stream: Pin<Box<dyn Stream<Item = A> + Send>>,
+}
+
+impl<A> Unpin for FromWatch<A> {}
+
+impl<A> Signal for FromWatch<A> {
+    type Item = A;
+
+    #[inline]
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.stream.as_mut().poll_next(cx)
+    }
+}
+
+impl<A> fmt::Debug for FromWatch<A> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("FromWatch")
+            .finish()
+    }
+}
+
+/// Converts a tokio `watch::Receiver` into a `Signal`.
+///
+/// The `Signal` starts with the current value of the `watch::Receiver`, and it
+/// ends when the `watch::Sender` is dropped.
+pub fn from_watch<A>(receiver: watch::Receiver<A>) -> FromWatch<A> where A: Clone + Send + Sync + 'static {
+    let stream = stream::unfold((receiver, true), |(mut receiver, first)| async move {
+        if !first && receiver.changed().await.is_err() {
+            return None;
+        }
+
+        let value = receiver.borrow_and_update().clone();
+        Some((value, (receiver, false)))
+    });
+
+    FromWatch {
+        stream: Box::pin(stream),
+    }
+}
+
+/// Spawns a task on `handle` which sends the values of `signal` into a tokio `watch` channel.
+///
+/// The value is `None` until the `Signal` has produced its first value. The task stops
+/// when the `Signal` ends, or when every `watch::Receiver` has been dropped.
+pub fn to_watch<A>(signal: A, handle: &Handle) -> watch::Receiver<Option<A::Item>>
+    where A: Signal + Send + 'static,
+          A::Item: Send + Sync + 'static {
+
+    let (sender, receiver) = watch::channel(None);
+
+    handle.spawn(send_to_watch(signal, sender));
+
+    receiver
+}
+
+/// This is the same as [`to_watch`](fn.to_watch.html), except the `Signal` does not need to be `Send`.
+///
+/// The task is spawned with `tokio::task::spawn_local`, so this must be called inside of a `tokio::task::LocalSet`.
+pub fn to_watch_local<A>(signal: A) -> watch::Receiver<Option<A::Item>>
+    where A: Signal + 'static,
+          A::Item: Send + Sync + 'static {
+
+    let (sender, receiver) = watch::channel(None);
+
+    tokio::task::spawn_local(send_to_watch(signal, sender));
+
+    receiver
+}
+
+async fn send_to_watch<A>(signal: A, sender: watch::Sender<Option<A::Item>>) where A: Signal {
+    let mut stream = Box::pin(signal.to_stream());
+
+    while let Some(value) = futures_util::StreamExt::next(&mut stream).await {
+        if sender.send(Some(value)).is_err() {
+            break;
+        }
+    }
+}
@@ -1,10 +1,29 @@
-use std::pin::Pin;
-use std::marker::Unpin;
-use std::task::{Poll, Context};
-
-
+use core::pin::Pin;
+use core::marker::Unpin;
+use core::task::{Poll, Context};
+use alloc::collections::{BTreeSet, BTreeMap};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use serde::{Serialize, Deserialize};
+use crate::signal::{Signal, SignalExt, Map, Dedupe};
+#[cfg(feature = "std")]
+use core::hash::{Hash, BuildHasher};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use alloc::vec;
+#[cfg(feature = "std")]
+use futures_channel::mpsc;
+#[cfg(feature = "std")]
+use futures_util::stream::StreamExt;
+
+
+/// A change to a [`SignalMap`](trait.SignalMap.html).
+///
+/// It implements `Serialize` and `Deserialize`, so the changes can be sent to another
+/// process and then applied with [`apply_to_btree_map`](#method.apply_to_btree_map).
 // TODO make this non-exhaustive
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MapDiff<K, V> {
     Replace {
         entries: Vec<(K, V)>,
@@ -39,6 +58,61 @@ impl<K, A> MapDiff<K, A> {
             MapDiff::Clear {} => MapDiff::Clear {},
         }
     }
+
+    /// Applies the change to `map`.
+    ///
+    /// This is useful for keeping a copy of a `SignalMap`: if every change is applied to the same
+    /// `BTreeMap` (starting with an empty `BTreeMap`), then the `BTreeMap` will contain the same
+    /// entries as the `SignalMap`.
+    ///
+    /// ```rust
+    /// use std::collections::BTreeMap;
+    /// use futures_signals::signal_map::MapDiff;
+    ///
+    /// let mut map = BTreeMap::new();
+    ///
+    /// MapDiff::Replace { entries: vec![(1, "a"), (2, "b")] }.apply_to_btree_map(&mut map);
+    /// MapDiff::Update { key: 1, value: "c" }.apply_to_btree_map(&mut map);
+    /// MapDiff::Remove { key: 2 }.apply_to_btree_map(&mut map);
+    ///
+    /// assert_eq!(map, vec![(1, "c")].into_iter().collect());
+    /// ```
+    pub fn apply_to_btree_map(self, map: &mut BTreeMap<K, A>) where K: Ord {
+        match self {
+            MapDiff::Replace { entries } => {
+                *map = entries.into_iter().collect();
+            },
+            MapDiff::Insert { key, value } | MapDiff::Update { key, value } => {
+                map.insert(key, value);
+            },
+            MapDiff::Remove { key } => {
+                map.remove(&key);
+            },
+            MapDiff::Clear {} => {
+                map.clear();
+            },
+        }
+    }
+
+    /// This is the same as [`apply_to_btree_map`](#method.apply_to_btree_map), except it uses a `HashMap`.
+    #[cfg(feature = "std")]
+    pub fn apply_to_hash_map<S>(self, map: &mut HashMap<K, A, S>) where K: Eq + Hash, S: BuildHasher {
+        match self {
+            MapDiff::Replace { entries } => {
+                map.clear();
+                map.extend(entries);
+            },
+            MapDiff::Insert { key, value } | MapDiff::Update { key, value } => {
+                map.insert(key, value);
+            },
+            MapDiff::Remove { key } => {
+                map.remove(&key);
+            },
+            MapDiff::Clear {} => {
+                map.clear();
+            },
+        }
+    }
 }
 
 
@@ -53,6 +127,7 @@ pub trait SignalMap {
 
 
 // Copied from Future in the Rust stdlib
+#[allow(clippy::needless_lifetimes)]
 impl<'a, A> SignalMap for &'a mut A where A: ?Sized + SignalMap + Unpin {
     type Key = A::Key;
     type Value = A::Value;
@@ -76,10 +151,10 @@ impl<A> SignalMap for Box<A> where A: ?Sized + SignalMap + Unpin {
 
 // Copied from Future in the Rust stdlib
 impl<A> SignalMap for Pin<A>
-    where A: Unpin + ::std::ops::DerefMut,
+    where A: Unpin + ::core::ops::DerefMut,
           A::Target: SignalMap {
-    type Key = <<A as ::std::ops::Deref>::Target as SignalMap>::Key;
-    type Value = <<A as ::std::ops::Deref>::Target as SignalMap>::Value;
+    type Key = <<A as ::core::ops::Deref>::Target as SignalMap>::Key;
+    type Value = <<A as ::core::ops::Deref>::Target as SignalMap>::Value;
 
     #[inline]
     fn poll_map_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<MapDiff<Self::Key, Self::Value>>> {
@@ -90,6 +165,25 @@ impl<A> SignalMap for Pin<A>
 
 // TODO Seal this
 pub trait SignalMapExt: SignalMap {
+    /// Creates a `SignalMap` which uses a closure to transform the values.
+    ///
+    /// The keys and the structure of the `MapDiff`s are unchanged, only the values are passed to the closure.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "std")] {
+    /// # use futures_signals::signal_map::{MutableBTreeMap, SignalMapExt};
+    /// # let input = MutableBTreeMap::<u32, u32>::new();
+    /// let mapped = input.signal_map().map_value(|value| value * 2);
+    /// # }
+    /// ```
+    ///
+    /// # Performance
+    ///
+    /// This is an ***extremely*** efficient method: it is *guaranteed* constant time, regardless of how big `self` is.
+    ///
+    /// The only exception is when `self` notifies with `MapDiff::Replace`, in which case it is linear time.
     #[inline]
     fn map_value<A, F>(self, callback: F) -> MapValue<Self, F>
         where F: FnMut(Self::Value) -> A,
@@ -100,6 +194,83 @@ pub trait SignalMapExt: SignalMap {
         }
     }
 
+    /// Creates a `SignalMap` which uses a closure to determine if an entry should be included or not.
+    ///
+    /// The closure is called with the key and value of each entry in `self`, and the output `SignalMap`
+    /// only contains the entries where the closure returned `true`.
+    ///
+    /// When an entry is updated, the closure is called again: if the result changed then the output
+    /// `SignalMap` notifies with `MapDiff::Insert` or `MapDiff::Remove`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "std")] {
+    /// # use futures_signals::signal_map::{MutableBTreeMap, SignalMapExt};
+    /// # let input = MutableBTreeMap::<u32, u32>::new();
+    /// let filtered = input.signal_map().filter(|_key, value| *value < 5);
+    /// # }
+    /// ```
+    ///
+    /// # Performance
+    ///
+    /// It stores the keys which are included, so it needs the keys to be `Ord + Clone`.
+    /// Each change is logarithmic with the number of included keys.
+    #[inline]
+    fn filter<F>(self, callback: F) -> Filter<Self, F>
+        where F: FnMut(&Self::Key, &Self::Value) -> bool,
+              Self::Key: Ord + Clone,
+              Self: Sized {
+        Filter {
+            keys: BTreeSet::new(),
+            signal: self,
+            callback,
+        }
+    }
+
+    /// Creates a `Signal` which contains the value for `key`.
+    ///
+    /// If `key` doesn't exist in `self` then the `Signal` contains `None`.
+    ///
+    /// It only changes when `key` is inserted, updated, or removed. Changes to other keys are ignored.
+    #[inline]
+    fn key_cloned(self, key: Self::Key) -> MapWatchKeySignal<Self>
+        where Self::Key: PartialEq,
+              Self::Value: Clone,
+              Self: Sized {
+        MapWatchKeySignal {
+            signal: Some(self),
+            key,
+            value: None,
+            first: true,
+        }
+    }
+
+    /// Creates a `Signal` which contains the number of entries in `self`.
+    ///
+    /// The `Signal` only changes when the number of entries changes: updates are ignored,
+    /// and if several diffs arrive at the same time and the number of entries ends up the
+    /// same, it won't notify at all.
+    #[inline]
+    fn len(self) -> Len<Self> where Self: Sized {
+        Len {
+            signal: Some(self),
+            first: true,
+            len: 0,
+        }
+    }
+
+    /// Creates a `Signal` which is `true` if `self` is empty, and `false` otherwise.
+    ///
+    /// It only changes when `self` goes from empty to non-empty (or vice versa).
+    #[inline]
+    #[allow(clippy::wrong_self_convention)]
+    fn is_empty(self) -> IsEmpty<Self> where Self: Sized {
+        IsEmpty {
+            signal: self.len().map((|len| len == 0) as LenIsZero).dedupe(),
+        }
+    }
+
     /// A convenience for calling `SignalMap::poll_map_change` on `Unpin` types.
     #[inline]
     fn poll_map_change_unpin(&mut self, cx: &mut Context) -> Poll<Option<MapDiff<Self::Key, Self::Value>>> where Self: Unpin + Sized {
@@ -128,6 +299,7 @@ impl<A, B, F> SignalMap for MapValue<A, F>
 
     // TODO should this inline ?
     #[inline]
+    #[allow(clippy::redundant_closure)]
     fn poll_map_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<MapDiff<Self::Key, Self::Value>>> {
         unsafe_project!(self => {
             pin signal,
@@ -139,81 +311,499 @@ impl<A, B, F> SignalMap for MapValue<A, F>
 }
 
 
-// TODO verify that this is correct
-mod mutable_btree_map {
-    use super::{SignalMap, SignalMapExt, MapDiff};
-    use std::pin::Pin;
-    use std::marker::Unpin;
-    use std::fmt;
-    use std::ops::{Deref, Index};
-    use std::cmp::{Ord, Ordering};
-    use std::hash::{Hash, Hasher};
-    use std::collections::BTreeMap;
-    use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
-    use std::task::{Poll, Context};
-    use futures_channel::mpsc;
-    use futures_util::stream::StreamExt;
-    use serde::{Serialize, Deserialize, Serializer, Deserializer};
-    use crate::signal_vec::{SignalVec, VecDiff};
 
+#[derive(Debug)]
+#[must_use = "SignalMaps do nothing unless polled"]
+pub struct Filter<A, B> where A: SignalMap {
+    keys: BTreeSet<A::Key>,
+    signal: A,
+    callback: B,
+}
+
+impl<A, B> Unpin for Filter<A, B> where A: Unpin + SignalMap {}
 
-    #[derive(Debug)]
-    struct MutableBTreeState<K, V> {
-        values: BTreeMap<K, V>,
-        senders: Vec<mpsc::UnboundedSender<MapDiff<K, V>>>,
+impl<A, F> SignalMap for Filter<A, F>
+    where A: SignalMap,
+          A::Key: Ord + Clone,
+          F: FnMut(&A::Key, &A::Value) -> bool {
+    type Key = A::Key;
+    type Value = A::Value;
+
+    fn poll_map_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<MapDiff<Self::Key, Self::Value>>> {
+        unsafe_project!(self => {
+            mut keys,
+            pin signal,
+            mut callback,
+        });
+
+        loop {
+            return match signal.as_mut().poll_map_change(cx) {
+                Poll::Pending => Poll::Pending,
+                Poll::Ready(None) => Poll::Ready(None),
+                Poll::Ready(Some(change)) => match change {
+                    MapDiff::Replace { entries } => {
+                        keys.clear();
+
+                        Poll::Ready(Some(MapDiff::Replace {
+                            entries: entries.into_iter().filter(|(key, value)| {
+                                if callback(key, value) {
+                                    keys.insert(key.clone());
+                                    true
+
+                                } else {
+                                    false
+                                }
+                            }).collect()
+                        }))
+                    },
+
+                    MapDiff::Insert { key, value } => {
+                        if callback(&key, &value) {
+                            keys.insert(key.clone());
+                            Poll::Ready(Some(MapDiff::Insert { key, value }))
+
+                        } else {
+                            continue;
+                        }
+                    },
+
+                    MapDiff::Update { key, value } => {
+                        if callback(&key, &value) {
+                            if keys.contains(&key) {
+                                Poll::Ready(Some(MapDiff::Update { key, value }))
+
+                            } else {
+                                keys.insert(key.clone());
+                                Poll::Ready(Some(MapDiff::Insert { key, value }))
+                            }
+
+                        } else {
+                            if keys.remove(&key) {
+                                Poll::Ready(Some(MapDiff::Remove { key }))
+
+                            } else {
+                                continue;
+                            }
+                        }
+                    },
+
+                    MapDiff::Remove { key } => {
+                        if keys.remove(&key) {
+                            Poll::Ready(Some(MapDiff::Remove { key }))
+
+                        } else {
+                            continue;
+                        }
+                    },
+
+                    MapDiff::Clear {} => {
+                        keys.clear();
+                        Poll::Ready(Some(MapDiff::Clear {}))
+                    },
+                },
+            }
+        }
     }
+}
 
-    impl<K: Ord, V> MutableBTreeState<K, V> {
-        // TODO should this inline ?
-        #[inline]
-        fn notify<B: FnMut() -> MapDiff<K, V>>(&mut self, mut change: B) {
-            self.senders.retain(|sender| {
-                sender.unbounded_send(change()).is_ok()
-            });
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct MapWatchKeySignal<A> where A: SignalMap {
+    signal: Option<A>,
+    key: A::Key,
+    value: Option<A::Value>,
+    first: bool,
+}
+
+impl<A> Unpin for MapWatchKeySignal<A> where A: Unpin + SignalMap {}
+
+impl<A> Signal for MapWatchKeySignal<A>
+    where A: SignalMap,
+          A::Key: PartialEq,
+          A::Value: Clone {
+    type Item = Option<A::Value>;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut key,
+            mut value,
+            mut first,
+        });
+
+        let mut changed = false;
+
+        let done = loop {
+            break match signal.as_mut().as_pin_mut().map(|signal| signal.poll_map_change(cx)) {
+                None => {
+                    true
+                },
+                Some(Poll::Ready(None)) => {
+                    signal.set(None);
+                    true
+                },
+                Some(Poll::Ready(Some(change))) => {
+                    match change {
+                        MapDiff::Replace { entries } => {
+                            let new_value = entries.into_iter().find(|(k, _)| k == key).map(|(_, v)| v);
+
+                            if value.is_some() || new_value.is_some() {
+                                *value = new_value;
+                                changed = true;
+                            }
+                        },
+
+                        MapDiff::Insert { key: k, value: v } | MapDiff::Update { key: k, value: v } => {
+                            if k == *key {
+                                *value = Some(v);
+                                changed = true;
+                            }
+                        },
+
+                        MapDiff::Remove { key: k } => {
+                            if k == *key {
+                                *value = None;
+                                changed = true;
+                            }
+                        },
+
+                        MapDiff::Clear {} => {
+                            if value.is_some() {
+                                *value = None;
+                                changed = true;
+                            }
+                        },
+                    }
+
+                    continue;
+                },
+                Some(Poll::Pending) => {
+                    false
+                },
+            };
+        };
+
+        if changed || *first {
+            *first = false;
+            Poll::Ready(Some(value.clone()))
+
+        } else if done {
+            Poll::Ready(None)
+
+        } else {
+            Poll::Pending
         }
+    }
+}
 
-        // If there is only 1 sender then it won't clone at all.
-        // If there is more than 1 sender then it will clone N-1 times.
-        // TODO verify that this works correctly
-        #[inline]
-        fn notify_clone<A, F>(&mut self, value: Option<A>, mut f: F) where A: Clone, F: FnMut(A) -> MapDiff<K, V> {
-            if let Some(value) = value {
-                let mut len = self.senders.len();
 
-                if len > 0 {
-                    let mut copy = Some(value);
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Len<A> {
+    signal: Option<A>,
+    first: bool,
+    len: usize,
+}
 
-                    self.senders.retain(move |sender| {
-                        let value = copy.take().unwrap();
+impl<A> Unpin for Len<A> where A: Unpin {}
 
-                        len -= 1;
+impl<A> Signal for Len<A> where A: SignalMap {
+    type Item = usize;
 
-                        // This isn't the last element
-                        if len != 0 {
-                            copy = Some(value.clone());
-                        }
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut first,
+            mut len,
+        });
 
-                        sender.unbounded_send(f(value)).is_ok()
-                    });
-                }
+        // This is used to dedupe the changes, e.g. an Insert followed by a Remove doesn't change the len
+        let old_len = *len;
+
+        let done = loop {
+            break match signal.as_mut().as_pin_mut().map(|signal| signal.poll_map_change(cx)) {
+                None => {
+                    true
+                },
+                Some(Poll::Ready(None)) => {
+                    signal.set(None);
+                    true
+                },
+                Some(Poll::Ready(Some(change))) => {
+                    match change {
+                        MapDiff::Replace { entries } => {
+                            *len = entries.len();
+                        },
+
+                        MapDiff::Insert { .. } => {
+                            *len += 1;
+                        },
+
+                        MapDiff::Update { .. } => {},
+
+                        MapDiff::Remove { .. } => {
+                            *len -= 1;
+                        },
+
+                        MapDiff::Clear {} => {
+                            *len = 0;
+                        },
+                    }
+
+                    continue;
+                },
+                Some(Poll::Pending) => {
+                    false
+                },
+            };
+        };
+
+        if *len != old_len || *first {
+            *first = false;
+            Poll::Ready(Some(*len))
+
+        } else if done {
+            Poll::Ready(None)
+
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+
+type LenIsZero = fn(usize) -> bool;
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct IsEmpty<A> where A: SignalMap {
+    signal: Dedupe<Map<Len<A>, LenIsZero>>,
+}
+
+impl<A> Unpin for IsEmpty<A> where A: Unpin + SignalMap {}
+
+impl<A> Signal for IsEmpty<A> where A: SignalMap {
+    type Item = bool;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+        });
+
+        signal.poll_change(cx)
+    }
+}
+
+// This is shared by MutableBTreeMap and MutableHashMap
+#[cfg(feature = "std")]
+#[derive(Debug)]
+#[must_use = "SignalMaps do nothing unless polled"]
+pub struct MutableSignalMap<K, V> {
+    receiver: mpsc::UnboundedReceiver<MapDiff<K, V>>,
+}
+
+#[cfg(feature = "std")]
+impl<K, V> Unpin for MutableSignalMap<K, V> {}
+
+#[cfg(feature = "std")]
+impl<K, V> SignalMap for MutableSignalMap<K, V> {
+    type Key = K;
+    type Value = V;
+
+    #[inline]
+    fn poll_map_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<MapDiff<Self::Key, Self::Value>>> {
+        self.receiver.poll_next_unpin(cx)
+    }
+}
+
+
+/// A `Signal` which contains a snapshot of a `SignalMap`, as an [`im::OrdMap`](https://docs.rs/im/15/im/struct.OrdMap.html).
+///
+/// The changes are applied to the `im::OrdMap`, so each change only takes `O(log n)` time, and
+/// cloning the snapshot is `O(1)`. This makes it cheap to view the whole collection at once.
+///
+/// This is only available with the `im` feature.
+#[cfg(feature = "im")]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct ImOrdMapSignal<A> where A: SignalMap, A::Key: Ord + Clone, A::Value: Clone {
+    signal: Option<A>,
+    values: im::OrdMap<A::Key, A::Value>,
+    first: bool,
+}
+
+#[cfg(feature = "im")]
+impl<A> ImOrdMapSignal<A> where A: SignalMap, A::Key: Ord + Clone, A::Value: Clone {
+    #[inline]
+    pub fn new(signal: A) -> Self {
+        Self {
+            signal: Some(signal),
+            values: im::OrdMap::new(),
+            first: true,
+        }
+    }
+}
+
+#[cfg(feature = "im")]
+impl<A> Unpin for ImOrdMapSignal<A> where A: SignalMap + Unpin, A::Key: Ord + Clone, A::Value: Clone {}
+
+#[cfg(feature = "im")]
+impl<A> Signal for ImOrdMapSignal<A> where A: SignalMap, A::Key: Ord + Clone, A::Value: Clone {
+    type Item = im::OrdMap<A::Key, A::Value>;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut values,
+            mut first,
+        });
+
+        let mut changed = false;
+
+        loop {
+            match signal.as_mut().as_pin_mut().map(|signal| signal.poll_map_change(cx)) {
+                None => {},
+                Some(Poll::Ready(None)) => {
+                    signal.set(None);
+                },
+                Some(Poll::Ready(Some(change))) => {
+                    match change {
+                        MapDiff::Replace { entries } => {
+                            *values = entries.into_iter().collect();
+                        },
+                        MapDiff::Insert { key, value } | MapDiff::Update { key, value } => {
+                            values.insert(key, value);
+                        },
+                        MapDiff::Remove { key } => {
+                            values.remove(&key);
+                        },
+                        MapDiff::Clear {} => {
+                            values.clear();
+                        },
+                    }
+
+                    changed = true;
+                    continue;
+                },
+                Some(Poll::Pending) => {},
             }
+            break;
         }
 
-        #[inline]
-        fn change<A, F>(&self, f: F) -> Option<A> where F: FnOnce() -> A {
-            if self.senders.is_empty() {
-                None
+        if changed || *first {
+            *first = false;
+            Poll::Ready(Some(values.clone()))
 
-            } else {
-                Some(f())
+        } else if signal.is_none() {
+            Poll::Ready(None)
+
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+
+// This is shared by MutableBTreeMap, MutableHashMap, and MutableSet
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub(crate) struct Senders<A> {
+    senders: Vec<mpsc::UnboundedSender<A>>,
+}
+
+#[cfg(feature = "std")]
+impl<A> Senders<A> {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Self { senders: vec![] }
+    }
+
+    #[inline]
+    pub(crate) fn push(&mut self, sender: mpsc::UnboundedSender<A>) {
+        self.senders.push(sender);
+    }
+
+    // TODO should this inline ?
+    #[inline]
+    pub(crate) fn notify<B: FnMut() -> A>(&mut self, mut change: B) {
+        self.senders.retain(|sender| {
+            sender.unbounded_send(change()).is_ok()
+        });
+    }
+
+    // If there is only 1 sender then it won't clone at all.
+    // If there is more than 1 sender then it will clone N-1 times.
+    // TODO verify that this works correctly
+    #[inline]
+    pub(crate) fn notify_clone<B, F>(&mut self, value: Option<B>, mut f: F) where B: Clone, F: FnMut(B) -> A {
+        if let Some(value) = value {
+            let mut len = self.senders.len();
+
+            if len > 0 {
+                let mut copy = Some(value);
+
+                self.senders.retain(move |sender| {
+                    let value = copy.take().unwrap();
+
+                    len -= 1;
+
+                    // This isn't the last element
+                    if len != 0 {
+                        copy = Some(value.clone());
+                    }
+
+                    sender.unbounded_send(f(value)).is_ok()
+                });
             }
         }
+    }
+
+    #[inline]
+    pub(crate) fn change<B, F>(&self, f: F) -> Option<B> where F: FnOnce() -> B {
+        if self.senders.is_empty() {
+            None
+
+        } else {
+            Some(f())
+        }
+    }
+}
+
+
+// TODO verify that this is correct
+#[cfg(feature = "std")]
+mod mutable_btree_map {
+    use super::{SignalMapExt, MapDiff, MutableSignalMap, Senders};
+    use core::pin::Pin;
+    use core::marker::Unpin;
+    use core::fmt;
+    use core::ops::{Deref, Index};
+    use core::cmp::{Ord, Ordering};
+    use core::hash::{Hash, Hasher};
+    use alloc::collections::BTreeMap;
+    use alloc::sync::Arc;
+    use crate::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+    use core::task::{Poll, Context};
+    use futures_channel::mpsc;
+    use serde::{Serialize, Deserialize, Serializer, Deserializer};
+    use crate::signal_vec::{SignalVec, VecDiff};
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+
+    #[derive(Debug)]
+    struct MutableBTreeState<K, V> {
+        values: BTreeMap<K, V>,
+        senders: Senders<MapDiff<K, V>>,
+    }
 
+    impl<K: Ord, V> MutableBTreeState<K, V> {
         fn clear(&mut self) {
             if !self.values.is_empty() {
                 self.values.clear();
 
-                self.notify(|| MapDiff::Clear {});
+                self.senders.notify(|| MapDiff::Clear {});
             }
         }
     }
@@ -222,14 +812,15 @@ mod mutable_btree_map {
         fn remove(&mut self, key: &K) -> Option<V> {
             let value = self.values.remove(key)?;
 
-            let key = self.change(|| key.clone());
-            self.notify_clone(key, |key| MapDiff::Remove { key });
+            let key = self.senders.change(|| key.clone());
+            self.senders.notify_clone(key, |key| MapDiff::Remove { key });
 
             Some(value)
         }
     }
 
     impl<K: Clone + Ord, V: Clone> MutableBTreeState<K, V> {
+        #[allow(clippy::into_iter_on_ref)]
         fn entries(values: &BTreeMap<K, V>) -> Vec<(K, V)> {
             values.into_iter().map(|(k, v)| {
                 (k.clone(), v.clone())
@@ -237,22 +828,22 @@ mod mutable_btree_map {
         }
 
         fn replace_cloned(&mut self, values: BTreeMap<K, V>) {
-            let entries = self.change(|| Self::entries(&values));
+            let entries = self.senders.change(|| Self::entries(&values));
 
             self.values = values;
 
-            self.notify_clone(entries, |entries| MapDiff::Replace { entries });
+            self.senders.notify_clone(entries, |entries| MapDiff::Replace { entries });
         }
 
         fn insert_cloned(&mut self, key: K, value: V) -> Option<V> {
-            let x = self.change(|| (key.clone(), value.clone()));
+            let x = self.senders.change(|| (key.clone(), value.clone()));
 
             if let Some(value) = self.values.insert(key, value) {
-                self.notify_clone(x, |(key, value)| MapDiff::Update { key, value });
+                self.senders.notify_clone(x, |(key, value)| MapDiff::Update { key, value });
                 Some(value)
 
             } else {
-                self.notify_clone(x, |(key, value)| MapDiff::Insert { key, value });
+                self.senders.notify_clone(x, |(key, value)| MapDiff::Insert { key, value });
                 None
             }
         }
@@ -277,11 +868,13 @@ mod mutable_btree_map {
 
     macro_rules! make_shared {
         ($t:ty) => {
+            #[allow(clippy::partialeq_ne_impl)]
             impl<'a, K, V> PartialEq<BTreeMap<K, V>> for $t where K: PartialEq<K>, V: PartialEq<V> {
                 #[inline] fn eq(&self, other: &BTreeMap<K, V>) -> bool { **self == *other }
                 #[inline] fn ne(&self, other: &BTreeMap<K, V>) -> bool { **self != *other }
             }
 
+            #[allow(clippy::partialeq_ne_impl)]
             impl<'a, K, V> PartialEq<$t> for $t where K: PartialEq<K>, V: PartialEq<V> {
                 #[inline] fn eq(&self, other: &$t) -> bool { *self == **other }
                 #[inline] fn ne(&self, other: &$t) -> bool { *self != **other }
@@ -367,6 +960,18 @@ mod mutable_btree_map {
         }
     }
 
+    impl<'a, K, V> MutableBTreeMapLockMut<'a, K, V> where K: Ord + Copy, V: Copy {
+        #[inline]
+        pub fn replace(&mut self, values: BTreeMap<K, V>) {
+            self.lock.replace_cloned(values)
+        }
+
+        #[inline]
+        pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+            self.lock.insert_cloned(key, value)
+        }
+    }
+
     impl<'a, K, V> MutableBTreeMapLockMut<'a, K, V> where K: Ord + Clone, V: Clone {
         #[inline]
         pub fn replace_cloned(&mut self, values: BTreeMap<K, V>) {
@@ -390,23 +995,23 @@ mod mutable_btree_map {
         pub fn with_values(values: BTreeMap<K, V>) -> Self {
             Self(Arc::new(RwLock::new(MutableBTreeState {
                 values,
-                senders: vec![],
+                senders: Senders::new(),
             })))
         }
 
         // TODO return Result ?
         #[inline]
-        pub fn lock_ref(&self) -> MutableBTreeMapLockRef<K, V> {
+        pub fn lock_ref(&self) -> MutableBTreeMapLockRef<'_, K, V> {
             MutableBTreeMapLockRef {
-                lock: self.0.read().unwrap(),
+                lock: self.0.read(),
             }
         }
 
         // TODO return Result ?
         #[inline]
-        pub fn lock_mut(&self) -> MutableBTreeMapLockMut<K, V> {
+        pub fn lock_mut(&self) -> MutableBTreeMapLockMut<'_, K, V> {
             MutableBTreeMapLockMut {
-                lock: self.0.write().unwrap(),
+                lock: self.0.write(),
             }
         }
     }
@@ -418,12 +1023,31 @@ mod mutable_btree_map {
         }
     }
 
+    impl<K, V> MutableBTreeMap<K, V> where K: Ord + Copy, V: Copy {
+        #[inline]
+        pub fn signal_map(&self) -> MutableSignalMap<K, V> {
+            self.0.write().signal_map_cloned()
+        }
+    }
+
     impl<K, V> MutableBTreeMap<K, V> where K: Ord + Clone, V: Clone {
         #[inline]
         pub fn signal_map_cloned(&self) -> MutableSignalMap<K, V> {
-            self.0.write().unwrap().signal_map_cloned()
+            self.0.write().signal_map_cloned()
+        }
+
+        /// Returns a `Signal` which contains a snapshot of the whole `MutableBTreeMap`, as an `im::OrdMap`.
+        ///
+        /// Cloning the snapshot is `O(1)`, and each change only takes `O(log n)` time.
+        ///
+        /// This is only available with the `im` feature.
+        #[cfg(feature = "im")]
+        #[inline]
+        pub fn signal_cloned(&self) -> super::ImOrdMapSignal<MutableSignalMap<K, V>> {
+            super::ImOrdMapSignal::new(self.signal_map_cloned())
         }
 
+        /// Returns a `SignalVec` which contains the keys of the map, in sorted order.
         #[inline]
         pub fn signal_vec_keys(&self) -> MutableBTreeMapKeys<K, V> {
             MutableBTreeMapKeys {
@@ -432,19 +1056,25 @@ mod mutable_btree_map {
             }
         }
 
-        // TODO deprecate and rename to signal_vec_entries
+        /// Returns a `SignalVec` which contains the `(key, value)` entries of the map, sorted by key.
         #[inline]
-        pub fn entries_cloned(&self) -> MutableBTreeMapEntries<K, V> {
+        pub fn signal_vec_entries(&self) -> MutableBTreeMapEntries<K, V> {
             MutableBTreeMapEntries {
                 signal: self.signal_map_cloned(),
                 keys: vec![],
             }
         }
+
+        #[deprecated(since = "0.3.16", note = "Use signal_vec_entries instead")]
+        #[inline]
+        pub fn entries_cloned(&self) -> MutableBTreeMapEntries<K, V> {
+            self.signal_vec_entries()
+        }
     }
 
     impl<K, V> fmt::Debug for MutableBTreeMap<K, V> where K: fmt::Debug, V: fmt::Debug {
         fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-            let state = self.0.read().unwrap();
+            let state = self.0.read();
 
             fmt.debug_tuple("MutableBTreeMap")
                 .field(&state.values)
@@ -455,7 +1085,7 @@ mod mutable_btree_map {
     impl<K, V> Serialize for MutableBTreeMap<K, V> where BTreeMap<K, V>: Serialize {
         #[inline]
         fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
-            self.0.read().unwrap().values.serialize(serializer)
+            self.0.read().values.serialize(serializer)
         }
     }
 
@@ -474,25 +1104,6 @@ mod mutable_btree_map {
     }
 
 
-    #[derive(Debug)]
-    #[must_use = "SignalMaps do nothing unless polled"]
-    pub struct MutableSignalMap<K, V> {
-        receiver: mpsc::UnboundedReceiver<MapDiff<K, V>>,
-    }
-
-    impl<K, V> Unpin for MutableSignalMap<K, V> {}
-
-    impl<K, V> SignalMap for MutableSignalMap<K, V> {
-        type Key = K;
-        type Value = V;
-
-        #[inline]
-        fn poll_map_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<MapDiff<Self::Key, Self::Value>>> {
-            self.receiver.poll_next_unpin(cx)
-        }
-    }
-
-
     #[derive(Debug)]
     #[must_use = "SignalVecs do nothing unless polled"]
     pub struct MutableBTreeMapKeys<K, V> {
@@ -586,4 +1197,511 @@ mod mutable_btree_map {
     }
 }
 
+#[cfg(feature = "std")]
 pub use self::mutable_btree_map::*;
+
+
+// TODO verify that this is correct
+#[cfg(feature = "std")]
+mod mutable_hash_map {
+    use super::{MapDiff, MutableSignalMap, Senders};
+    use core::fmt;
+    use core::ops::{Deref, Index};
+    use core::hash::{Hash, BuildHasher};
+    use core::borrow::Borrow;
+    use std::collections::HashMap;
+    use std::collections::hash_map::RandomState;
+    use alloc::sync::Arc;
+    use crate::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+    use futures_channel::mpsc;
+    use serde::{Serialize, Deserialize, Serializer, Deserializer};
+    use alloc::vec::Vec;
+
+
+    #[derive(Debug)]
+    struct MutableHashState<K, V, S> {
+        values: HashMap<K, V, S>,
+        senders: Senders<MapDiff<K, V>>,
+    }
+
+    impl<K, V, S> MutableHashState<K, V, S> where K: Hash + Eq, S: BuildHasher {
+        fn clear(&mut self) {
+            if !self.values.is_empty() {
+                self.values.clear();
+
+                self.senders.notify(|| MapDiff::Clear {});
+            }
+        }
+    }
+
+    impl<K, V, S> MutableHashState<K, V, S> where K: Hash + Eq + Clone, S: BuildHasher {
+        fn remove(&mut self, key: &K) -> Option<V> {
+            let value = self.values.remove(key)?;
+
+            let key = self.senders.change(|| key.clone());
+            self.senders.notify_clone(key, |key| MapDiff::Remove { key });
+
+            Some(value)
+        }
+    }
+
+    impl<K, V, S> MutableHashState<K, V, S> where K: Hash + Eq + Clone, V: Clone, S: BuildHasher {
+        fn entries(values: &HashMap<K, V, S>) -> Vec<(K, V)> {
+            values.iter().map(|(k, v)| {
+                (k.clone(), v.clone())
+            }).collect()
+        }
+
+        fn replace_cloned(&mut self, values: HashMap<K, V, S>) {
+            let entries = self.senders.change(|| Self::entries(&values));
+
+            self.values = values;
+
+            self.senders.notify_clone(entries, |entries| MapDiff::Replace { entries });
+        }
+
+        fn insert_cloned(&mut self, key: K, value: V) -> Option<V> {
+            let x = self.senders.change(|| (key.clone(), value.clone()));
+
+            if let Some(value) = self.values.insert(key, value) {
+                self.senders.notify_clone(x, |(key, value)| MapDiff::Update { key, value });
+                Some(value)
+
+            } else {
+                self.senders.notify_clone(x, |(key, value)| MapDiff::Insert { key, value });
+                None
+            }
+        }
+
+        fn signal_map_cloned(&mut self) -> MutableSignalMap<K, V> {
+            let (sender, receiver) = mpsc::unbounded();
+
+            if !self.values.is_empty() {
+                sender.unbounded_send(MapDiff::Replace {
+                    entries: Self::entries(&self.values),
+                }).unwrap();
+            }
+
+            self.senders.push(sender);
+
+            MutableSignalMap {
+                receiver
+            }
+        }
+    }
+
+
+    macro_rules! make_shared {
+        ($t:ty) => {
+            impl<'a, K, V, S> PartialEq<HashMap<K, V, S>> for $t where K: Hash + Eq, V: PartialEq, S: BuildHasher {
+                #[inline] fn eq(&self, other: &HashMap<K, V, S>) -> bool { **self == *other }
+            }
+
+            impl<'a, K, V, S> PartialEq<$t> for $t where K: Hash + Eq, V: PartialEq, S: BuildHasher {
+                #[inline] fn eq(&self, other: &$t) -> bool { *self == **other }
+            }
+
+            impl<'a, K, V, S> Eq for $t where K: Hash + Eq, V: Eq, S: BuildHasher {}
+
+            impl<'a, 'b, K, Q, V, S> Index<&'b Q> for $t where K: Hash + Eq + Borrow<Q>, Q: Hash + Eq + ?Sized, S: BuildHasher {
+                type Output = V;
+
+                #[inline]
+                fn index(&self, key: &'b Q) -> &Self::Output {
+                    Index::index(&**self, key)
+                }
+            }
+
+            impl<'a, K, V, S> Deref for $t {
+                type Target = HashMap<K, V, S>;
+
+                #[inline]
+                fn deref(&self) -> &Self::Target {
+                    &self.lock.values
+                }
+            }
+        };
+    }
+
+
+    #[derive(Debug)]
+    pub struct MutableHashMapLockRef<'a, K, V, S = RandomState> where K: 'a, V: 'a, S: 'a {
+        lock: RwLockReadGuard<'a, MutableHashState<K, V, S>>,
+    }
+
+    make_shared!(MutableHashMapLockRef<'a, K, V, S>);
+
+
+    #[derive(Debug)]
+    pub struct MutableHashMapLockMut<'a, K, V, S = RandomState> where K: 'a, V: 'a, S: 'a {
+        lock: RwLockWriteGuard<'a, MutableHashState<K, V, S>>,
+    }
+
+    make_shared!(MutableHashMapLockMut<'a, K, V, S>);
+
+    impl<'a, K, V, S> MutableHashMapLockMut<'a, K, V, S> where K: Hash + Eq, S: BuildHasher {
+        #[inline]
+        pub fn clear(&mut self) {
+            self.lock.clear()
+        }
+    }
+
+    impl<'a, K, V, S> MutableHashMapLockMut<'a, K, V, S> where K: Hash + Eq + Clone, S: BuildHasher {
+        #[inline]
+        pub fn remove(&mut self, key: &K) -> Option<V> {
+            self.lock.remove(key)
+        }
+    }
+
+    impl<'a, K, V, S> MutableHashMapLockMut<'a, K, V, S> where K: Hash + Eq + Copy, V: Copy, S: BuildHasher {
+        #[inline]
+        pub fn replace(&mut self, values: HashMap<K, V, S>) {
+            self.lock.replace_cloned(values)
+        }
+
+        #[inline]
+        pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+            self.lock.insert_cloned(key, value)
+        }
+    }
+
+    impl<'a, K, V, S> MutableHashMapLockMut<'a, K, V, S> where K: Hash + Eq + Clone, V: Clone, S: BuildHasher {
+        #[inline]
+        pub fn replace_cloned(&mut self, values: HashMap<K, V, S>) {
+            self.lock.replace_cloned(values)
+        }
+
+        #[inline]
+        pub fn insert_cloned(&mut self, key: K, value: V) -> Option<V> {
+            self.lock.insert_cloned(key, value)
+        }
+    }
+
+
+    /// A reactive map backed by a `HashMap`.
+    ///
+    /// This is the same as [`MutableBTreeMap`](struct.MutableBTreeMap.html), except the keys only need
+    /// to be `Hash + Eq` rather than `Ord`. Because the entries are unordered, it doesn't have
+    /// `signal_vec_keys` or `signal_vec_entries`.
+    // TODO get rid of the Arc
+    // TODO impl some of the same traits as HashMap
+    pub struct MutableHashMap<K, V, S = RandomState>(Arc<RwLock<MutableHashState<K, V, S>>>);
+
+    impl<K, V, S> MutableHashMap<K, V, S> {
+        // TODO deprecate this and replace with From ?
+        #[inline]
+        pub fn with_values(values: HashMap<K, V, S>) -> Self {
+            Self(Arc::new(RwLock::new(MutableHashState {
+                values,
+                senders: Senders::new(),
+            })))
+        }
+
+        // TODO return Result ?
+        #[inline]
+        pub fn lock_ref(&self) -> MutableHashMapLockRef<'_, K, V, S> {
+            MutableHashMapLockRef {
+                lock: self.0.read(),
+            }
+        }
+
+        // TODO return Result ?
+        #[inline]
+        pub fn lock_mut(&self) -> MutableHashMapLockMut<'_, K, V, S> {
+            MutableHashMapLockMut {
+                lock: self.0.write(),
+            }
+        }
+    }
+
+    impl<K, V> MutableHashMap<K, V, RandomState> where K: Hash + Eq {
+        #[inline]
+        pub fn new() -> Self {
+            Self::with_values(HashMap::new())
+        }
+    }
+
+    impl<K, V, S> MutableHashMap<K, V, S> where K: Hash + Eq + Copy, V: Copy, S: BuildHasher {
+        #[inline]
+        pub fn signal_map(&self) -> MutableSignalMap<K, V> {
+            self.0.write().signal_map_cloned()
+        }
+    }
+
+    impl<K, V, S> MutableHashMap<K, V, S> where K: Hash + Eq + Clone, V: Clone, S: BuildHasher {
+        #[inline]
+        pub fn signal_map_cloned(&self) -> MutableSignalMap<K, V> {
+            self.0.write().signal_map_cloned()
+        }
+    }
+
+    impl<K, V, S> fmt::Debug for MutableHashMap<K, V, S> where K: fmt::Debug, V: fmt::Debug {
+        fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            let state = self.0.read();
+
+            fmt.debug_tuple("MutableHashMap")
+                .field(&state.values)
+                .finish()
+        }
+    }
+
+    impl<K, V, S> Serialize for MutableHashMap<K, V, S> where HashMap<K, V, S>: Serialize {
+        #[inline]
+        fn serialize<T>(&self, serializer: T) -> Result<T::Ok, T::Error> where T: Serializer {
+            self.0.read().values.serialize(serializer)
+        }
+    }
+
+    impl<'de, K, V, S> Deserialize<'de> for MutableHashMap<K, V, S> where HashMap<K, V, S>: Deserialize<'de> {
+        #[inline]
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+            <HashMap<K, V, S>>::deserialize(deserializer).map(MutableHashMap::with_values)
+        }
+    }
+
+    impl<K, V, S> Default for MutableHashMap<K, V, S> where S: Default {
+        #[inline]
+        fn default() -> Self {
+            MutableHashMap::with_values(HashMap::default())
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub use self::mutable_hash_map::*;
+
+
+#[cfg(feature = "std")]
+mod broadcaster_map {
+    use super::{SignalMap, MapDiff};
+    use core::fmt;
+    use core::pin::Pin;
+    use alloc::collections::BTreeMap;
+    use alloc::sync::Arc;
+    use crate::sync::Ordering;
+    use crate::sync::Mutex;
+    use core::task::{Poll, Context};
+    use futures_channel::mpsc;
+    use futures_util::stream::StreamExt;
+    use futures_util::task;
+    use crate::signal::broadcaster::{BroadcasterStatus, BroadcasterNotifier};
+    use alloc::boxed::Box;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+
+    struct BroadcasterMapInnerState<A> where A: SignalMap {
+        signal: Option<Pin<Box<A>>>,
+        entries: BTreeMap<A::Key, A::Value>,
+        senders: Vec<mpsc::UnboundedSender<MapDiff<A::Key, A::Value>>>,
+    }
+
+    impl<A> BroadcasterMapInnerState<A> where A: SignalMap, A::Key: Ord + Clone, A::Value: Clone {
+        fn apply(&mut self, change: MapDiff<A::Key, A::Value>) {
+            match change {
+                MapDiff::Replace { entries } => {
+                    self.entries = entries.into_iter().collect();
+                },
+                MapDiff::Insert { key, value } | MapDiff::Update { key, value } => {
+                    self.entries.insert(key, value);
+                },
+                MapDiff::Remove { key } => {
+                    self.entries.remove(&key);
+                },
+                MapDiff::Clear {} => {
+                    self.entries.clear();
+                },
+            }
+        }
+
+        // Poll the underlying signal for changes, forwarding them to every child and
+        // keeping `entries` up to date so that new children can start from a snapshot.
+        fn poll_underlying(&mut self, notifier: Arc<BroadcasterNotifier>) {
+            let waker = task::waker(notifier);
+            let cx = &mut Context::from_waker(&waker);
+
+            loop {
+                match self.signal.as_mut().map(|signal| signal.as_mut().poll_map_change(cx)) {
+                    Some(Poll::Ready(Some(change))) => {
+                        self.senders.retain(|sender| sender.unbounded_send(change.clone()).is_ok());
+                        self.apply(change);
+                    },
+                    Some(Poll::Ready(None)) => {
+                        self.signal = None;
+                        // This causes the children to end once they've received all of the changes
+                        self.senders.clear();
+                        break;
+                    },
+                    Some(Poll::Pending) | None => {
+                        break;
+                    },
+                }
+            }
+        }
+    }
+
+
+    struct BroadcasterMapSharedState<A> where A: SignalMap {
+        inner: Mutex<BroadcasterMapInnerState<A>>,
+        notifier: Arc<BroadcasterNotifier>,
+    }
+
+    impl<A> fmt::Debug for BroadcasterMapSharedState<A>
+        where A: fmt::Debug + SignalMap,
+              A::Key: fmt::Debug,
+              A::Value: fmt::Debug {
+
+        fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            let lock = self.inner.lock();
+
+            fmt.debug_struct("BroadcasterMapSharedState")
+                .field("signal", &lock.signal)
+                .field("entries", &lock.entries)
+                .field("notifier", &self.notifier)
+                .finish()
+        }
+    }
+
+
+    /// Wraps any `SignalMap` to make it possible to "broadcast" it to several
+    /// consumers.
+    ///
+    /// Each child `SignalMap` starts with a `MapDiff::Replace` containing the current
+    /// entries, and then receives the same changes as the original `SignalMap`.
+    pub struct BroadcasterMap<A> where A: SignalMap {
+        shared_state: Arc<BroadcasterMapSharedState<A>>,
+    }
+
+    impl<A> BroadcasterMap<A> where A: SignalMap, A::Key: Ord {
+        /// Create a new `BroadcasterMap`
+        pub fn new(signal: A) -> Self {
+            Self {
+                shared_state: Arc::new(BroadcasterMapSharedState {
+                    inner: Mutex::new(BroadcasterMapInnerState {
+                        signal: Some(Box::pin(signal)),
+                        entries: BTreeMap::new(),
+                        senders: vec![],
+                    }),
+                    notifier: Arc::new(BroadcasterNotifier::new()),
+                }),
+            }
+        }
+    }
+
+    impl<A> BroadcasterMap<A> where A: SignalMap, A::Key: Ord + Clone, A::Value: Clone {
+        /// Create a new `SignalMap` which clones entries from the `SignalMap` wrapped
+        /// by the `BroadcasterMap`
+        pub fn signal_map_cloned(&self) -> BroadcasterSignalMap<A> {
+            let (sender, receiver) = mpsc::unbounded();
+
+            {
+                let shared_state = &self.shared_state;
+                let mut lock = shared_state.inner.lock();
+
+                // This brings the entries up to date, so that the Replace isn't followed by an older Replace
+                if shared_state.notifier.is_changed.swap(false, Ordering::AcqRel) {
+                    lock.poll_underlying(shared_state.notifier.clone());
+                }
+
+                // This is sent even if it is empty, so every child starts with the current state
+                sender.unbounded_send(MapDiff::Replace {
+                    entries: lock.entries.iter().map(|(key, value)| (key.clone(), value.clone())).collect(),
+                }).unwrap();
+
+                // If the signal has already ended then the sender is dropped, so the child ends after the Replace
+                if lock.signal.is_some() {
+                    lock.senders.push(sender);
+                }
+            }
+
+            let status = Arc::new(BroadcasterStatus::new());
+
+            {
+                let mut lock = self.shared_state.notifier.targets.lock();
+                lock.push(Arc::downgrade(&status));
+            }
+
+            BroadcasterSignalMap {
+                status,
+                receiver,
+                shared_state: self.shared_state.clone(),
+            }
+        }
+    }
+
+    impl<A> Clone for BroadcasterMap<A> where A: SignalMap {
+        #[inline]
+        fn clone(&self) -> Self {
+            Self {
+                shared_state: self.shared_state.clone(),
+            }
+        }
+    }
+
+    impl<A> fmt::Debug for BroadcasterMap<A>
+        where A: fmt::Debug + SignalMap,
+              A::Key: fmt::Debug,
+              A::Value: fmt::Debug {
+
+        fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.debug_struct("BroadcasterMap")
+                .field("shared_state", &self.shared_state)
+                .finish()
+        }
+    }
+
+
+    #[must_use = "SignalMaps do nothing unless polled"]
+    pub struct BroadcasterSignalMap<A> where A: SignalMap {
+        status: Arc<BroadcasterStatus>,
+        receiver: mpsc::UnboundedReceiver<MapDiff<A::Key, A::Value>>,
+        shared_state: Arc<BroadcasterMapSharedState<A>>,
+    }
+
+    impl<A> Unpin for BroadcasterSignalMap<A> where A: SignalMap {}
+
+    impl<A> SignalMap for BroadcasterSignalMap<A> where A: SignalMap, A::Key: Ord + Clone, A::Value: Clone {
+        type Key = A::Key;
+        type Value = A::Value;
+
+        fn poll_map_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<MapDiff<Self::Key, Self::Value>>> {
+            let shared_state = &self.shared_state;
+
+            // Only one child needs to poll the underlying signal, it then sends the changes to every child
+            if shared_state.notifier.is_changed.swap(false, Ordering::AcqRel) {
+                shared_state.inner.lock().poll_underlying(shared_state.notifier.clone());
+            }
+
+            match self.receiver.poll_next_unpin(cx) {
+                Poll::Pending => {
+                    *self.status.waker.lock() = Some(cx.waker().clone());
+
+                    // The underlying signal might have been woken up after it was polled
+                    if self.shared_state.notifier.is_changed.load(Ordering::Acquire) {
+                        cx.waker().wake_by_ref();
+                    }
+
+                    Poll::Pending
+                },
+                poll => poll,
+            }
+        }
+    }
+
+    impl<A> fmt::Debug for BroadcasterSignalMap<A>
+        where A: fmt::Debug + SignalMap,
+              A::Key: fmt::Debug,
+              A::Value: fmt::Debug {
+
+        fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.debug_struct("BroadcasterSignalMap")
+                .field("status", &self.status)
+                .field("receiver", &self.receiver)
+                .field("shared_state", &self.shared_state)
+                .finish()
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub use self::broadcaster_map::*;
@@ -0,0 +1,487 @@
+use core::pin::Pin;
+use core::marker::Unpin;
+use core::task::{Poll, Context};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use crate::signal::Signal;
+#[cfg(feature = "std")]
+use futures_channel::mpsc;
+#[cfg(feature = "std")]
+use futures_util::stream::StreamExt;
+
+
+// TODO make this non-exhaustive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SetDiff<A> {
+    Replace {
+        values: Vec<A>,
+    },
+
+    Insert {
+        value: A,
+    },
+
+    Remove {
+        value: A,
+    },
+
+    Clear {},
+}
+
+
+// TODO impl for AssertUnwindSafe ?
+#[must_use = "SignalSets do nothing unless polled"]
+pub trait SignalSet {
+    type Item;
+
+    fn poll_set_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<SetDiff<Self::Item>>>;
+}
+
+
+// Copied from Future in the Rust stdlib
+impl<A> SignalSet for &mut A where A: ?Sized + SignalSet + Unpin {
+    type Item = A::Item;
+
+    #[inline]
+    fn poll_set_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<SetDiff<Self::Item>>> {
+        A::poll_set_change(Pin::new(&mut **self), cx)
+    }
+}
+
+// Copied from Future in the Rust stdlib
+impl<A> SignalSet for Box<A> where A: ?Sized + SignalSet + Unpin {
+    type Item = A::Item;
+
+    #[inline]
+    fn poll_set_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<SetDiff<Self::Item>>> {
+        A::poll_set_change(Pin::new(&mut *self), cx)
+    }
+}
+
+// Copied from Future in the Rust stdlib
+impl<A> SignalSet for Pin<A>
+    where A: Unpin + ::core::ops::DerefMut,
+          A::Target: SignalSet {
+    type Item = <<A as ::core::ops::Deref>::Target as SignalSet>::Item;
+
+    #[inline]
+    fn poll_set_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<SetDiff<Self::Item>>> {
+        Pin::get_mut(self).as_mut().poll_set_change(cx)
+    }
+}
+
+
+// TODO Seal this
+pub trait SignalSetExt: SignalSet {
+    /// Creates a `Signal` which is `true` if `value` is in the set, and `false` otherwise.
+    ///
+    /// The `Signal` only changes when the membership of `value` changes: if several diffs
+    /// arrive at the same time and `value` ends up with the same membership, it won't
+    /// notify at all.
+    #[inline]
+    fn contains(self, value: Self::Item) -> Contains<Self> where Self::Item: PartialEq, Self: Sized {
+        Contains {
+            signal: Some(self),
+            value,
+            first: true,
+            contains: false,
+        }
+    }
+
+    /// A convenience for calling `SignalSet::poll_set_change` on `Unpin` types.
+    #[inline]
+    fn poll_set_change_unpin(&mut self, cx: &mut Context) -> Poll<Option<SetDiff<Self::Item>>> where Self: Unpin + Sized {
+        Pin::new(self).poll_set_change(cx)
+    }
+}
+
+// TODO why is this ?Sized
+impl<T: ?Sized> SignalSetExt for T where T: SignalSet {}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Contains<A> where A: SignalSet {
+    signal: Option<A>,
+    value: A::Item,
+    first: bool,
+    contains: bool,
+}
+
+impl<A> Unpin for Contains<A> where A: SignalSet + Unpin {}
+
+impl<A> Signal for Contains<A> where A: SignalSet, A::Item: PartialEq {
+    type Item = bool;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut value,
+            mut first,
+            mut contains,
+        });
+
+        // This is used to dedupe the changes, e.g. an Insert followed by a Remove doesn't change anything
+        let old_contains = *contains;
+
+        let done = loop {
+            break match signal.as_mut().as_pin_mut().map(|signal| signal.poll_set_change(cx)) {
+                None => {
+                    true
+                },
+                Some(Poll::Ready(None)) => {
+                    signal.set(None);
+                    true
+                },
+                Some(Poll::Ready(Some(change))) => {
+                    match change {
+                        SetDiff::Replace { values } => {
+                            *contains = values.contains(value);
+                        },
+
+                        SetDiff::Insert { value: inserted } => {
+                            if inserted == *value {
+                                *contains = true;
+                            }
+                        },
+
+                        SetDiff::Remove { value: removed } => {
+                            if removed == *value {
+                                *contains = false;
+                            }
+                        },
+
+                        SetDiff::Clear {} => {
+                            *contains = false;
+                        },
+                    }
+
+                    continue;
+                },
+                Some(Poll::Pending) => {
+                    false
+                },
+            };
+        };
+
+        if *contains != old_contains || *first {
+            *first = false;
+            Poll::Ready(Some(*contains))
+
+        } else if done {
+            Poll::Ready(None)
+
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+
+#[cfg(feature = "std")]
+#[derive(Debug)]
+#[must_use = "SignalSets do nothing unless polled"]
+pub struct MutableSignalSet<A> {
+    receiver: mpsc::UnboundedReceiver<SetDiff<A>>,
+}
+
+#[cfg(feature = "std")]
+impl<A> Unpin for MutableSignalSet<A> {}
+
+#[cfg(feature = "std")]
+impl<A> SignalSet for MutableSignalSet<A> {
+    type Item = A;
+
+    #[inline]
+    fn poll_set_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<SetDiff<Self::Item>>> {
+        self.receiver.poll_next_unpin(cx)
+    }
+}
+
+
+// TODO verify that this is correct
+#[cfg(feature = "std")]
+mod mutable_set {
+    use super::{SetDiff, MutableSignalSet};
+    use crate::signal_map::Senders;
+    use core::fmt;
+    use core::ops::Deref;
+    use core::cmp::{Ord, Ordering};
+    use core::hash::{Hash, Hasher};
+    use alloc::collections::BTreeSet;
+    use alloc::sync::Arc;
+    use crate::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+    use futures_channel::mpsc;
+    use serde::{Serialize, Deserialize, Serializer, Deserializer};
+    use alloc::vec::Vec;
+
+
+    #[derive(Debug)]
+    struct MutableSetState<A> {
+        values: BTreeSet<A>,
+        senders: Senders<SetDiff<A>>,
+    }
+
+    impl<A: Ord> MutableSetState<A> {
+        fn clear(&mut self) {
+            if !self.values.is_empty() {
+                self.values.clear();
+
+                self.senders.notify(|| SetDiff::Clear {});
+            }
+        }
+    }
+
+    impl<A: Ord + Clone> MutableSetState<A> {
+        fn remove(&mut self, value: &A) -> bool {
+            if self.values.remove(value) {
+                let value = self.senders.change(|| value.clone());
+                self.senders.notify_clone(value, |value| SetDiff::Remove { value });
+                true
+
+            } else {
+                false
+            }
+        }
+
+        fn values(values: &BTreeSet<A>) -> Vec<A> {
+            values.iter().cloned().collect()
+        }
+
+        fn replace_cloned(&mut self, values: BTreeSet<A>) {
+            let new_values = self.senders.change(|| Self::values(&values));
+
+            self.values = values;
+
+            self.senders.notify_clone(new_values, |values| SetDiff::Replace { values });
+        }
+
+        fn insert_cloned(&mut self, value: A) -> bool {
+            let new_value = self.senders.change(|| value.clone());
+
+            if self.values.insert(value) {
+                self.senders.notify_clone(new_value, |value| SetDiff::Insert { value });
+                true
+
+            } else {
+                false
+            }
+        }
+
+        fn signal_set_cloned(&mut self) -> MutableSignalSet<A> {
+            let (sender, receiver) = mpsc::unbounded();
+
+            if !self.values.is_empty() {
+                sender.unbounded_send(SetDiff::Replace {
+                    values: Self::values(&self.values),
+                }).unwrap();
+            }
+
+            self.senders.push(sender);
+
+            MutableSignalSet {
+                receiver
+            }
+        }
+    }
+
+
+    macro_rules! make_shared {
+        ($t:ty) => {
+            impl<'a, A> PartialEq<BTreeSet<A>> for $t where A: PartialEq<A> {
+                #[inline] fn eq(&self, other: &BTreeSet<A>) -> bool { **self == *other }
+            }
+
+            impl<'a, A> PartialEq<$t> for $t where A: PartialEq<A> {
+                #[inline] fn eq(&self, other: &$t) -> bool { *self == **other }
+            }
+
+            impl<'a, A> Eq for $t where A: Eq {}
+
+            impl<'a, A> PartialOrd<BTreeSet<A>> for $t where A: PartialOrd<A> {
+                #[inline]
+                fn partial_cmp(&self, other: &BTreeSet<A>) -> Option<Ordering> {
+                    PartialOrd::partial_cmp(&**self, &*other)
+                }
+            }
+
+            impl<'a, A> PartialOrd<$t> for $t where A: PartialOrd<A> {
+                #[inline]
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    PartialOrd::partial_cmp(&**self, &**other)
+                }
+            }
+
+            impl<'a, A> Ord for $t where A: Ord {
+                #[inline]
+                fn cmp(&self, other: &Self) -> Ordering {
+                    Ord::cmp(&**self, &**other)
+                }
+            }
+
+            impl<'a, A> Deref for $t {
+                type Target = BTreeSet<A>;
+
+                #[inline]
+                fn deref(&self) -> &Self::Target {
+                    &self.lock.values
+                }
+            }
+
+            impl<'a, A> Hash for $t where A: Hash {
+                #[inline]
+                fn hash<H>(&self, state: &mut H) where H: Hasher {
+                    Hash::hash(&**self, state)
+                }
+            }
+        };
+    }
+
+
+    #[derive(Debug)]
+    pub struct MutableSetLockRef<'a, A> where A: 'a {
+        lock: RwLockReadGuard<'a, MutableSetState<A>>,
+    }
+
+    make_shared!(MutableSetLockRef<'a, A>);
+
+
+    #[derive(Debug)]
+    pub struct MutableSetLockMut<'a, A> where A: 'a {
+        lock: RwLockWriteGuard<'a, MutableSetState<A>>,
+    }
+
+    make_shared!(MutableSetLockMut<'a, A>);
+
+    impl<'a, A> MutableSetLockMut<'a, A> where A: Ord {
+        #[inline]
+        pub fn clear(&mut self) {
+            self.lock.clear()
+        }
+    }
+
+    impl<'a, A> MutableSetLockMut<'a, A> where A: Ord + Clone {
+        /// Removes the value from the set, returning `true` if it existed.
+        #[inline]
+        pub fn remove(&mut self, value: &A) -> bool {
+            self.lock.remove(value)
+        }
+    }
+
+    impl<'a, A> MutableSetLockMut<'a, A> where A: Ord + Copy {
+        #[inline]
+        pub fn replace(&mut self, values: BTreeSet<A>) {
+            self.lock.replace_cloned(values)
+        }
+
+        /// Inserts the value into the set, returning `true` if it didn't already exist.
+        #[inline]
+        pub fn insert(&mut self, value: A) -> bool {
+            self.lock.insert_cloned(value)
+        }
+    }
+
+    impl<'a, A> MutableSetLockMut<'a, A> where A: Ord + Clone {
+        #[inline]
+        pub fn replace_cloned(&mut self, values: BTreeSet<A>) {
+            self.lock.replace_cloned(values)
+        }
+
+        /// Inserts the value into the set, returning `true` if it didn't already exist.
+        #[inline]
+        pub fn insert_cloned(&mut self, value: A) -> bool {
+            self.lock.insert_cloned(value)
+        }
+    }
+
+
+    /// A reactive set backed by a `BTreeSet`.
+    ///
+    /// Inserting a value which already exists (or removing a value which doesn't exist)
+    /// does not notify.
+    // TODO get rid of the Arc
+    // TODO impl some of the same traits as BTreeSet
+    pub struct MutableSet<A>(Arc<RwLock<MutableSetState<A>>>);
+
+    impl<A> MutableSet<A> {
+        // TODO deprecate this and replace with From ?
+        #[inline]
+        pub fn with_values(values: BTreeSet<A>) -> Self {
+            Self(Arc::new(RwLock::new(MutableSetState {
+                values,
+                senders: Senders::new(),
+            })))
+        }
+
+        // TODO return Result ?
+        #[inline]
+        pub fn lock_ref(&self) -> MutableSetLockRef<'_, A> {
+            MutableSetLockRef {
+                lock: self.0.read(),
+            }
+        }
+
+        // TODO return Result ?
+        #[inline]
+        pub fn lock_mut(&self) -> MutableSetLockMut<'_, A> {
+            MutableSetLockMut {
+                lock: self.0.write(),
+            }
+        }
+    }
+
+    impl<A> MutableSet<A> where A: Ord {
+        #[inline]
+        pub fn new() -> Self {
+            Self::with_values(BTreeSet::new())
+        }
+    }
+
+    impl<A> MutableSet<A> where A: Ord + Copy {
+        #[inline]
+        pub fn signal_set(&self) -> MutableSignalSet<A> {
+            self.0.write().signal_set_cloned()
+        }
+    }
+
+    impl<A> MutableSet<A> where A: Ord + Clone {
+        #[inline]
+        pub fn signal_set_cloned(&self) -> MutableSignalSet<A> {
+            self.0.write().signal_set_cloned()
+        }
+    }
+
+    impl<A> fmt::Debug for MutableSet<A> where A: fmt::Debug {
+        fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            let state = self.0.read();
+
+            fmt.debug_tuple("MutableSet")
+                .field(&state.values)
+                .finish()
+        }
+    }
+
+    impl<A> Serialize for MutableSet<A> where BTreeSet<A>: Serialize {
+        #[inline]
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+            self.0.read().values.serialize(serializer)
+        }
+    }
+
+    impl<'de, A> Deserialize<'de> for MutableSet<A> where BTreeSet<A>: Deserialize<'de> {
+        #[inline]
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+            <BTreeSet<A>>::deserialize(deserializer).map(MutableSet::with_values)
+        }
+    }
+
+    impl<A> Default for MutableSet<A> where A: Ord {
+        #[inline]
+        fn default() -> Self {
+            MutableSet::new()
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub use self::mutable_set::*;
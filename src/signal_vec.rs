@@ -1,19 +1,28 @@
-use std::iter::Sum;
-use std::collections::VecDeque;
-use std::pin::Pin;
-use std::marker::Unpin;
-use std::cmp::Ordering;
-use std::future::Future;
-use std::task::{Poll, Context};
+use core::iter::Sum;
+use core::ops::{AddAssign, SubAssign};
+use alloc::collections::VecDeque;
+use core::pin::Pin;
+use core::marker::Unpin;
+use core::cmp::Ordering;
+use core::future::Future;
+use core::task::{Poll, Context};
 use futures_core::Stream;
 use futures_util::stream;
 use futures_util::stream::StreamExt;
+use serde::{Serialize, Deserialize};
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
 
 use crate::signal::{Signal, Mutable, ReadOnlyMutable};
 
 
+/// A change to a [`SignalVec`](trait.SignalVec.html).
+///
+/// It implements `Serialize` and `Deserialize`, so the changes can be sent to another
+/// process and then applied with [`apply_to_vec`](#method.apply_to_vec).
 // TODO make this non-exhaustive
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum VecDiff<A> {
     Replace {
         values: Vec<A>,
@@ -74,6 +83,27 @@ impl<A> VecDiff<A> {
         }
     }
 
+    /// Applies the change to `vec`.
+    ///
+    /// This is useful for keeping a copy of a `SignalVec`: if every change is applied to the same
+    /// `Vec` (starting with an empty `Vec`), then the `Vec` will contain the same values as the `SignalVec`.
+    ///
+    /// ```rust
+    /// use futures_signals::signal_vec::VecDiff;
+    ///
+    /// let mut vec = vec![];
+    ///
+    /// VecDiff::Replace { values: vec![1, 2, 3] }.apply_to_vec(&mut vec);
+    /// VecDiff::Move { old_index: 0, new_index: 2 }.apply_to_vec(&mut vec);
+    /// VecDiff::Pop {}.apply_to_vec(&mut vec);
+    ///
+    /// assert_eq!(vec, vec![2, 3]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// It panics if an index is out of bounds, or if it pops an empty `Vec`. That can only
+    /// happen if `vec` doesn't contain the same values as the `SignalVec`.
     pub fn apply_to_vec(self, vec: &mut Vec<A>) {
         match self {
             VecDiff::Replace { values } => {
@@ -116,6 +146,7 @@ pub trait SignalVec {
 
 
 // Copied from Future in the Rust stdlib
+#[allow(clippy::needless_lifetimes)]
 impl<'a, A> SignalVec for &'a mut A where A: ?Sized + SignalVec + Unpin {
     type Item = A::Item;
 
@@ -137,9 +168,9 @@ impl<A> SignalVec for Box<A> where A: ?Sized + SignalVec + Unpin {
 
 // Copied from Future in the Rust stdlib
 impl<A> SignalVec for Pin<A>
-    where A: Unpin + ::std::ops::DerefMut,
+    where A: Unpin + ::core::ops::DerefMut,
           A::Target: SignalVec {
-    type Item = <<A as ::std::ops::Deref>::Target as SignalVec>::Item;
+    type Item = <<A as ::core::ops::Deref>::Target as SignalVec>::Item;
 
     #[inline]
     fn poll_vec_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<VecDiff<Self::Item>>> {
@@ -204,6 +235,29 @@ pub trait SignalVecExt: SignalVec {
         }
     }
 
+    /// Creates a `SignalVec` which calls the closure for each value in `self`, and then uses the
+    /// `Signal` returned by the closure as the value.
+    ///
+    /// Whenever one of the `Signal`s changes, the output `SignalVec` notifies with
+    /// `VecDiff::UpdateAt` for that index. This makes it possible to react to changes *inside* of the
+    /// values, e.g. when the values contain a `Mutable`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use futures_signals::signal::Mutable;
+    /// # use futures_signals::signal_vec::{always, SignalVecExt};
+    /// # let input = always(vec![Mutable::new(1), Mutable::new(2)]);
+    /// let mapped = input.map_signal(|value| value.signal());
+    /// ```
+    ///
+    /// If `input` has the values `[Mutable::new(1), Mutable::new(2)]` then `mapped` has the values `[1, 2]`,
+    /// and it will update whenever one of the `Mutable`s is changed.
+    ///
+    /// # Performance
+    ///
+    /// Whenever `self` or any of the `Signal`s change, it polls every `Signal`, so it's linear with
+    /// the number of values in `self`.
     #[inline]
     fn map_signal<A, F>(self, callback: F) -> MapSignal<Self, A, F>
         where A: Signal,
@@ -217,6 +271,14 @@ pub trait SignalVecExt: SignalVec {
         }
     }
 
+    /// Creates a `Signal` by calling the closure with all of the values in `self`.
+    ///
+    /// It applies the `VecDiff`s to an internal `Vec`, and then calls the closure with a
+    /// slice of the current values. The closure is called at most once per change, even if
+    /// multiple `VecDiff`s arrived at the same time.
+    ///
+    /// Because the closure receives the entire `Vec`, this is less efficient than the other
+    /// `SignalVec` methods, so it should only be used when you really need all of the values.
     #[inline]
     fn to_signal_map<A, F>(self, callback: F) -> ToSignalMap<Self, F>
         where F: FnMut(&[Self::Item]) -> A,
@@ -229,6 +291,20 @@ pub trait SignalVecExt: SignalVec {
         }
     }
 
+    /// Creates a `Signal` which contains a clone of all of the values in `self`.
+    ///
+    /// This is the same as [`to_signal_map`](#method.to_signal_map) with a closure which
+    /// clones the values into a new `Vec`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use futures_signals::signal_vec::{always, SignalVecExt};
+    /// # let input = always(vec![1, 2, 3]);
+    /// let values = input.to_signal_cloned();
+    /// ```
+    ///
+    /// If `input` has the values `[1, 2, 3]` then `values` is `vec![1, 2, 3]`
     #[inline]
     fn to_signal_cloned(self) -> ToSignalCloned<Self>
         where Self::Item: Clone,
@@ -284,6 +360,69 @@ pub trait SignalVecExt: SignalVec {
         }
     }
 
+    /// Creates a `SignalVec` which both filters and maps the values of `self`.
+    ///
+    /// It calls the closure once for each value in `self`. The output `SignalVec` contains the
+    /// `Some` values returned by the closure, in the same order as `self`. Values where the
+    /// closure returned `None` are skipped.
+    ///
+    /// This is more efficient than using [`filter`](#method.filter) followed by [`map`](#method.map),
+    /// because it only needs to translate the indexes once.
+    ///
+    /// It is guaranteed that the closure will be called *exactly* once for each value in `self`.
+    ///
+    /// # Examples
+    ///
+    /// Only include values less than `5`, and multiply them by `10`:
+    ///
+    /// ```rust
+    /// # use futures_signals::signal_vec::{always, SignalVecExt};
+    /// # let input = always(vec![3, 1, 6, 2, 0, 4, 5, 8, 9, 7]);
+    /// let filtered = input.filter_map(|value| {
+    ///     if value < 5 {
+    ///         Some(value * 10)
+    ///
+    ///     } else {
+    ///         None
+    ///     }
+    /// });
+    /// ```
+    ///
+    /// If `input` has the values `[3, 1, 6, 2, 0, 4, 5, 8, 9, 7]` then `filtered` has the values `[30, 10, 20, 0, 40]`
+    ///
+    /// # Performance
+    ///
+    /// This has the same performance as [`filter`](#method.filter).
+    #[inline]
+    fn filter_map<A, F>(self, callback: F) -> FilterMap<Self, F>
+        where F: FnMut(Self::Item) -> Option<A>,
+              Self: Sized {
+        FilterMap {
+            indexes: vec![],
+            signal: self,
+            callback,
+        }
+    }
+
+    /// Creates a `SignalVec` which ignores `VecDiff::UpdateAt` and `VecDiff::Replace` if the new
+    /// values are the same as the old values.
+    ///
+    /// This is useful when `self` notifies with the same values multiple times (e.g. because it's
+    /// receiving data from a server), since it avoids doing unnecessary work in the consumer.
+    ///
+    /// # Performance
+    ///
+    /// It clones each value in `self`, so that it can compare the new values with the old values.
+    #[inline]
+    fn dedupe_cloned(self) -> DedupeCloned<Self>
+        where Self::Item: Clone + PartialEq,
+              Self: Sized {
+        DedupeCloned {
+            values: vec![],
+            signal: self,
+        }
+    }
+
     #[inline]
     fn filter_signal_cloned<A, F>(self, callback: F) -> FilterSignalCloned<Self, A, F>
         where A: Signal<Item = bool>,
@@ -309,6 +448,26 @@ pub trait SignalVecExt: SignalVec {
         }
     }
 
+    /// Creates a `Signal` which contains the sum of all the values in `self`.
+    ///
+    /// This is the same as [`sum`](#method.sum), except the sum is updated incrementally:
+    /// inserting a value adds it to the sum, removing a value subtracts it, and updating a value
+    /// subtracts the old value and adds the new value. So each change is constant time, rather
+    /// than recalculating the sum of the entire `SignalVec`.
+    ///
+    /// For floating point numbers this means that rounding errors can accumulate over many changes.
+    #[inline]
+    fn incremental_sum(self) -> IncrementalSumSignal<Self>
+        where Self::Item: Default + Clone + AddAssign + SubAssign,
+              Self: Sized {
+        IncrementalSumSignal {
+            signal: Some(self),
+            first: true,
+            values: vec![],
+            sum: Default::default(),
+        }
+    }
+
     /// Creates a `SignalVec` which uses a closure to sort the values.
     ///
     /// When the output `SignalVec` is spawned:
@@ -415,6 +574,12 @@ pub trait SignalVecExt: SignalVec {
         }
     }
 
+    /// Converts `self` into a `Stream` of `VecDiff`s.
+    ///
+    /// This is useful for piping the changes into other code which works with `Stream`s,
+    /// e.g. sending them through a channel or over the network.
+    ///
+    /// The `Stream` ends when `self` ends.
     #[inline]
     fn to_stream(self) -> SignalVecStream<Self> where Self: Sized {
         SignalVecStream {
@@ -422,6 +587,23 @@ pub trait SignalVecExt: SignalVec {
         }
     }
 
+    /// Returns a `Future` which calls the closure for each `VecDiff` in `self`.
+    ///
+    /// The closure returns a `Future`, which is awaited before the next `VecDiff` is processed.
+    ///
+    /// The `Future` completes when `self` ends. This is the usual way of consuming a `SignalVec`,
+    /// e.g. to render a list or to log the changes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use futures_signals::signal_vec::{always, SignalVecExt};
+    /// # let input = always(vec![1, 2, 3]);
+    /// let future = input.for_each(|change| {
+    ///     println!("{:?}", change);
+    ///     async {}
+    /// });
+    /// ```
     #[inline]
     // TODO file Rust bug about bad error message when `callback` isn't marked as `mut`
     fn for_each<U, F>(self, callback: F) -> ForEach<Self, U, F>
@@ -436,6 +618,13 @@ pub trait SignalVecExt: SignalVec {
         }
     }
 
+    /// Creates a `Signal` which contains the number of values in `self`.
+    ///
+    /// The length is updated incrementally from the `VecDiff`s, so it does not need to store the values.
+    ///
+    /// The `Signal` only changes when the length changes: if several diffs arrive at the same
+    /// time (e.g. a `Push` followed by a `Pop`) and the length ends up the same, it won't
+    /// notify at all.
     // TODO replace with to_signal_map ?
     #[inline]
     fn len(self) -> Len<Self> where Self: Sized {
@@ -446,6 +635,19 @@ pub trait SignalVecExt: SignalVec {
         }
     }
 
+    /// Creates a `Signal` which is `true` if `self` is empty, and `false` otherwise.
+    ///
+    /// It only changes when `self` goes from empty to non-empty (or vice versa),
+    /// so it doesn't notify when values are added to a non-empty `SignalVec`.
+    #[inline]
+    #[allow(clippy::wrong_self_convention)]
+    fn is_empty(self) -> IsEmpty<Self> where Self: Sized {
+        IsEmpty {
+            len: self.len(),
+            old: None,
+        }
+    }
+
     #[inline]
     fn enumerate(self) -> Enumerate<Self> where Self: Sized {
         Enumerate {
@@ -454,6 +656,17 @@ pub trait SignalVecExt: SignalVec {
         }
     }
 
+    /// Creates a `SignalVec` which delays removing values until a `Future` completes.
+    ///
+    /// The closure is called for each value in `self`, and it returns a `Future`. When the value is
+    /// removed from `self`, the output `SignalVec` waits for that `Future` to complete before it
+    /// removes the value. Values which are inserted or updated are not delayed.
+    ///
+    /// While the removal is delayed the value stays in the output `SignalVec`, and the indexes of
+    /// any new changes are adjusted so that they stay correct.
+    ///
+    /// This is useful for animations, e.g. playing an animation when a value is removed, and then
+    /// removing it after the animation is finished.
     #[inline]
     fn delay_remove<A, F>(self, f: F) -> DelayRemove<Self, A, F>
         where A: Future<Output = ()>,
@@ -543,6 +756,7 @@ impl<A, B, F> SignalVec for Map<A, F>
 
     // TODO should this inline ?
     #[inline]
+    #[allow(clippy::redundant_closure)]
     fn poll_vec_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<VecDiff<Self::Item>>> {
         unsafe_project!(self => {
             pin signal,
@@ -555,12 +769,13 @@ impl<A, B, F> SignalVec for Map<A, F>
 
 
 #[must_use = "Signals do nothing unless polled"]
+#[allow(clippy::type_complexity)]
 pub struct ToSignalCloned<A> where A: SignalVec {
     signal: ToSignalMap<A, fn(&[A::Item]) -> Vec<A::Item>>,
 }
 
-impl<A> std::fmt::Debug for ToSignalCloned<A> where A: SignalVec {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<A> core::fmt::Debug for ToSignalCloned<A> where A: SignalVec {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_str("ToSignalCloned { ... }")
     }
 }
@@ -598,6 +813,7 @@ impl<A, B, F> Signal for ToSignalMap<A, F>
           F: FnMut(&[A::Item]) -> B {
     type Item = B;
 
+    #[allow(clippy::needless_borrow)]
     fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
         unsafe_project!(self => {
             pin signal,
@@ -827,6 +1043,7 @@ impl<A> PendingBuilder<A> {
         }
     }
 
+    #[allow(clippy::redundant_pattern_matching)]
     fn push(&mut self, value: A) {
         if let None = self.first {
             self.first = Some(value);
@@ -863,6 +1080,7 @@ impl<A, B, F> SignalVec for MapSignal<A, B, F>
           F: FnMut(A::Item) -> B {
     type Item = B::Item;
 
+    #[allow(clippy::into_iter_on_ref)]
     fn poll_vec_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<VecDiff<Self::Item>>> {
         unsafe_project!(self => {
             pin signal,
@@ -1002,6 +1220,7 @@ pub struct FilterSignalCloned<A, B, F> where A: SignalVec {
 }
 
 impl<A, B, F> FilterSignalCloned<A, B, F> where A: SignalVec {
+    #[allow(clippy::into_iter_on_ref)]
     fn find_index(signals: &[FilterSignalClonedState<A::Item, B>], index: usize) -> usize {
         signals[0..index].into_iter().filter(|x| x.exists).count()
     }
@@ -1016,6 +1235,7 @@ impl<A, B, F> SignalVec for FilterSignalCloned<A, B, F>
           F: FnMut(&A::Item) -> B {
     type Item = A::Item;
 
+    #[allow(clippy::into_iter_on_ref)]
     fn poll_vec_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<VecDiff<Self::Item>>> {
         unsafe_project!(self => {
             pin signal,
@@ -1129,8 +1349,10 @@ impl<A, B, F> SignalVec for FilterSignalCloned<A, B, F>
                             }
                         },
 
-                        // TODO unit tests for this
                         VecDiff::Move { old_index, new_index } => {
+                            // This must be calculated before the index is moved
+                            let old_filtered_index = Self::find_index(signals, old_index);
+
                             let state = signals.remove(old_index);
                             let exists = state.exists;
 
@@ -1138,7 +1360,7 @@ impl<A, B, F> SignalVec for FilterSignalCloned<A, B, F>
 
                             if exists {
                                 VecDiff::Move {
-                                    old_index: Self::find_index(signals, old_index),
+                                    old_index: old_filtered_index,
                                     new_index: Self::find_index(signals, new_index),
                                 }
 
@@ -1211,7 +1433,6 @@ impl<A, B, F> SignalVec for FilterSignalCloned<A, B, F>
             }
 
             if state.exists != old {
-                // TODO test these
                 // TODO use Push and Pop when the index is at the end
                 if state.exists {
                     new_pending.push(VecDiff::InsertAt { index: real_index, value: state.value.clone() });
@@ -1240,6 +1461,45 @@ impl<A, B, F> SignalVec for FilterSignalCloned<A, B, F>
 }
 
 
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct IsEmpty<A> {
+    len: Len<A>,
+    old: Option<bool>,
+}
+
+impl<A> Unpin for IsEmpty<A> where A: Unpin {}
+
+impl<A> Signal for IsEmpty<A> where A: SignalVec {
+    type Item = bool;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin len,
+            mut old,
+        });
+
+        loop {
+            return match len.as_mut().poll_change(cx) {
+                Poll::Ready(Some(len)) => {
+                    let new = Some(len == 0);
+
+                    if *old != new {
+                        *old = new;
+                        Poll::Ready(new)
+
+                    } else {
+                        continue;
+                    }
+                },
+                Poll::Ready(None) => Poll::Ready(None),
+                Poll::Pending => Poll::Pending,
+            };
+        }
+    }
+}
+
+
 #[derive(Debug)]
 #[must_use = "Signals do nothing unless polled"]
 pub struct Len<A> {
@@ -1260,7 +1520,8 @@ impl<A> Signal for Len<A> where A: SignalVec {
             mut len,
         });
 
-        let mut changed = false;
+        // This is used to dedupe the changes, e.g. a Push followed by a Pop doesn't change the len
+        let old_len = *len;
 
         let done = loop {
             break match signal.as_mut().as_pin_mut().map(|signal| signal.poll_vec_change(cx)) {
@@ -1274,31 +1535,21 @@ impl<A> Signal for Len<A> where A: SignalVec {
                 Some(Poll::Ready(Some(change))) => {
                     match change {
                         VecDiff::Replace { values } => {
-                            let new_len = values.len();
-
-                            if *len != new_len {
-                                *len = new_len;
-                                changed = true;
-                            }
+                            *len = values.len();
                         },
 
                         VecDiff::InsertAt { .. } | VecDiff::Push { .. } => {
                             *len += 1;
-                            changed = true;
                         },
 
                         VecDiff::UpdateAt { .. } | VecDiff::Move { .. } => {},
 
                         VecDiff::RemoveAt { .. } | VecDiff::Pop {} => {
                             *len -= 1;
-                            changed = true;
                         },
 
                         VecDiff::Clear {} => {
-                            if *len != 0 {
-                                *len = 0;
-                                changed = true;
-                            }
+                            *len = 0;
                         },
                     }
 
@@ -1310,7 +1561,7 @@ impl<A> Signal for Len<A> where A: SignalVec {
             };
         };
 
-        if changed || *first {
+        if *len != old_len || *first {
             *first = false;
             // TODO is this correct ?
             Poll::Ready(Some(*len))
@@ -1347,6 +1598,67 @@ impl<A: SignalVec> Stream for SignalVecStream<A> {
 }
 
 
+#[derive(Debug)]
+#[must_use = "SignalVecs do nothing unless polled"]
+pub struct DedupeCloned<A> where A: SignalVec {
+    values: Vec<A::Item>,
+    signal: A,
+}
+
+impl<A> Unpin for DedupeCloned<A> where A: Unpin + SignalVec {}
+
+impl<A> SignalVec for DedupeCloned<A>
+    where A: SignalVec,
+          A::Item: Clone + PartialEq {
+    type Item = A::Item;
+
+    fn poll_vec_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<VecDiff<Self::Item>>> {
+        unsafe_project!(self => {
+            mut values,
+            pin signal,
+        });
+
+        loop {
+            return match signal.as_mut().poll_vec_change(cx) {
+                Poll::Ready(Some(change)) => match change {
+                    VecDiff::Replace { values: new_values } => {
+                        if *values == new_values {
+                            continue;
+
+                        } else {
+                            *values = new_values.clone();
+                            Poll::Ready(Some(VecDiff::Replace { values: new_values }))
+                        }
+                    },
+
+                    VecDiff::UpdateAt { index, value } => {
+                        if values[index] == value {
+                            continue;
+
+                        } else {
+                            values[index] = value.clone();
+                            Poll::Ready(Some(VecDiff::UpdateAt { index, value }))
+                        }
+                    },
+
+                    change => {
+                        change.clone().apply_to_vec(values);
+                        Poll::Ready(Some(change))
+                    },
+                },
+                Poll::Ready(None) => Poll::Ready(None),
+                Poll::Pending => Poll::Pending,
+            };
+        }
+    }
+}
+
+
+// Translates an index in the input into an index in the output
+fn find_filter_index(indexes: &[bool], index: usize) -> usize {
+    indexes[0..index].iter().filter(|x| **x).count()
+}
+
 #[derive(Debug)]
 #[must_use = "SignalVecs do nothing unless polled"]
 pub struct Filter<A, B> {
@@ -1356,12 +1668,6 @@ pub struct Filter<A, B> {
     callback: B,
 }
 
-impl<A, B> Filter<A, B> {
-    fn find_index(indexes: &[bool], index: usize) -> usize {
-        indexes[0..index].into_iter().filter(|x| **x).count()
-    }
-}
-
 impl<A, B> Unpin for Filter<A, B> where A: Unpin {}
 
 impl<A, F> SignalVec for Filter<A, F>
@@ -1396,7 +1702,7 @@ impl<A, F> SignalVec for Filter<A, F>
                     VecDiff::InsertAt { index, value } => {
                         if callback(&value) {
                             indexes.insert(index, true);
-                            Poll::Ready(Some(VecDiff::InsertAt { index: Self::find_index(indexes, index), value }))
+                            Poll::Ready(Some(VecDiff::InsertAt { index: find_filter_index(indexes, index), value }))
 
                         } else {
                             indexes.insert(index, false);
@@ -1407,17 +1713,17 @@ impl<A, F> SignalVec for Filter<A, F>
                     VecDiff::UpdateAt { index, value } => {
                         if callback(&value) {
                             if indexes[index] {
-                                Poll::Ready(Some(VecDiff::UpdateAt { index: Self::find_index(indexes, index), value }))
+                                Poll::Ready(Some(VecDiff::UpdateAt { index: find_filter_index(indexes, index), value }))
 
                             } else {
                                 indexes[index] = true;
-                                Poll::Ready(Some(VecDiff::InsertAt { index: Self::find_index(indexes, index), value }))
+                                Poll::Ready(Some(VecDiff::InsertAt { index: find_filter_index(indexes, index), value }))
                             }
 
                         } else {
                             if indexes[index] {
                                 indexes[index] = false;
-                                Poll::Ready(Some(VecDiff::RemoveAt { index: Self::find_index(indexes, index) }))
+                                Poll::Ready(Some(VecDiff::RemoveAt { index: find_filter_index(indexes, index) }))
 
                             } else {
                                 continue;
@@ -1425,14 +1731,16 @@ impl<A, F> SignalVec for Filter<A, F>
                         }
                     },
 
-                    // TODO unit tests for this
                     VecDiff::Move { old_index, new_index } => {
+                        // This must be calculated before the index is moved
+                        let old_filtered_index = find_filter_index(indexes, old_index);
+
                         if indexes.remove(old_index) {
                             indexes.insert(new_index, true);
 
                             Poll::Ready(Some(VecDiff::Move {
-                                old_index: Self::find_index(indexes, old_index),
-                                new_index: Self::find_index(indexes, new_index),
+                                old_index: old_filtered_index,
+                                new_index: find_filter_index(indexes, new_index),
                             }))
 
                         } else {
@@ -1443,7 +1751,7 @@ impl<A, F> SignalVec for Filter<A, F>
 
                     VecDiff::RemoveAt { index } => {
                         if indexes.remove(index) {
-                            Poll::Ready(Some(VecDiff::RemoveAt { index: Self::find_index(indexes, index) }))
+                            Poll::Ready(Some(VecDiff::RemoveAt { index: find_filter_index(indexes, index) }))
 
                         } else {
                             continue;
@@ -1482,74 +1790,204 @@ impl<A, F> SignalVec for Filter<A, F>
 
 
 #[derive(Debug)]
-#[must_use = "Signals do nothing unless polled"]
-pub struct SumSignal<A> where A: SignalVec {
-    signal: Option<A>,
-    first: bool,
-    values: Vec<A::Item>,
+#[must_use = "SignalVecs do nothing unless polled"]
+pub struct FilterMap<A, B> {
+    // TODO use a bit vec for smaller size
+    indexes: Vec<bool>,
+    signal: A,
+    callback: B,
 }
 
-impl<A> Unpin for SumSignal<A> where A: Unpin + SignalVec {}
+impl<A, B> Unpin for FilterMap<A, B> where A: Unpin {}
 
-impl<A> Signal for SumSignal<A>
+impl<A, B, F> SignalVec for FilterMap<A, F>
     where A: SignalVec,
-          A::Item: for<'a> Sum<&'a A::Item> {
-    type Item = A::Item;
+          F: FnMut(A::Item) -> Option<B> {
+    type Item = B;
 
-    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+    fn poll_vec_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<VecDiff<Self::Item>>> {
         unsafe_project!(self => {
+            mut indexes,
             pin signal,
-            mut first,
-            mut values,
+            mut callback,
         });
 
-        let mut changed = false;
-
-        let done = loop {
-            break match signal.as_mut().as_pin_mut().map(|signal| signal.poll_vec_change(cx)) {
-                None => {
-                    true
-                },
-                Some(Poll::Ready(None)) => {
-                    signal.set(None);
-                    true
-                },
-                Some(Poll::Ready(Some(change))) => {
-                    match change {
-                        VecDiff::Replace { values: new_values } => {
-                            // TODO only mark changed if the values are different
-                            *values = new_values;
-                        },
-
-                        VecDiff::InsertAt { index, value } => {
-                            // TODO only mark changed if the value isn't 0
-                            values.insert(index, value);
-                        },
+        loop {
+            return match signal.as_mut().poll_vec_change(cx) {
+                Poll::Pending => Poll::Pending,
+                Poll::Ready(None) => Poll::Ready(None),
+                Poll::Ready(Some(change)) => match change {
+                    VecDiff::Replace { values } => {
+                        *indexes = Vec::with_capacity(values.len());
 
-                        VecDiff::Push { value } => {
-                            // TODO only mark changed if the value isn't 0
-                            values.push(value);
-                        },
+                        Poll::Ready(Some(VecDiff::Replace {
+                            values: values.into_iter().filter_map(|value| {
+                                let value = callback(value);
+                                indexes.push(value.is_some());
+                                value
+                            }).collect()
+                        }))
+                    },
 
-                        VecDiff::UpdateAt { index, value } => {
-                            // TODO only mark changed if the value is different
-                            values[index] = value;
-                        },
+                    VecDiff::InsertAt { index, value } => {
+                        if let Some(value) = callback(value) {
+                            indexes.insert(index, true);
+                            Poll::Ready(Some(VecDiff::InsertAt { index: find_filter_index(indexes, index), value }))
 
-                        VecDiff::Move { old_index, new_index } => {
-                            let value = values.remove(old_index);
-                            values.insert(new_index, value);
-                            // Moving shouldn't recalculate the sum
+                        } else {
+                            indexes.insert(index, false);
                             continue;
-                        },
+                        }
+                    },
 
-                        VecDiff::RemoveAt { index } => {
-                            // TODO only mark changed if the value isn't 0
-                            values.remove(index);
-                        },
+                    VecDiff::UpdateAt { index, value } => {
+                        if let Some(value) = callback(value) {
+                            if indexes[index] {
+                                Poll::Ready(Some(VecDiff::UpdateAt { index: find_filter_index(indexes, index), value }))
 
-                        VecDiff::Pop {} => {
-                            // TODO only mark changed if the value isn't 0
+                            } else {
+                                indexes[index] = true;
+                                Poll::Ready(Some(VecDiff::InsertAt { index: find_filter_index(indexes, index), value }))
+                            }
+
+                        } else {
+                            if indexes[index] {
+                                indexes[index] = false;
+                                Poll::Ready(Some(VecDiff::RemoveAt { index: find_filter_index(indexes, index) }))
+
+                            } else {
+                                continue;
+                            }
+                        }
+                    },
+
+                    VecDiff::Move { old_index, new_index } => {
+                        // This must be calculated before the index is moved
+                        let old_filtered_index = find_filter_index(indexes, old_index);
+
+                        if indexes.remove(old_index) {
+                            indexes.insert(new_index, true);
+
+                            Poll::Ready(Some(VecDiff::Move {
+                                old_index: old_filtered_index,
+                                new_index: find_filter_index(indexes, new_index),
+                            }))
+
+                        } else {
+                            indexes.insert(new_index, false);
+                            continue;
+                        }
+                    },
+
+                    VecDiff::RemoveAt { index } => {
+                        if indexes.remove(index) {
+                            Poll::Ready(Some(VecDiff::RemoveAt { index: find_filter_index(indexes, index) }))
+
+                        } else {
+                            continue;
+                        }
+                    },
+
+                    VecDiff::Push { value } => {
+                        if let Some(value) = callback(value) {
+                            indexes.push(true);
+                            Poll::Ready(Some(VecDiff::Push { value }))
+
+                        } else {
+                            indexes.push(false);
+                            continue;
+                        }
+                    },
+
+                    VecDiff::Pop {} => {
+                        if indexes.pop().expect("Cannot pop from empty vec") {
+                            Poll::Ready(Some(VecDiff::Pop {}))
+
+                        } else {
+                            continue;
+                        }
+                    },
+
+                    VecDiff::Clear {} => {
+                        indexes.clear();
+                        Poll::Ready(Some(VecDiff::Clear {}))
+                    },
+                },
+            }
+        }
+    }
+}
+
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct SumSignal<A> where A: SignalVec {
+    signal: Option<A>,
+    first: bool,
+    values: Vec<A::Item>,
+}
+
+impl<A> Unpin for SumSignal<A> where A: Unpin + SignalVec {}
+
+impl<A> Signal for SumSignal<A>
+    where A: SignalVec,
+          A::Item: for<'a> Sum<&'a A::Item> {
+    type Item = A::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut first,
+            mut values,
+        });
+
+        let mut changed = false;
+
+        let done = loop {
+            break match signal.as_mut().as_pin_mut().map(|signal| signal.poll_vec_change(cx)) {
+                None => {
+                    true
+                },
+                Some(Poll::Ready(None)) => {
+                    signal.set(None);
+                    true
+                },
+                Some(Poll::Ready(Some(change))) => {
+                    match change {
+                        VecDiff::Replace { values: new_values } => {
+                            // TODO only mark changed if the values are different
+                            *values = new_values;
+                        },
+
+                        VecDiff::InsertAt { index, value } => {
+                            // TODO only mark changed if the value isn't 0
+                            values.insert(index, value);
+                        },
+
+                        VecDiff::Push { value } => {
+                            // TODO only mark changed if the value isn't 0
+                            values.push(value);
+                        },
+
+                        VecDiff::UpdateAt { index, value } => {
+                            // TODO only mark changed if the value is different
+                            values[index] = value;
+                        },
+
+                        VecDiff::Move { old_index, new_index } => {
+                            let value = values.remove(old_index);
+                            values.insert(new_index, value);
+                            // Moving shouldn't recalculate the sum
+                            continue;
+                        },
+
+                        VecDiff::RemoveAt { index } => {
+                            // TODO only mark changed if the value isn't 0
+                            values.remove(index);
+                        },
+
+                        VecDiff::Pop {} => {
+                            // TODO only mark changed if the value isn't 0
                             values.pop().unwrap();
                         },
 
@@ -1583,6 +2021,120 @@ impl<A> Signal for SumSignal<A>
 }
 
 
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct IncrementalSumSignal<A> where A: SignalVec {
+    signal: Option<A>,
+    first: bool,
+    values: Vec<A::Item>,
+    sum: A::Item,
+}
+
+impl<A> Unpin for IncrementalSumSignal<A> where A: Unpin + SignalVec {}
+
+impl<A> Signal for IncrementalSumSignal<A>
+    where A: SignalVec,
+          A::Item: Default + Clone + AddAssign + SubAssign {
+    type Item = A::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut first,
+            mut values,
+            mut sum,
+        });
+
+        let mut changed = false;
+
+        let done = loop {
+            break match signal.as_mut().as_pin_mut().map(|signal| signal.poll_vec_change(cx)) {
+                None => {
+                    true
+                },
+                Some(Poll::Ready(None)) => {
+                    signal.set(None);
+                    true
+                },
+                Some(Poll::Ready(Some(change))) => {
+                    match change {
+                        VecDiff::Replace { values: new_values } => {
+                            // TODO only mark changed if the values are different
+                            *sum = Default::default();
+
+                            for value in new_values.iter() {
+                                *sum += value.clone();
+                            }
+
+                            *values = new_values;
+                        },
+
+                        VecDiff::InsertAt { index, value } => {
+                            // TODO only mark changed if the value isn't 0
+                            *sum += value.clone();
+                            values.insert(index, value);
+                        },
+
+                        VecDiff::Push { value } => {
+                            // TODO only mark changed if the value isn't 0
+                            *sum += value.clone();
+                            values.push(value);
+                        },
+
+                        VecDiff::UpdateAt { index, value } => {
+                            // TODO only mark changed if the value is different
+                            // The old value is subtracted first, so that it doesn't overflow
+                            *sum -= core::mem::replace(&mut values[index], value.clone());
+                            *sum += value;
+                        },
+
+                        VecDiff::Move { old_index, new_index } => {
+                            let value = values.remove(old_index);
+                            values.insert(new_index, value);
+                            // Moving shouldn't change the sum
+                            continue;
+                        },
+
+                        VecDiff::RemoveAt { index } => {
+                            // TODO only mark changed if the value isn't 0
+                            *sum -= values.remove(index);
+                        },
+
+                        VecDiff::Pop {} => {
+                            // TODO only mark changed if the value isn't 0
+                            *sum -= values.pop().unwrap();
+                        },
+
+                        VecDiff::Clear {} => {
+                            // TODO only mark changed if the len is different
+                            values.clear();
+                            *sum = Default::default();
+                        },
+                    }
+
+                    changed = true;
+                    continue;
+                },
+                Some(Poll::Pending) => {
+                    false
+                },
+            };
+        };
+
+        if changed || *first {
+            *first = false;
+            Poll::Ready(Some(sum.clone()))
+
+        } else if done {
+            Poll::Ready(None)
+
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+
 #[derive(Debug)]
 #[must_use = "SignalVecs do nothing unless polled"]
 pub struct SortByCloned<A, B> where A: SignalVec {
@@ -1881,6 +2433,7 @@ impl<S, A, F> DelayRemove<S, A, F>
         }
     }
 
+    #[allow(clippy::into_iter_on_ref)]
     fn find_index(futures: &[DelayRemoveState<A>], parent_index: usize) -> Option<usize> {
         let mut seen = 0;
 
@@ -1899,10 +2452,12 @@ impl<S, A, F> DelayRemove<S, A, F>
         })
     }
 
+    #[allow(clippy::into_iter_on_ref)]
     fn find_last_index(futures: &[DelayRemoveState<A>]) -> Option<usize> {
         futures.into_iter().rposition(|state| !state.is_removing)
     }
 
+    #[allow(clippy::collapsible_if)]
     fn remove_existing_futures(futures: &mut Vec<DelayRemoveState<A>>, pending: &mut PendingBuilder<VecDiff<S::Item>>, cx: &mut Context) {
         let mut indexes = vec![];
 
@@ -1930,6 +2485,7 @@ impl<S, A, F> SignalVec for DelayRemove<S, A, F>
     type Item = S::Item;
 
     // TODO this can probably be implemented more efficiently
+    #[allow(clippy::len_zero, clippy::unnecessary_lazy_evaluations)]
     fn poll_vec_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<VecDiff<Self::Item>>> {
         unsafe_project!(self => {
             pin signal,
@@ -1994,7 +2550,6 @@ impl<S, A, F> SignalVec for DelayRemove<S, A, F>
                             new_pending.push(VecDiff::UpdateAt { index, value });
                         },
 
-                        // TODO test this
                         // TODO should this be treated as a removal + insertion ?
                         VecDiff::Move { old_index, new_index } => {
                             let old_index = Self::find_index(futures, old_index).expect("Could not find value");
@@ -2072,22 +2627,126 @@ impl<S, A, F> SignalVec for DelayRemove<S, A, F>
 }
 
 
+/// A `Signal` which contains a snapshot of a `SignalVec`, as an [`im::Vector`](https://docs.rs/im/15/im/struct.Vector.html).
+///
+/// The changes are applied to the `im::Vector`, so each change only takes `O(log n)` time, and
+/// cloning the snapshot is `O(1)`. This makes it cheap to view the whole collection at once.
+///
+/// This is only available with the `im` feature.
+#[cfg(feature = "im")]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct ImVectorSignal<A> where A: SignalVec, A::Item: Clone {
+    signal: Option<A>,
+    values: im::Vector<A::Item>,
+    first: bool,
+}
+
+#[cfg(feature = "im")]
+impl<A> ImVectorSignal<A> where A: SignalVec, A::Item: Clone {
+    #[inline]
+    pub fn new(signal: A) -> Self {
+        Self {
+            signal: Some(signal),
+            values: im::Vector::new(),
+            first: true,
+        }
+    }
+}
+
+#[cfg(feature = "im")]
+impl<A> Unpin for ImVectorSignal<A> where A: SignalVec + Unpin, A::Item: Clone {}
+
+#[cfg(feature = "im")]
+impl<A> Signal for ImVectorSignal<A> where A: SignalVec, A::Item: Clone {
+    type Item = im::Vector<A::Item>;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            pin signal,
+            mut values,
+            mut first,
+        });
+
+        let mut changed = false;
+
+        loop {
+            match signal.as_mut().as_pin_mut().map(|signal| signal.poll_vec_change(cx)) {
+                None => {},
+                Some(Poll::Ready(None)) => {
+                    signal.set(None);
+                },
+                Some(Poll::Ready(Some(change))) => {
+                    match change {
+                        VecDiff::Replace { values: new_values } => {
+                            *values = new_values.into_iter().collect();
+                        },
+                        VecDiff::InsertAt { index, value } => {
+                            values.insert(index, value);
+                        },
+                        VecDiff::UpdateAt { index, value } => {
+                            values.set(index, value);
+                        },
+                        VecDiff::RemoveAt { index } => {
+                            values.remove(index);
+                        },
+                        VecDiff::Move { old_index, new_index } => {
+                            let value = values.remove(old_index);
+                            values.insert(new_index, value);
+                        },
+                        VecDiff::Push { value } => {
+                            values.push_back(value);
+                        },
+                        VecDiff::Pop {} => {
+                            values.pop_back().unwrap();
+                        },
+                        VecDiff::Clear {} => {
+                            values.clear();
+                        },
+                    }
+
+                    changed = true;
+                    continue;
+                },
+                Some(Poll::Pending) => {},
+            }
+            break;
+        }
+
+        if changed || *first {
+            *first = false;
+            Poll::Ready(Some(values.clone()))
+
+        } else if signal.is_none() {
+            Poll::Ready(None)
+
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+
 // TODO verify that this is correct
+#[cfg(feature = "std")]
 mod mutable_vec {
     use super::{SignalVec, VecDiff};
-    use std::pin::Pin;
-    use std::marker::Unpin;
-    use std::fmt;
-    use std::ops::{Deref, Index};
-    use std::slice::SliceIndex;
-    use std::borrow::Borrow;
-    use std::cmp::{Ord, Ordering};
-    use std::hash::{Hash, Hasher};
-    use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
-    use std::task::{Poll, Context};
+    use core::pin::Pin;
+    use core::marker::Unpin;
+    use core::fmt;
+    use core::ops::{Deref, Index};
+    use core::slice::SliceIndex;
+    use core::borrow::Borrow;
+    use core::cmp::{Ord, Ordering};
+    use core::hash::{Hash, Hasher};
+    use alloc::sync::Arc;
+    use crate::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+    use core::task::{Poll, Context};
     use futures_channel::mpsc;
     use futures_util::stream::StreamExt;
     use serde::{Serialize, Deserialize, Serializer, Deserializer};
+    use alloc::vec;
+    use alloc::vec::Vec;
 
 
     #[derive(Debug)]
@@ -2164,6 +2823,44 @@ mod mutable_vec {
             value
         }
 
+        fn swap_remove(&mut self, index: usize) -> A {
+            let len = self.values.len();
+
+            let value = self.values.swap_remove(index);
+
+            if index == (len - 1) {
+                self.notify(|| VecDiff::Pop {});
+
+            } else {
+                self.notify(|| VecDiff::RemoveAt { index });
+
+                // After removing, the last value is at len - 2, so it needs to be moved into the hole
+                let last = len - 2;
+
+                if index != last {
+                    self.notify(|| VecDiff::Move { old_index: last, new_index: index });
+                }
+            }
+
+            value
+        }
+
+        fn truncate(&mut self, len: usize) {
+            let end = self.values.len();
+
+            if len == 0 {
+                self.clear();
+
+            } else if len < end {
+                self.values.truncate(len);
+
+                // TODO use VecDiff::Batch
+                for _ in len..end {
+                    self.notify(|| VecDiff::Pop {});
+                }
+            }
+        }
+
         fn move_from_to(&mut self, old_index: usize, new_index: usize) {
             if old_index != new_index {
                 let value = self.values.remove(old_index);
@@ -2172,6 +2869,7 @@ mod mutable_vec {
             }
         }
 
+        #[allow(clippy::len_zero)]
         fn clear(&mut self) {
             if self.values.len() > 0 {
                 self.values.clear();
@@ -2180,6 +2878,7 @@ mod mutable_vec {
             }
         }
 
+        #[allow(clippy::len_zero)]
         fn retain<F>(&mut self, mut f: F) where F: FnMut(&A) -> bool {
             let mut len = self.values.len();
 
@@ -2230,6 +2929,7 @@ mod mutable_vec {
             output
         }
 
+        #[allow(clippy::len_zero)]
         fn signal_vec_copy(&mut self) -> MutableSignalVec<A> {
             let (sender, receiver) = mpsc::unbounded();
 
@@ -2283,6 +2983,7 @@ mod mutable_vec {
         }
 
         // TODO change this to return a MutableSignalVecClone ?
+        #[allow(clippy::len_zero)]
         fn signal_vec_clone(&mut self) -> MutableSignalVec<A> {
             let (sender, receiver) = mpsc::unbounded();
 
@@ -2343,11 +3044,13 @@ mod mutable_vec {
                 }
             }
 
+            #[allow(clippy::partialeq_ne_impl)]
             impl<'a, 'b, A, B> PartialEq<&'b [B]> for $t where A: PartialEq<B> {
                 #[inline] fn eq(&self, other: &&'b [B]) -> bool { self[..] == other[..] }
                 #[inline] fn ne(&self, other: &&'b [B]) -> bool { self[..] != other[..] }
             }
 
+            #[allow(clippy::partialeq_ne_impl)]
             impl<'a, 'b, A, B> PartialEq<$r> for $t where A: PartialEq<B> {
                 #[inline] fn eq(&self, other: &$r) -> bool { self[..] == other[..] }
                 #[inline] fn ne(&self, other: &$r) -> bool { self[..] != other[..] }
@@ -2429,7 +3132,7 @@ mod mutable_vec {
     // TODO rotate_left, rotate_right, sort, sort_by, sort_by_cached_key, sort_by_key,
     //      sort_unstable, sort_unstable_by, sort_unstable_by_key, dedup, dedup_by,
     //      dedup_by_key, drain, extend_from_slice, resize, resize_with, splice,
-    //      split_off, swap_remove, truncate
+    //      split_off
     // TODO Extend
     #[derive(Debug)]
     pub struct MutableVecLockMut<'a, A> where A: 'a {
@@ -2452,6 +3155,24 @@ mod mutable_vec {
             self.lock.clear()
         }
 
+        /// Removes the value at `index` and replaces it with the last value.
+        ///
+        /// This notifies with `VecDiff::RemoveAt` followed by `VecDiff::Move`
+        /// (or just `VecDiff::Pop` if `index` is the last index).
+        #[inline]
+        pub fn swap_remove(&mut self, index: usize) -> A {
+            self.lock.swap_remove(index)
+        }
+
+        /// Removes all of the values after `len`.
+        ///
+        /// This notifies with a `VecDiff::Pop` for each removed value
+        /// (or `VecDiff::Clear` if `len` is `0`).
+        #[inline]
+        pub fn truncate(&mut self, len: usize) {
+            self.lock.truncate(len)
+        }
+
         #[inline]
         pub fn move_from_to(&mut self, old_index: usize, new_index: usize) {
             self.lock.move_from_to(old_index, new_index);
@@ -2579,17 +3300,17 @@ mod mutable_vec {
 
         // TODO return Result ?
         #[inline]
-        pub fn lock_ref(&self) -> MutableVecLockRef<A> {
+        pub fn lock_ref(&self) -> MutableVecLockRef<'_, A> {
             MutableVecLockRef {
-                lock: self.0.read().unwrap(),
+                lock: self.0.read(),
             }
         }
 
         // TODO return Result ?
         #[inline]
-        pub fn lock_mut(&self) -> MutableVecLockMut<A> {
+        pub fn lock_mut(&self) -> MutableVecLockMut<'_, A> {
             MutableVecLockMut {
-                lock: self.0.write().unwrap(),
+                lock: self.0.write(),
             }
         }
     }
@@ -2597,20 +3318,31 @@ mod mutable_vec {
     impl<A: Copy> MutableVec<A> {
         #[inline]
         pub fn signal_vec(&self) -> MutableSignalVec<A> {
-            self.0.write().unwrap().signal_vec_copy()
+            self.0.write().signal_vec_copy()
         }
     }
 
     impl<A: Clone> MutableVec<A> {
         #[inline]
         pub fn signal_vec_cloned(&self) -> MutableSignalVec<A> {
-            self.0.write().unwrap().signal_vec_clone()
+            self.0.write().signal_vec_clone()
+        }
+
+        /// Returns a `Signal` which contains a snapshot of the whole `MutableVec`, as an `im::Vector`.
+        ///
+        /// Cloning the snapshot is `O(1)`, and each change only takes `O(log n)` time.
+        ///
+        /// This is only available with the `im` feature.
+        #[cfg(feature = "im")]
+        #[inline]
+        pub fn signal_cloned(&self) -> super::ImVectorSignal<MutableSignalVec<A>> {
+            super::ImVectorSignal::new(self.signal_vec_cloned())
         }
     }
 
     impl<A> fmt::Debug for MutableVec<A> where A: fmt::Debug {
         fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-            let state = self.0.read().unwrap();
+            let state = self.0.read();
 
             fmt.debug_tuple("MutableVec")
                 .field(&state.values)
@@ -2621,7 +3353,7 @@ mod mutable_vec {
     impl<T> Serialize for MutableVec<T> where T: Serialize {
         #[inline]
         fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
-            self.0.read().unwrap().values.serialize(serializer)
+            self.0.read().values.serialize(serializer)
         }
     }
 
@@ -2658,4 +3390,233 @@ mod mutable_vec {
     }
 }
 
+#[cfg(feature = "std")]
 pub use self::mutable_vec::*;
+
+
+#[cfg(feature = "std")]
+mod broadcaster_vec {
+    use super::{SignalVec, VecDiff};
+    use core::fmt;
+    use core::pin::Pin;
+    use alloc::sync::Arc;
+    use crate::sync::Ordering;
+    use crate::sync::Mutex;
+    use core::task::{Poll, Context};
+    use futures_channel::mpsc;
+    use futures_util::stream::StreamExt;
+    use futures_util::task;
+    use crate::signal::broadcaster::{BroadcasterStatus, BroadcasterNotifier};
+    use alloc::boxed::Box;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+
+    struct BroadcasterVecInnerState<A> where A: SignalVec {
+        signal: Option<Pin<Box<A>>>,
+        values: Vec<A::Item>,
+        senders: Vec<mpsc::UnboundedSender<VecDiff<A::Item>>>,
+    }
+
+    impl<A> BroadcasterVecInnerState<A> where A: SignalVec, A::Item: Clone {
+        // Poll the underlying signal for changes, forwarding them to every child and
+        // keeping `values` up to date so that new children can start from a snapshot.
+        fn poll_underlying(&mut self, notifier: Arc<BroadcasterNotifier>) {
+            let waker = task::waker(notifier);
+            let cx = &mut Context::from_waker(&waker);
+
+            loop {
+                match self.signal.as_mut().map(|signal| signal.as_mut().poll_vec_change(cx)) {
+                    Some(Poll::Ready(Some(change))) => {
+                        self.senders.retain(|sender| sender.unbounded_send(change.clone()).is_ok());
+                        change.apply_to_vec(&mut self.values);
+                    },
+                    Some(Poll::Ready(None)) => {
+                        self.signal = None;
+                        // This causes the children to end once they've received all of the changes
+                        self.senders.clear();
+                        break;
+                    },
+                    Some(Poll::Pending) | None => {
+                        break;
+                    },
+                }
+            }
+        }
+    }
+
+
+    struct BroadcasterVecSharedState<A> where A: SignalVec {
+        inner: Mutex<BroadcasterVecInnerState<A>>,
+        notifier: Arc<BroadcasterNotifier>,
+    }
+
+    impl<A> fmt::Debug for BroadcasterVecSharedState<A>
+        where A: fmt::Debug + SignalVec,
+              A::Item: fmt::Debug {
+
+        fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            let lock = self.inner.lock();
+
+            fmt.debug_struct("BroadcasterVecSharedState")
+                .field("signal", &lock.signal)
+                .field("values", &lock.values)
+                .field("notifier", &self.notifier)
+                .finish()
+        }
+    }
+
+
+    /// Wraps any `SignalVec` to make it possible to "broadcast" it to several
+    /// consumers.
+    ///
+    /// Each child `SignalVec` starts with a `VecDiff::Replace` containing the current
+    /// values, and then receives the same changes as the original `SignalVec`.
+    pub struct BroadcasterVec<A> where A: SignalVec {
+        shared_state: Arc<BroadcasterVecSharedState<A>>,
+    }
+
+    impl<A> BroadcasterVec<A> where A: SignalVec {
+        /// Create a new `BroadcasterVec`
+        pub fn new(signal: A) -> Self {
+            Self {
+                shared_state: Arc::new(BroadcasterVecSharedState {
+                    inner: Mutex::new(BroadcasterVecInnerState {
+                        signal: Some(Box::pin(signal)),
+                        values: vec![],
+                        senders: vec![],
+                    }),
+                    notifier: Arc::new(BroadcasterNotifier::new()),
+                }),
+            }
+        }
+    }
+
+    impl<A> BroadcasterVec<A> where A: SignalVec, A::Item: Clone {
+        fn child(&self) -> BroadcasterSignalVec<A> {
+            let (sender, receiver) = mpsc::unbounded();
+
+            {
+                let shared_state = &self.shared_state;
+                let mut lock = shared_state.inner.lock();
+
+                // This brings the values up to date, so that the Replace isn't followed by an older Replace
+                if shared_state.notifier.is_changed.swap(false, Ordering::AcqRel) {
+                    lock.poll_underlying(shared_state.notifier.clone());
+                }
+
+                // This is sent even if it is empty, so every child starts with the current state
+                sender.unbounded_send(VecDiff::Replace { values: lock.values.clone() }).unwrap();
+
+                // If the signal has already ended then the sender is dropped, so the child ends after the Replace
+                if lock.signal.is_some() {
+                    lock.senders.push(sender);
+                }
+            }
+
+            let status = Arc::new(BroadcasterStatus::new());
+
+            {
+                let mut lock = self.shared_state.notifier.targets.lock();
+                lock.push(Arc::downgrade(&status));
+            }
+
+            BroadcasterSignalVec {
+                status,
+                receiver,
+                shared_state: self.shared_state.clone(),
+            }
+        }
+    }
+
+    impl<A> BroadcasterVec<A> where A: SignalVec, A::Item: Copy {
+        /// Create a new `SignalVec` which copies values from the `SignalVec` wrapped
+        /// by the `BroadcasterVec`
+        #[inline]
+        pub fn signal_vec(&self) -> BroadcasterSignalVec<A> {
+            self.child()
+        }
+    }
+
+    impl<A> BroadcasterVec<A> where A: SignalVec, A::Item: Clone {
+        /// Create a new `SignalVec` which clones values from the `SignalVec` wrapped
+        /// by the `BroadcasterVec`
+        #[inline]
+        pub fn signal_vec_cloned(&self) -> BroadcasterSignalVec<A> {
+            self.child()
+        }
+    }
+
+    impl<A> Clone for BroadcasterVec<A> where A: SignalVec {
+        #[inline]
+        fn clone(&self) -> Self {
+            Self {
+                shared_state: self.shared_state.clone(),
+            }
+        }
+    }
+
+    impl<A> fmt::Debug for BroadcasterVec<A>
+        where A: fmt::Debug + SignalVec,
+              A::Item: fmt::Debug {
+
+        fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.debug_struct("BroadcasterVec")
+                .field("shared_state", &self.shared_state)
+                .finish()
+        }
+    }
+
+
+    #[must_use = "SignalVecs do nothing unless polled"]
+    pub struct BroadcasterSignalVec<A> where A: SignalVec {
+        status: Arc<BroadcasterStatus>,
+        receiver: mpsc::UnboundedReceiver<VecDiff<A::Item>>,
+        shared_state: Arc<BroadcasterVecSharedState<A>>,
+    }
+
+    impl<A> Unpin for BroadcasterSignalVec<A> where A: SignalVec {}
+
+    impl<A> SignalVec for BroadcasterSignalVec<A> where A: SignalVec, A::Item: Clone {
+        type Item = A::Item;
+
+        fn poll_vec_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<VecDiff<Self::Item>>> {
+            let shared_state = &self.shared_state;
+
+            // Only one child needs to poll the underlying signal, it then sends the changes to every child
+            if shared_state.notifier.is_changed.swap(false, Ordering::AcqRel) {
+                shared_state.inner.lock().poll_underlying(shared_state.notifier.clone());
+            }
+
+            match self.receiver.poll_next_unpin(cx) {
+                Poll::Pending => {
+                    *self.status.waker.lock() = Some(cx.waker().clone());
+
+                    // The underlying signal might have been woken up after it was polled
+                    if self.shared_state.notifier.is_changed.load(Ordering::Acquire) {
+                        cx.waker().wake_by_ref();
+                    }
+
+                    Poll::Pending
+                },
+                poll => poll,
+            }
+        }
+    }
+
+    impl<A> fmt::Debug for BroadcasterSignalVec<A>
+        where A: fmt::Debug + SignalVec,
+              A::Item: fmt::Debug {
+
+        fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.debug_struct("BroadcasterSignalVec")
+                .field("status", &self.status)
+                .field("receiver", &self.receiver)
+                .field("shared_state", &self.shared_state)
+                .finish()
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub use self::broadcaster_vec::*;
@@ -0,0 +1,40 @@
+//! Spawning Signals on a single-threaded executor (such as the browser's event loop).
+//!
+//! This is only available with the `wasm` feature.
+//!
+//! None of the `Signal` or `SignalVec` methods require `Send`, so they work with
+//! `!Send` values (such as `Rc` or JavaScript objects). These functions spawn them with
+//! [`wasm_bindgen_futures::spawn_local`](https://docs.rs/wasm-bindgen-futures/*/wasm_bindgen_futures/fn.spawn_local.html),
+//! so that you don't need to call `for_each` and then spawn the `Future` yourself.
+
+use std::future::Future;
+use wasm_bindgen_futures::spawn_local;
+use crate::signal::{Signal, SignalExt};
+use crate::signal_vec::{SignalVec, SignalVecExt, VecDiff};
+
+
+/// Spawns a task which calls `callback` with every value of `signal`.
+///
+/// This is the same as `spawn_local(signal.for_each(callback))`.
+///
+/// The task stops when the `Signal` ends.
+#[inline]
+pub fn spawn_local_for_each<A, U, F>(signal: A, callback: F)
+    where A: Signal + 'static,
+          U: Future<Output = ()> + 'static,
+          F: FnMut(A::Item) -> U + 'static {
+    spawn_local(signal.for_each(callback));
+}
+
+/// Spawns a task which calls `callback` with every change of `signal`.
+///
+/// This is the same as `spawn_local(signal.for_each(callback))`.
+///
+/// The task stops when the `SignalVec` ends.
+#[inline]
+pub fn spawn_local_for_each_vec<A, U, F>(signal: A, callback: F)
+    where A: SignalVec + 'static,
+          U: Future<Output = ()> + 'static,
+          F: FnMut(VecDiff<A::Item>) -> U + 'static {
+    spawn_local(signal.for_each(callback));
+}
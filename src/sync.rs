@@ -0,0 +1,245 @@
+// Locks which never return a poisoned error, so that the same code works with
+// std, parking_lot, and spin.
+//
+// The `spin` feature uses spinlocks, which don't need any support from the OS,
+// so it is used when the `std` feature is disabled. If both `parking_lot` and
+// `spin` are enabled then `parking_lot` is used.
+//
+// The `portable-atomic` feature uses portable-atomic for the atomics (and makes
+// spin use it too), for targets which don't have native atomics.
+//
+// With `--cfg loom` the locks and atomics are loom's, so that the notify / waker
+// code can be model checked (see the `loom_util` module). This takes priority
+// over the features.
+//
+// Memory ordering
+// ---------------
+//
+// The atomics follow these rules, so that they don't need to use SeqCst:
+//
+// * A flag which is only written and read while holding the same lock (such as the
+//   `has_changed` flags, which are accessed while holding the receiver's waker lock
+//   or the Mutable's RwLock) uses Relaxed. The lock already orders the accesses.
+//
+// * A flag which is read without holding the lock which protects the write (such as
+//   the Broadcaster notifier's `is_changed`) is written with Release and read with
+//   Acquire (AcqRel for swap).
+//
+// * A value which is read without a lock (such as the value of a MutableAtomic) is
+//   also Release / Acquire, so a `get` on another thread sees everything which
+//   happened before the `set`.
+//
+// * Reference counts follow `Arc`: increments are Relaxed, decrements are AcqRel.
+//
+// Because the Waker is always stored and taken while holding a lock, a change which
+// happens-before a `set` also happens-before the Signal outputs the new value.
+//
+// MutableAtomic is the exception, it doesn't use any locks. Its signals register their
+// Waker in an AtomicWaker *before* checking the version (which `set` increments with
+// AcqRel after storing the value), so either the signal sees the new version or the
+// `set` sees the Waker and wakes it up.
+//
+// By default a poisoned std lock is recovered, because otherwise a panic in one
+// consumer would cause every other user of the same Mutable (or Broadcaster, etc.)
+// to panic as well. With the `poison-panic` feature it panics instead.
+
+#[cfg(feature = "std")]
+use std::sync::LockResult;
+
+
+#[cfg(all(feature = "std", not(feature = "poison-panic")))]
+#[inline]
+pub(crate) fn recover<A>(result: LockResult<A>) -> A {
+    result.unwrap_or_else(|error| error.into_inner())
+}
+
+#[cfg(feature = "poison-panic")]
+#[inline]
+pub(crate) fn recover<A>(result: LockResult<A>) -> A {
+    result.unwrap()
+}
+
+
+pub(crate) use core::sync::atomic::Ordering;
+
+#[cfg(loom)]
+pub(crate) use loom::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, AtomicU64};
+
+#[cfg(all(feature = "portable-atomic", not(loom)))]
+pub(crate) use portable_atomic::{AtomicBool, AtomicPtr, AtomicUsize, AtomicU64};
+
+#[cfg(not(any(feature = "portable-atomic", loom)))]
+pub(crate) use core::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, AtomicU64};
+
+
+#[cfg(feature = "std")]
+pub(crate) use std::sync::OnceLock;
+
+#[cfg(not(feature = "std"))]
+pub(crate) use self::spin_once::OnceLock;
+
+
+#[cfg(loom)]
+pub(crate) use self::loom_sync::*;
+
+#[cfg(all(feature = "parking_lot", not(loom)))]
+pub(crate) use parking_lot::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+#[cfg(all(feature = "spin", not(feature = "parking_lot"), not(loom)))]
+pub(crate) use spin::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+#[cfg(all(feature = "std", not(any(feature = "parking_lot", feature = "spin", loom))))]
+pub(crate) use self::std_sync::*;
+
+#[cfg(loom)]
+mod loom_sync {
+    use std::fmt;
+    use super::recover;
+    pub(crate) use loom::sync::{MutexGuard, RwLockReadGuard, RwLockWriteGuard};
+
+
+    pub(crate) struct Mutex<A: ?Sized>(loom::sync::Mutex<A>);
+
+    impl<A> Mutex<A> {
+        #[inline]
+        pub(crate) fn new(value: A) -> Self {
+            Mutex(loom::sync::Mutex::new(value))
+        }
+    }
+
+    impl<A: ?Sized> Mutex<A> {
+        #[inline]
+        pub(crate) fn lock(&self) -> MutexGuard<'_, A> {
+            recover(self.0.lock())
+        }
+    }
+
+    // loom's Mutex doesn't implement Debug, and locking inside of Debug would add extra
+    // branches to every model, so this doesn't show the value
+    impl<A: ?Sized> fmt::Debug for Mutex<A> {
+        #[inline]
+        fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.debug_struct("Mutex").finish_non_exhaustive()
+        }
+    }
+
+
+    pub(crate) struct RwLock<A>(loom::sync::RwLock<A>);
+
+    impl<A> RwLock<A> {
+        #[inline]
+        pub(crate) fn new(value: A) -> Self {
+            RwLock(loom::sync::RwLock::new(value))
+        }
+
+        #[inline]
+        pub(crate) fn read(&self) -> RwLockReadGuard<'_, A> {
+            recover(self.0.read())
+        }
+
+        #[inline]
+        pub(crate) fn write(&self) -> RwLockWriteGuard<'_, A> {
+            recover(self.0.write())
+        }
+    }
+
+    impl<A> fmt::Debug for RwLock<A> {
+        #[inline]
+        fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.debug_struct("RwLock").finish_non_exhaustive()
+        }
+    }
+}
+
+#[cfg(all(feature = "std", not(any(feature = "parking_lot", feature = "spin", loom))))]
+mod std_sync {
+    use std::fmt;
+    use super::recover;
+    pub(crate) use std::sync::{MutexGuard, RwLockReadGuard, RwLockWriteGuard};
+
+
+    pub(crate) struct Mutex<A: ?Sized>(std::sync::Mutex<A>);
+
+    impl<A> Mutex<A> {
+        #[inline]
+        pub(crate) fn new(value: A) -> Self {
+            Mutex(std::sync::Mutex::new(value))
+        }
+    }
+
+    impl<A: ?Sized> Mutex<A> {
+        #[inline]
+        pub(crate) fn lock(&self) -> MutexGuard<'_, A> {
+            recover(self.0.lock())
+        }
+    }
+
+    impl<A: ?Sized + fmt::Debug> fmt::Debug for Mutex<A> {
+        #[inline]
+        fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            self.0.fmt(fmt)
+        }
+    }
+
+
+    pub(crate) struct RwLock<A: ?Sized>(std::sync::RwLock<A>);
+
+    impl<A> RwLock<A> {
+        #[inline]
+        pub(crate) fn new(value: A) -> Self {
+            RwLock(std::sync::RwLock::new(value))
+        }
+    }
+
+    impl<A: ?Sized> RwLock<A> {
+        #[inline]
+        pub(crate) fn read(&self) -> RwLockReadGuard<'_, A> {
+            recover(self.0.read())
+        }
+
+        #[inline]
+        pub(crate) fn write(&self) -> RwLockWriteGuard<'_, A> {
+            recover(self.0.write())
+        }
+    }
+
+    impl<A: ?Sized + fmt::Debug> fmt::Debug for RwLock<A> {
+        #[inline]
+        fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            self.0.fmt(fmt)
+        }
+    }
+}
+
+// This has the same API as the parts of `std::sync::OnceLock` which are used
+#[cfg(not(feature = "std"))]
+mod spin_once {
+    use core::fmt;
+
+
+    pub(crate) struct OnceLock<A>(spin::Once<A>);
+
+    impl<A> OnceLock<A> {
+        #[inline]
+        pub(crate) const fn new() -> Self {
+            OnceLock(spin::Once::new())
+        }
+
+        #[inline]
+        pub(crate) fn get_or_init<F>(&self, f: F) -> &A where F: FnOnce() -> A {
+            self.0.call_once(f)
+        }
+
+        #[inline]
+        pub(crate) fn take(&mut self) -> Option<A> {
+            core::mem::replace(&mut self.0, spin::Once::new()).try_into_inner()
+        }
+    }
+
+    impl<A: fmt::Debug> fmt::Debug for OnceLock<A> {
+        #[inline]
+        fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.debug_tuple("OnceLock").field(&self.0.get()).finish()
+        }
+    }
+}
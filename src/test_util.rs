@@ -0,0 +1,162 @@
+//! Utilities for testing Signals.
+//!
+//! This is only available with the `test-util` feature.
+
+use std::fmt;
+use std::pin::Pin;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Poll, Context};
+use futures_util::task::{self, ArcWake};
+use std::boxed::Box;
+use std::vec;
+use std::vec::Vec;
+
+
+#[derive(Debug)]
+struct TaskWaker {
+    is_woken: AtomicBool,
+}
+
+impl ArcWake for TaskWaker {
+    #[inline]
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.is_woken.store(true, Ordering::SeqCst);
+    }
+}
+
+
+struct Task {
+    waker: Arc<TaskWaker>,
+    future: Pin<Box<dyn Future<Output = ()>>>,
+}
+
+
+/// A single-threaded executor which only runs when it is told to.
+///
+/// This makes it possible to test exactly when changes propagate through a Signal
+/// pipeline (e.g. `Mutable` → `map` → `for_each`), without using a real runtime.
+///
+/// ```rust
+/// use std::rc::Rc;
+/// use std::cell::Cell;
+/// use futures::future::ready;
+/// use futures_signals::signal::{Mutable, SignalExt};
+/// use futures_signals::test_util::ManualExecutor;
+///
+/// let mutable = Mutable::new(1);
+/// let output = Rc::new(Cell::new(0));
+///
+/// let mut executor = ManualExecutor::new();
+///
+/// executor.spawn(mutable.signal().map(|x| x * 10).for_each({
+///     let output = output.clone();
+///     move |x| {
+///         output.set(x);
+///         ready(())
+///     }
+/// }));
+///
+/// // Nothing happens until the executor runs
+/// assert_eq!(output.get(), 0);
+///
+/// executor.run_until_stalled();
+/// assert_eq!(output.get(), 10);
+///
+/// mutable.set(2);
+/// assert_eq!(output.get(), 10);
+///
+/// executor.run_until_stalled();
+/// assert_eq!(output.get(), 20);
+/// ```
+pub struct ManualExecutor {
+    tasks: Vec<Task>,
+}
+
+impl ManualExecutor {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            tasks: vec![],
+        }
+    }
+
+    /// Adds `future` to the executor. It is not polled until `step` or `run_until_stalled` is called.
+    pub fn spawn<A>(&mut self, future: A) where A: Future<Output = ()> + 'static {
+        self.tasks.push(Task {
+            waker: Arc::new(TaskWaker {
+                is_woken: AtomicBool::new(true),
+            }),
+            future: Box::pin(future),
+        });
+    }
+
+    /// Returns the number of futures which haven't finished yet.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns `true` if all of the futures have finished.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Polls every future which has been woken up, once, in the order they were spawned.
+    ///
+    /// Futures which are woken up while this is running will not be polled until the next `step`.
+    ///
+    /// Returns `true` if any futures were polled.
+    pub fn step(&mut self) -> bool {
+        let mut polled = false;
+
+        self.tasks.retain_mut(|task| {
+            if task.waker.is_woken.swap(false, Ordering::SeqCst) {
+                polled = true;
+
+                let waker = task::waker(task.waker.clone());
+                let cx = &mut Context::from_waker(&waker);
+
+                match task.future.as_mut().poll(cx) {
+                    Poll::Ready(()) => false,
+                    Poll::Pending => true,
+                }
+
+            } else {
+                true
+            }
+        });
+
+        polled
+    }
+
+    /// Calls `step` until none of the futures have been woken up.
+    ///
+    /// Returns the number of times that `step` polled any futures.
+    pub fn run_until_stalled(&mut self) -> usize {
+        let mut steps = 0;
+
+        while self.step() {
+            steps += 1;
+        }
+
+        steps
+    }
+}
+
+impl Default for ManualExecutor {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ManualExecutor {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("ManualExecutor")
+            .field("len", &self.tasks.len())
+            .finish()
+    }
+}
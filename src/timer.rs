@@ -0,0 +1,249 @@
+//! Clocks for the time-based `Signal`s.
+//!
+//! This crate doesn't depend on any particular runtime, so anything which needs to
+//! know the time takes a [`Timer`](trait.Timer.html) (such as
+//! [`tween`](../signal/trait.SignalExt.html#method.tween) and
+//! [`rate_limit`](../signal/trait.SignalExt.html#method.rate_limit)), or a `Signal` of
+//! timestamps (such as [`MutableAnimation`](../animation/struct.MutableAnimation.html)).
+//! The [`timestamps`](fn.timestamps.html) function creates that `Signal` from any `Timer`.
+//!
+//! These `Timer`s are included:
+//!
+//! * With the `tokio` feature, [`TokioTimer`](struct.TokioTimer.html) uses the tokio runtime.
+//! * With the `async-std` feature, [`AsyncStdTimer`](struct.AsyncStdTimer.html) uses the async-std runtime.
+//! * With the `wasm` feature, [`WasmTimer`](struct.WasmTimer.html) uses the browser's `setTimeout`.
+
+use core::pin::Pin;
+use core::marker::Unpin;
+use core::future::Future;
+use core::time::Duration;
+use core::task::{Poll, Context};
+use crate::signal::Signal;
+
+
+/// A clock which can be used to drive the time-based `Signal`s.
+pub trait Timer {
+    type Sleep: Future<Output = ()>;
+
+    /// Returns the current time in milliseconds, relative to an arbitrary fixed point.
+    fn now(&self) -> f64;
+
+    /// Returns a `Future` which completes after `duration`.
+    fn sleep(&self, duration: Duration) -> Self::Sleep;
+}
+
+impl<A> Timer for &A where A: ?Sized + Timer {
+    type Sleep = A::Sleep;
+
+    #[inline]
+    fn now(&self) -> f64 {
+        A::now(self)
+    }
+
+    #[inline]
+    fn sleep(&self, duration: Duration) -> Self::Sleep {
+        A::sleep(self, duration)
+    }
+}
+
+
+/// Creates a `Signal` which contains the current time (in milliseconds), and which
+/// changes every `interval`.
+///
+/// It never ends, so it should only be polled while it is needed, which is what
+/// `MutableAnimation` does.
+#[inline]
+pub fn timestamps<A>(timer: A, interval: Duration) -> Timestamps<A> where A: Timer {
+    Timestamps {
+        timer,
+        interval,
+        sleep: None,
+    }
+}
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Timestamps<A> where A: Timer {
+    timer: A,
+    interval: Duration,
+    sleep: Option<A::Sleep>,
+}
+
+impl<A> Unpin for Timestamps<A> where A: Timer + Unpin, A::Sleep: Unpin {}
+
+impl<A> Signal for Timestamps<A> where A: Timer {
+    type Item = f64;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        unsafe_project!(self => {
+            mut timer,
+            mut interval,
+            pin sleep,
+        });
+
+        match sleep.as_mut().as_pin_mut().map(|sleep| sleep.poll(cx)) {
+            Some(Poll::Pending) => Poll::Pending,
+            Some(Poll::Ready(())) | None => {
+                sleep.set(Some(timer.sleep(*interval)));
+                Poll::Ready(Some(timer.now()))
+            },
+        }
+    }
+}
+
+
+#[cfg(feature = "tokio")]
+mod tokio_timer {
+    use super::Timer;
+    use std::time::Duration;
+    use tokio::time::{Instant, Sleep};
+
+
+    /// A [`Timer`](trait.Timer.html) which uses the tokio runtime.
+    ///
+    /// The time is relative to when the `TokioTimer` was created.
+    #[derive(Debug, Clone, Copy)]
+    pub struct TokioTimer {
+        start: Instant,
+    }
+
+    impl TokioTimer {
+        #[inline]
+        pub fn new() -> Self {
+            Self {
+                start: Instant::now(),
+            }
+        }
+    }
+
+    impl Default for TokioTimer {
+        #[inline]
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Timer for TokioTimer {
+        type Sleep = Sleep;
+
+        #[inline]
+        fn now(&self) -> f64 {
+            self.start.elapsed().as_secs_f64() * 1000.0
+        }
+
+        #[inline]
+        fn sleep(&self, duration: Duration) -> Self::Sleep {
+            tokio::time::sleep(duration)
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+pub use self::tokio_timer::*;
+
+
+#[cfg(feature = "async-std")]
+mod async_std_timer {
+    use super::Timer;
+    use std::pin::Pin;
+    use std::future::Future;
+    use std::time::{Duration, Instant};
+    use std::boxed::Box;
+
+
+    /// A [`Timer`](trait.Timer.html) which uses the async-std runtime.
+    ///
+    /// The time is relative to when the `AsyncStdTimer` was created.
+    #[derive(Debug, Clone, Copy)]
+    pub struct AsyncStdTimer {
+        start: Instant,
+    }
+
+    impl AsyncStdTimer {
+        #[inline]
+        pub fn new() -> Self {
+            Self {
+                start: Instant::now(),
+            }
+        }
+    }
+
+    impl Default for AsyncStdTimer {
+        #[inline]
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Timer for AsyncStdTimer {
+        // async-std doesn't have a nameable type for `sleep`, so it has to be boxed
+        type Sleep = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+        #[inline]
+        fn now(&self) -> f64 {
+            self.start.elapsed().as_secs_f64() * 1000.0
+        }
+
+        #[inline]
+        fn sleep(&self, duration: Duration) -> Self::Sleep {
+            Box::pin(async_std::task::sleep(duration))
+        }
+    }
+}
+
+#[cfg(feature = "async-std")]
+pub use self::async_std_timer::*;
+
+
+#[cfg(feature = "wasm")]
+mod wasm_timer {
+    use super::Timer;
+    use std::convert::TryFrom;
+    use std::time::Duration;
+    use gloo_timers::future::TimeoutFuture;
+
+
+    /// A [`Timer`](trait.Timer.html) which uses the browser's `setTimeout`.
+    ///
+    /// The time is relative to when the `WasmTimer` was created. It uses `Date.now()`, so it
+    /// can jump if the system clock changes.
+    #[derive(Debug, Clone, Copy)]
+    pub struct WasmTimer {
+        start: f64,
+    }
+
+    impl WasmTimer {
+        #[inline]
+        pub fn new() -> Self {
+            Self {
+                start: js_sys::Date::now(),
+            }
+        }
+    }
+
+    impl Default for WasmTimer {
+        #[inline]
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Timer for WasmTimer {
+        type Sleep = TimeoutFuture;
+
+        #[inline]
+        fn now(&self) -> f64 {
+            js_sys::Date::now() - self.start
+        }
+
+        #[inline]
+        fn sleep(&self, duration: Duration) -> Self::Sleep {
+            // setTimeout fires immediately if the delay is bigger than a signed 32-bit integer
+            let millis = i32::try_from(duration.as_millis()).unwrap_or(i32::MAX);
+            TimeoutFuture::new(millis as u32)
+        }
+    }
+}
+
+#[cfg(feature = "wasm")]
+pub use self::wasm_timer::*;
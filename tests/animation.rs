@@ -0,0 +1,109 @@
+use std::task::Poll;
+use futures_signals::animation::MutableAnimation;
+use futures_signals::signal::{SignalExt, Mutable};
+
+mod util;
+
+
+#[test]
+fn test_sync() {
+    let _: Box<dyn Send + Sync> = Box::new(MutableAnimation::new(100.0));
+    let _: Box<dyn Send + Sync> = Box::new(MutableAnimation::new(100.0).signal(Mutable::new(0.0).signal()));
+}
+
+
+#[test]
+fn test_animate_to() {
+    let time = Mutable::new(0.0);
+    let animation = MutableAnimation::new(100.0);
+    let mut signal = animation.signal(time.signal());
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(0.0)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        // Time doesn't matter when it isn't animating
+        time.set(1000.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        animation.animate_to(1.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        time.set(1025.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(0.25)));
+
+        time.set(1050.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(0.5)));
+        assert_eq!(animation.current(), 0.5);
+
+        // It goes back from the current value
+        animation.animate_to(0.0);
+        time.set(1075.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(0.25)));
+
+        time.set(2000.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(0.0)));
+
+        time.set(3000.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+    });
+}
+
+
+#[test]
+fn test_jump_to() {
+    let time = Mutable::new(0.0);
+    let animation = MutableAnimation::new(100.0);
+    let mut signal = animation.signal(time.signal());
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(0.0)));
+
+        animation.jump_to(0.75);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(0.75)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        drop(animation);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[test]
+fn test_pause() {
+    let time = Mutable::new(0.0);
+    let animation = MutableAnimation::new(100.0);
+    let mut signal = animation.signal(time.signal());
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(0.0)));
+
+        animation.animate_to(1.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        time.set(10.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(0.1)));
+
+        animation.pause();
+        time.set(50.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        // The time spent paused is skipped
+        animation.play();
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        time.set(60.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(0.2)));
+
+        // A shorter duration makes it faster
+        animation.set_duration(10.0);
+        time.set(61.0);
+        assert_eq!(signal.poll_change_unpin(cx).map(|x| x.map(|x| (x * 100.0).round())), Poll::Ready(Some(30.0)));
+
+        time.set(100.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(1.0)));
+
+        drop(animation);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
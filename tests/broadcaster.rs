@@ -1,5 +1,9 @@
 use futures_signals::map_ref;
-use futures_signals::signal::{SignalExt, Mutable, Broadcaster};
+use futures_signals::signal::{SignalExt, Mutable, Broadcaster, LocalMutable, LocalBroadcaster};
+#[cfg(feature = "std")]
+use futures_signals::signal_vec::{SignalVecExt, MutableVec, VecDiff, BroadcasterVec};
+#[cfg(feature = "std")]
+use futures_signals::signal_map::{SignalMapExt, MutableBTreeMap, MapDiff, BroadcasterMap};
 use std::task::Poll;
 
 mod util;
@@ -30,6 +34,76 @@ fn test_broadcaster() {
     });
 }
 
+#[test]
+fn test_clone() {
+    let mutable = Mutable::new(1);
+    let broadcaster = Broadcaster::new(mutable.signal());
+    let cloned = broadcaster.clone();
+    let mut b1 = broadcaster.signal();
+    let mut b2 = cloned.signal();
+
+    drop(broadcaster);
+
+    util::with_noop_context(|cx| {
+        assert_eq!(b1.poll_change_unpin(cx), Poll::Ready(Some(1)));
+        assert_eq!(b2.poll_change_unpin(cx), Poll::Ready(Some(1)));
+
+        mutable.set(5);
+        assert_eq!(b1.poll_change_unpin(cx), Poll::Ready(Some(5)));
+        assert_eq!(b2.poll_change_unpin(cx), Poll::Ready(Some(5)));
+
+        drop(mutable);
+        assert_eq!(b1.poll_change_unpin(cx), Poll::Ready(None));
+        assert_eq!(cloned.signal().poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+#[test]
+fn test_signal_ref() {
+    let mutable = Mutable::new(vec![1, 2, 3]);
+    let broadcaster = Broadcaster::new(mutable.signal_cloned());
+    let mut b1 = broadcaster.signal_ref(|value| value.len());
+    let mut b2 = broadcaster.signal_ref(|value| value[0]);
+
+    util::with_noop_context(|cx| {
+        assert_eq!(b1.poll_change_unpin(cx), Poll::Ready(Some(3)));
+        assert_eq!(b1.poll_change_unpin(cx), Poll::Pending);
+        assert_eq!(b2.poll_change_unpin(cx), Poll::Ready(Some(1)));
+        assert_eq!(b2.poll_change_unpin(cx), Poll::Pending);
+
+        mutable.set(vec![5]);
+        assert_eq!(b1.poll_change_unpin(cx), Poll::Ready(Some(1)));
+        assert_eq!(b2.poll_change_unpin(cx), Poll::Ready(Some(5)));
+
+        drop(mutable);
+        assert_eq!(b1.poll_change_unpin(cx), Poll::Ready(None));
+        assert_eq!(b2.poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+#[test]
+fn test_local_broadcaster() {
+    let mutable = LocalMutable::new(std::rc::Rc::new(1));
+    let broadcaster = LocalBroadcaster::new(mutable.signal_cloned());
+    let mut b1 = broadcaster.signal_cloned();
+    let mut b2 = broadcaster.clone().signal_ref(|value| **value + 1);
+
+    util::with_noop_context(|cx| {
+        assert_eq!(b1.poll_change_unpin(cx), Poll::Ready(Some(std::rc::Rc::new(1))));
+        assert_eq!(b1.poll_change_unpin(cx), Poll::Pending);
+        assert_eq!(b2.poll_change_unpin(cx), Poll::Ready(Some(2)));
+        assert_eq!(b2.poll_change_unpin(cx), Poll::Pending);
+
+        mutable.set(std::rc::Rc::new(5));
+        assert_eq!(b1.poll_change_unpin(cx), Poll::Ready(Some(std::rc::Rc::new(5))));
+        assert_eq!(b2.poll_change_unpin(cx), Poll::Ready(Some(6)));
+
+        drop(mutable);
+        assert_eq!(b1.poll_change_unpin(cx), Poll::Ready(None));
+        assert_eq!(b2.poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
 #[test]
 fn test_polls() {
     let mutable = Mutable::new(1);
@@ -62,3 +136,114 @@ fn test_polls() {
         Poll::Ready(None),
     ]);
 }
+
+
+#[cfg(feature = "std")]
+#[test]
+fn test_broadcaster_vec() {
+    let vec = MutableVec::new_with_values(vec![1, 2]);
+    let broadcaster = BroadcasterVec::new(vec.signal_vec());
+    let mut b1 = broadcaster.signal_vec();
+    let mut b2 = broadcaster.signal_vec_cloned();
+
+    util::with_noop_context(|cx| {
+        assert_eq!(b1.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::Replace { values: vec![1, 2] })));
+        assert_eq!(b1.poll_vec_change_unpin(cx), Poll::Pending);
+        assert_eq!(b2.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::Replace { values: vec![1, 2] })));
+        assert_eq!(b2.poll_vec_change_unpin(cx), Poll::Pending);
+
+        vec.lock_mut().push(3);
+        assert_eq!(b1.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::Push { value: 3 })));
+        assert_eq!(b1.poll_vec_change_unpin(cx), Poll::Pending);
+
+        // Late subscribers start with the current values
+        let mut b3 = broadcaster.signal_vec();
+        assert_eq!(b3.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::Replace { values: vec![1, 2, 3] })));
+        assert_eq!(b3.poll_vec_change_unpin(cx), Poll::Pending);
+
+        vec.lock_mut().remove(0);
+        assert_eq!(b3.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::RemoveAt { index: 0 })));
+        assert_eq!(b2.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::Push { value: 3 })));
+        assert_eq!(b2.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::RemoveAt { index: 0 })));
+        assert_eq!(b2.poll_vec_change_unpin(cx), Poll::Pending);
+
+        drop(vec);
+        assert_eq!(b1.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::RemoveAt { index: 0 })));
+        assert_eq!(b1.poll_vec_change_unpin(cx), Poll::Ready(None));
+        assert_eq!(b2.poll_vec_change_unpin(cx), Poll::Ready(None));
+        assert_eq!(b3.poll_vec_change_unpin(cx), Poll::Ready(None));
+
+        let mut b4 = broadcaster.signal_vec();
+        assert_eq!(b4.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::Replace { values: vec![2, 3] })));
+        assert_eq!(b4.poll_vec_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[cfg(feature = "std")]
+#[test]
+fn test_broadcaster_map() {
+    let map = MutableBTreeMap::new();
+    map.lock_mut().insert(1, 10);
+
+    let broadcaster = BroadcasterMap::new(map.signal_map());
+    let mut b1 = broadcaster.signal_map_cloned();
+    let mut b2 = broadcaster.signal_map_cloned();
+
+    util::with_noop_context(|cx| {
+        assert_eq!(b1.poll_map_change_unpin(cx), Poll::Ready(Some(MapDiff::Replace { entries: vec![(1, 10)] })));
+        assert_eq!(b1.poll_map_change_unpin(cx), Poll::Pending);
+
+        map.lock_mut().insert(2, 20);
+        map.lock_mut().insert(1, 15);
+        assert_eq!(b1.poll_map_change_unpin(cx), Poll::Ready(Some(MapDiff::Insert { key: 2, value: 20 })));
+        assert_eq!(b1.poll_map_change_unpin(cx), Poll::Ready(Some(MapDiff::Update { key: 1, value: 15 })));
+        assert_eq!(b1.poll_map_change_unpin(cx), Poll::Pending);
+
+        // Late subscribers start with the current entries
+        let mut b3 = broadcaster.signal_map_cloned();
+        assert_eq!(b3.poll_map_change_unpin(cx), Poll::Ready(Some(MapDiff::Replace { entries: vec![(1, 15), (2, 20)] })));
+        assert_eq!(b3.poll_map_change_unpin(cx), Poll::Pending);
+
+        assert_eq!(b2.poll_map_change_unpin(cx), Poll::Ready(Some(MapDiff::Replace { entries: vec![(1, 10)] })));
+        assert_eq!(b2.poll_map_change_unpin(cx), Poll::Ready(Some(MapDiff::Insert { key: 2, value: 20 })));
+        assert_eq!(b2.poll_map_change_unpin(cx), Poll::Ready(Some(MapDiff::Update { key: 1, value: 15 })));
+        assert_eq!(b2.poll_map_change_unpin(cx), Poll::Pending);
+
+        map.lock_mut().remove(&1);
+        drop(map);
+        assert_eq!(b3.poll_map_change_unpin(cx), Poll::Ready(Some(MapDiff::Remove { key: 1 })));
+        assert_eq!(b3.poll_map_change_unpin(cx), Poll::Ready(None));
+        assert_eq!(b1.poll_map_change_unpin(cx), Poll::Ready(Some(MapDiff::Remove { key: 1 })));
+        assert_eq!(b1.poll_map_change_unpin(cx), Poll::Ready(None));
+        assert_eq!(b2.poll_map_change_unpin(cx), Poll::Ready(Some(MapDiff::Remove { key: 1 })));
+        assert_eq!(b2.poll_map_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[cfg(feature = "std")]
+#[test]
+fn test_broadcaster_empty() {
+    let vec = MutableVec::<u32>::new();
+    let broadcaster_vec = BroadcasterVec::new(vec.signal_vec());
+
+    let map = MutableBTreeMap::<u32, u32>::new();
+    let broadcaster_map = BroadcasterMap::new(map.signal_map());
+
+    util::with_noop_context(|cx| {
+        // Every child starts with the current state, even if it is empty
+        let mut b1 = broadcaster_vec.signal_vec();
+        assert_eq!(b1.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::Replace { values: vec![] })));
+        assert_eq!(b1.poll_vec_change_unpin(cx), Poll::Pending);
+
+        let mut b2 = broadcaster_map.signal_map_cloned();
+        assert_eq!(b2.poll_map_change_unpin(cx), Poll::Ready(Some(MapDiff::Replace { entries: vec![] })));
+        assert_eq!(b2.poll_map_change_unpin(cx), Poll::Pending);
+
+        vec.lock_mut().push(1);
+        map.lock_mut().insert(1, 10);
+        assert_eq!(b1.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::Push { value: 1 })));
+        assert_eq!(b2.poll_map_change_unpin(cx), Poll::Ready(Some(MapDiff::Insert { key: 1, value: 10 })));
+    });
+}
@@ -0,0 +1,164 @@
+use std::task::Poll;
+use futures_signals::signal::{SignalExt, channel, channel_with, bounded_channel};
+use futures_util::sink::SinkExt;
+use futures_util::stream::StreamExt;
+use futures_executor::block_on;
+use futures_util::future::join;
+
+mod util;
+
+
+#[test]
+fn test_bounded_channel() {
+    let (mut sender, mut receiver) = bounded_channel(2);
+
+    util::with_noop_context(|cx| {
+        assert_eq!(receiver.poll_change_unpin(cx), Poll::Pending);
+
+        assert_eq!(sender.try_send(1), Ok(()));
+        assert_eq!(sender.try_send(2), Ok(()));
+        assert_eq!(sender.try_send(3), Err(3));
+        assert_eq!(sender.poll_ready_unpin(cx), Poll::Pending);
+
+        assert_eq!(receiver.poll_change_unpin(cx), Poll::Ready(Some(1)));
+        assert_eq!(sender.poll_ready_unpin(cx), Poll::Ready(Ok(())));
+        assert_eq!(sender.try_send(3), Ok(()));
+
+        assert_eq!(receiver.poll_change_unpin(cx), Poll::Ready(Some(2)));
+        assert_eq!(receiver.poll_change_unpin(cx), Poll::Ready(Some(3)));
+        assert_eq!(receiver.poll_change_unpin(cx), Poll::Pending);
+
+        drop(sender);
+        assert_eq!(receiver.poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[test]
+fn test_bounded_channel_sink() {
+    let (mut sender, receiver) = bounded_channel(1);
+
+    let send = async move {
+        for value in 0..5 {
+            sender.send(value).await.unwrap();
+        }
+    };
+
+    let (_, values) = block_on(join(send, receiver.to_stream().collect::<Vec<_>>()));
+
+    assert_eq!(values, vec![0, 1, 2, 3, 4]);
+}
+
+
+#[test]
+fn test_bounded_channel_dropped_receiver() {
+    let (mut sender, receiver) = bounded_channel(1);
+
+    drop(receiver);
+
+    assert_eq!(sender.try_send(1), Err(1));
+    assert_eq!(block_on(sender.send(1)), Err(futures_signals::signal::SendError));
+}
+
+
+#[test]
+fn test_channel_clone_sender() {
+    let (sender1, mut receiver) = channel(1);
+    let sender2 = sender1.clone();
+
+    util::with_noop_context(|cx| {
+        assert_eq!(receiver.poll_change_unpin(cx), Poll::Ready(Some(1)));
+        assert_eq!(receiver.poll_change_unpin(cx), Poll::Pending);
+
+        sender2.send(2).unwrap();
+        assert_eq!(receiver.poll_change_unpin(cx), Poll::Ready(Some(2)));
+
+        drop(sender1);
+        assert_eq!(receiver.poll_change_unpin(cx), Poll::Pending);
+
+        sender2.send(3).unwrap();
+        drop(sender2);
+        assert_eq!(receiver.poll_change_unpin(cx), Poll::Ready(Some(3)));
+        assert_eq!(receiver.poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[test]
+fn test_channel_send_if_changed() {
+    let (sender, receiver) = channel(1);
+
+    let polls = util::get_signal_polls(receiver, move || {
+        sender.send_if_changed(1).unwrap();
+        sender.send_if_changed(2).unwrap();
+        sender.send_if_changed(2).unwrap();
+    });
+
+    assert_eq!(polls, vec![
+        Poll::Ready(Some(1)),
+        Poll::Pending,
+        Poll::Ready(Some(2)),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_channel_to_stream() {
+    let (sender, receiver) = channel(1);
+    let (other_sender, other) = futures_channel::mpsc::unbounded();
+
+    let mut stream = futures_util::stream::select(receiver.to_stream(), other);
+
+    util::with_noop_context(|cx| {
+        assert_eq!(stream.poll_next_unpin(cx), Poll::Ready(Some(1)));
+        assert_eq!(stream.poll_next_unpin(cx), Poll::Pending);
+
+        // The channel is lossy, so only the most recent value is received
+        sender.send(2).unwrap();
+        sender.send(3).unwrap();
+        other_sender.unbounded_send(10).unwrap();
+        assert_eq!(stream.poll_next_unpin(cx), Poll::Ready(Some(3)));
+        assert_eq!(stream.poll_next_unpin(cx), Poll::Ready(Some(10)));
+        assert_eq!(stream.poll_next_unpin(cx), Poll::Pending);
+
+        drop(sender);
+        drop(other_sender);
+        assert_eq!(stream.poll_next_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[test]
+fn test_channel_with() {
+    let (sender, receiver) = channel_with(vec![1], |old: &mut Vec<u32>, new| old.extend(new));
+
+    let polls = util::get_signal_polls(receiver, move || {
+        sender.send(vec![2]).unwrap();
+        sender.send(vec![3, 4]).unwrap();
+    });
+
+    assert_eq!(polls, vec![
+        Poll::Ready(Some(vec![1])),
+        Poll::Pending,
+        Poll::Ready(Some(vec![2, 3, 4])),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_channel_with_sum() {
+    let (sender, mut receiver) = channel_with(1, |old, new| *old += new);
+
+    sender.send(2).unwrap();
+    sender.send(3).unwrap();
+
+    util::with_noop_context(|cx| {
+        assert_eq!(receiver.poll_change_unpin(cx), Poll::Ready(Some(6)));
+        assert_eq!(receiver.poll_change_unpin(cx), Poll::Pending);
+
+        sender.send(4).unwrap();
+        assert_eq!(receiver.poll_change_unpin(cx), Poll::Ready(Some(4)));
+    });
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "std")]
+
 use std::task::Poll;
 use futures_signals::cancelable_future;
 use futures_util::future::{ready, FutureExt};
@@ -0,0 +1,36 @@
+#![cfg(feature = "graph")]
+
+use std::task::Poll;
+use futures_signals::graph;
+use futures_signals::signal::{Mutable, SignalExt};
+
+mod util;
+
+
+#[test]
+fn test_dump_dot() {
+    let mutable = Mutable::new(1u32);
+
+    let mut signal = mutable.signal().map(|x| x + 1).named("plus one");
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(2)));
+    });
+
+    let dot = graph::dump_dot();
+
+    let mutable_node = dot.lines().find(|line| line.contains("[label=\"Mutable<u32>\"]")).unwrap();
+    let named_node = dot.lines().find(|line| line.contains("[label=\"plus one\"]")).unwrap();
+
+    let mutable_id = mutable_node.trim().split(' ').next().unwrap();
+    let named_id = named_node.trim().split(' ').next().unwrap();
+
+    assert!(dot.starts_with("digraph signals {\n"));
+    assert!(dot.contains(&format!("    {} -> {};\n", mutable_id, named_id)));
+
+    drop(signal);
+
+    let dot = graph::dump_dot();
+    assert!(!dot.contains("plus one"));
+    assert!(!dot.contains(&format!("{} ->", mutable_id)));
+}
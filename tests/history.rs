@@ -0,0 +1,70 @@
+use std::task::Poll;
+use futures_signals::signal::{History, SignalExt};
+
+mod util;
+
+
+#[test]
+fn test_undo_redo() {
+    let history = History::new(1);
+
+    let mut signal = history.signal();
+    let mut can_undo = history.can_undo();
+    let mut can_redo = history.can_redo();
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(1)));
+        assert_eq!(can_undo.poll_change_unpin(cx), Poll::Ready(Some(false)));
+        assert_eq!(can_redo.poll_change_unpin(cx), Poll::Ready(Some(false)));
+
+        assert!(!history.undo());
+
+        history.set(2);
+        history.update(|value| *value += 1);
+        assert_eq!(history.get(), 3);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(3)));
+        assert_eq!(can_undo.poll_change_unpin(cx), Poll::Ready(Some(true)));
+        assert_eq!(can_redo.poll_change_unpin(cx), Poll::Pending);
+
+        assert!(history.undo());
+        assert_eq!(history.get(), 2);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(2)));
+        assert_eq!(can_undo.poll_change_unpin(cx), Poll::Pending);
+        assert_eq!(can_redo.poll_change_unpin(cx), Poll::Ready(Some(true)));
+
+        assert!(history.undo());
+        assert_eq!(history.get(), 1);
+        assert_eq!(can_undo.poll_change_unpin(cx), Poll::Ready(Some(false)));
+
+        assert!(history.redo());
+        assert_eq!(history.get(), 2);
+        assert_eq!(can_undo.poll_change_unpin(cx), Poll::Ready(Some(true)));
+
+        // Setting a new value discards the redo snapshots
+        history.set(10);
+        assert!(!history.redo());
+        assert_eq!(can_redo.poll_change_unpin(cx), Poll::Ready(Some(false)));
+
+        assert!(history.undo());
+        assert_eq!(history.get(), 2);
+    });
+}
+
+
+#[test]
+fn test_limit() {
+    let history = History::with_limit(0, 2);
+
+    history.set(1);
+    history.set(2);
+    history.set(3);
+
+    assert!(history.undo());
+    assert!(history.undo());
+    assert!(!history.undo());
+    assert_eq!(history.get(), 1);
+
+    history.clear_history();
+    assert!(!history.redo());
+    assert_eq!(history.get(), 1);
+}
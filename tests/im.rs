@@ -0,0 +1,62 @@
+#![cfg(feature = "im")]
+
+use std::task::Poll;
+use futures_signals::signal::SignalExt;
+use futures_signals::signal_vec::MutableVec;
+use futures_signals::signal_map::MutableBTreeMap;
+
+mod util;
+
+
+#[test]
+fn test_vec_signal_cloned() {
+    let vec = MutableVec::<u32>::new();
+
+    let mut signal = vec.signal_cloned();
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(im::Vector::new())));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        {
+            let mut lock = vec.lock_mut();
+            lock.push(1);
+            lock.push(2);
+            lock.push(3);
+            lock.move_from_to(0, 2);
+            lock.set(0, 5);
+            lock.remove(1);
+        }
+
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(im::vector![5, 1])));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        drop(vec);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[test]
+fn test_btree_map_signal_cloned() {
+    let map = MutableBTreeMap::new();
+    map.lock_mut().insert("a", 1);
+
+    let mut signal = map.signal_cloned();
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(im::ordmap!{ "a" => 1 })));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        {
+            let mut lock = map.lock_mut();
+            lock.insert("b", 2);
+            lock.insert("a", 3);
+            lock.remove(&"b");
+            lock.insert("c", 4);
+        }
+
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(im::ordmap!{ "a" => 3, "c" => 4 })));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+    });
+}
@@ -0,0 +1,187 @@
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Context};
+use futures_signals::signal::{SignalExt, LocalMutable};
+use futures_util::task::{waker, ArcWake};
+
+mod util;
+
+
+#[test]
+fn test_lock_mut() {
+    let m = LocalMutable::new(1);
+
+    let polls = util::get_signal_polls(m.signal(), move || {
+        let mut lock = m.lock_mut();
+
+        if *lock == 1 {
+            *lock = 5;
+        }
+    });
+
+    assert_eq!(polls, vec![
+        Poll::Ready(Some(1)),
+        Poll::Pending,
+        Poll::Ready(Some(5)),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_not_send() {
+    // Rc isn't Send, so it can't be used with Mutable
+    let m = LocalMutable::new(Rc::new(1));
+    let mut signal = m.signal_cloned();
+    let mut signal_ref = m.signal_ref(|value| **value + 1);
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(Rc::new(1))));
+        assert_eq!(signal_ref.poll_change_unpin(cx), Poll::Ready(Some(2)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        m.set_neq(Rc::new(1));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        assert_eq!(m.replace(Rc::new(5)), Rc::new(1));
+        assert_eq!(*m.lock_ref(), Rc::new(5));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(Rc::new(5))));
+        assert_eq!(signal_ref.poll_change_unpin(cx), Poll::Ready(Some(6)));
+
+        let cloned = m.clone();
+        drop(m);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        drop(cloned);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(None));
+        assert_eq!(signal_ref.poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[test]
+fn test_same_api_as_mutable() {
+    let m = LocalMutable::new(Rc::new(1));
+    let read_only = m.read_only();
+
+    let mut signal = read_only.signal_cloned();
+    let mut arc = m.signal_arc();
+    assert_eq!(m.receiver_count(), 2);
+    assert!(read_only.has_receivers());
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(Rc::new(1))));
+        assert_eq!(arc.poll_change_unpin(cx).map(|value| value.map(|value| Rc::clone(&value))), Poll::Ready(Some(Rc::new(1))));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        assert_eq!(m.compare_and_set(&Rc::new(5), Rc::new(10)), Err(Rc::new(1)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        assert_eq!(m.compare_and_set(&Rc::new(1), Rc::new(10)), Ok(Rc::new(1)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(Rc::new(10))));
+
+        m.transaction(|value| {
+            *value = Rc::new(20);
+            *value = Rc::new(30);
+        });
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(Rc::new(30))));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+        assert_eq!(arc.poll_change_unpin(cx).map(|value| value.map(|value| Rc::clone(&value))), Poll::Ready(Some(Rc::new(30))));
+        assert_eq!(*read_only.get_arc(), Rc::new(30));
+    });
+
+    drop(arc);
+    assert_eq!(m.receiver_count(), 1);
+}
+
+
+#[test]
+fn test_arc_helpers() {
+    let m = LocalMutable::new(Arc::new(vec![1, 2, 3]));
+
+    let mut len = m.signal_deref(|value| value.len());
+
+    util::with_noop_context(|cx| {
+        assert_eq!(len.poll_change_unpin(cx), Poll::Ready(Some(3)));
+        assert_eq!(len.poll_change_unpin(cx), Poll::Pending);
+
+        m.make_mut(|value| value.push(4));
+        assert_eq!(len.poll_change_unpin(cx), Poll::Ready(Some(4)));
+
+        m.set_arc(vec![]);
+        assert_eq!(len.poll_change_unpin(cx), Poll::Ready(Some(0)));
+        assert_eq!(len.poll_change_unpin(cx), Poll::Pending);
+    });
+}
+
+
+#[test]
+fn test_lens() {
+    let m = LocalMutable::new((1, Rc::new("foo")));
+
+    let first = m.lens(|value| value.0, |value, first| value.0 = first);
+    let mut signal = first.signal();
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(1)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        first.set(5);
+        assert_eq!(first.get(), 5);
+        assert_eq!(m.lock_ref().0, 5);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(5)));
+    });
+}
+
+
+#[test]
+fn test_drop_signal_while_locked() {
+    let m = LocalMutable::new(1);
+    let signal = m.signal();
+
+    {
+        let mut lock = m.lock_mut();
+        *lock = 2;
+        // Dropping a signal must not borrow the value
+        drop(signal);
+    }
+
+    assert_eq!(m.receiver_count(), 0);
+}
+
+
+struct OrderWaker {
+    id: usize,
+    order: Arc<Mutex<Vec<usize>>>,
+}
+
+impl ArcWake for OrderWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.order.lock().unwrap().push(arc_self.id);
+    }
+}
+
+#[test]
+fn test_notify_order() {
+    let m = LocalMutable::new(1);
+    let order = Arc::new(Mutex::new(vec![]));
+
+    let mut signals = [
+        (1, m.signal()),
+        (2, m.signal_with_priority(-1)),
+        (3, m.signal()),
+        (4, m.signal_with_priority(5)),
+    ];
+
+    for (id, signal) in signals.iter_mut() {
+        let waker = waker(Arc::new(OrderWaker { id: *id, order: order.clone() }));
+        let cx = &mut Context::from_waker(&waker);
+
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(1)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+    }
+
+    m.set(2);
+
+    assert_eq!(*order.lock().unwrap(), vec![4, 1, 3, 2]);
+}
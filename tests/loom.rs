@@ -0,0 +1,83 @@
+#![cfg(loom)]
+
+use futures_signals::loom_util::{model, thread, block_on, collect};
+use futures_signals::signal::{Mutable, SignalExt, Broadcaster, LocalBroadcaster};
+
+
+#[test]
+fn test_mutable() {
+    model(|| {
+        let mutable = Mutable::new(0);
+        let signal = mutable.signal();
+
+        let handle = thread::spawn(move || {
+            mutable.set(1);
+            mutable.set(2);
+        });
+
+        let values = collect(signal);
+        assert_eq!(values.last(), Some(&2));
+
+        handle.join().unwrap();
+    });
+}
+
+
+#[test]
+fn test_mutable_batch() {
+    model(|| {
+        let mutable = Mutable::new(0);
+        let signal = mutable.signal();
+
+        let handle = thread::spawn(move || {
+            futures_signals::signal::batch(|| {
+                mutable.set(1);
+            });
+        });
+
+        let values = collect(signal);
+        assert_eq!(values.last(), Some(&1));
+
+        handle.join().unwrap();
+    });
+}
+
+
+#[test]
+fn test_broadcaster() {
+    model(|| {
+        let mutable = Mutable::new(0);
+        let broadcaster = Broadcaster::new(mutable.signal());
+        let signal = broadcaster.signal().map(|x| x * 10);
+
+        let handle = thread::spawn({
+            let mutable = mutable.clone();
+            move || mutable.set(1)
+        });
+
+        // If the change was missed this would deadlock
+        assert_eq!(block_on(signal.wait_for(10)), Some(10));
+
+        handle.join().unwrap();
+    });
+}
+
+
+#[test]
+fn test_local_broadcaster() {
+    model(|| {
+        let mutable = Mutable::new(0);
+        let broadcaster = LocalBroadcaster::new(mutable.signal());
+        let signal = broadcaster.signal();
+
+        // The LocalBroadcaster is !Send, but the Mutable can still be changed on another thread
+        let handle = thread::spawn({
+            let mutable = mutable.clone();
+            move || mutable.set(1)
+        });
+
+        assert_eq!(block_on(signal.wait_for(1)), Some(1));
+
+        handle.join().unwrap();
+    });
+}
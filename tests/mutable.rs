@@ -1,5 +1,12 @@
-use std::task::Poll;
-use futures_signals::signal::Mutable;
+use std::sync::Arc;
+#[cfg(feature = "std")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Poll, Context};
+use futures_signals::project;
+#[cfg(feature = "std")]
+use futures_signals::map_ref;
+use futures_signals::signal::{SignalExt, Mutable, batch};
+use futures_util::task::{waker, ArcWake};
 
 mod util;
 
@@ -44,3 +51,516 @@ fn test_lock_mut() {
         ]);
     }
 }
+
+
+#[test]
+fn test_drop_signal() {
+    let m = Mutable::new(1);
+
+    let mut s1 = m.signal();
+    let s2 = m.signal();
+    let mut s3 = m.signal();
+
+    util::with_noop_context(|cx| {
+        assert_eq!(s1.poll_change_unpin(cx), Poll::Ready(Some(1)));
+        assert_eq!(s3.poll_change_unpin(cx), Poll::Ready(Some(1)));
+
+        drop(s2);
+
+        m.set(2);
+        assert_eq!(s1.poll_change_unpin(cx), Poll::Ready(Some(2)));
+        assert_eq!(s3.poll_change_unpin(cx), Poll::Ready(Some(2)));
+
+        drop(s1);
+        let mut s4 = m.signal();
+
+        m.set(3);
+        assert_eq!(s3.poll_change_unpin(cx), Poll::Ready(Some(3)));
+        assert_eq!(s4.poll_change_unpin(cx), Poll::Ready(Some(3)));
+
+        let read_only = m.read_only();
+        drop(m);
+
+        let mut s5 = read_only.signal();
+        assert_eq!(s3.poll_change_unpin(cx), Poll::Ready(None));
+        assert_eq!(s5.poll_change_unpin(cx), Poll::Ready(Some(3)));
+        assert_eq!(s5.poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[test]
+fn test_receiver_count() {
+    let m = Mutable::new(1);
+    assert_eq!(m.receiver_count(), 0);
+    assert!(!m.has_receivers());
+
+    let s1 = m.signal();
+    let s2 = m.signal_ref(|x| *x + 1);
+    assert_eq!(m.receiver_count(), 2);
+    assert!(m.has_receivers());
+
+    drop(s1);
+    assert_eq!(m.receiver_count(), 1);
+
+    drop(s2);
+    assert_eq!(m.receiver_count(), 0);
+    assert!(!m.read_only().has_receivers());
+}
+
+
+#[test]
+fn test_signal_ref() {
+    // Not Clone
+    struct Large(Vec<u32>);
+
+    let m = Mutable::new(Large(vec![1, 2, 3]));
+
+    let mut s = m.signal_ref(|value| value.0.len());
+
+    util::with_noop_context(|cx| {
+        assert_eq!(s.poll_change_unpin(cx), Poll::Ready(Some(3)));
+        assert_eq!(s.poll_change_unpin(cx), Poll::Pending);
+
+        m.lock_mut().0.push(4);
+        assert_eq!(s.poll_change_unpin(cx), Poll::Ready(Some(4)));
+
+        drop(m);
+        assert_eq!(s.poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+// A panic while holding the lock shouldn't break the Mutable for everybody else
+#[cfg(not(feature = "poison-panic"))]
+#[test]
+fn test_poisoned() {
+    let m = Mutable::new(1);
+    let mut s = m.signal();
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let _lock = m.lock_mut();
+        panic!("oops");
+    }));
+
+    assert!(result.is_err());
+
+    m.set(2);
+    assert_eq!(m.get(), 2);
+
+    util::with_noop_context(|cx| {
+        assert_eq!(s.poll_change_unpin(cx), Poll::Ready(Some(2)));
+    });
+}
+
+
+#[test]
+fn test_set_if() {
+    let m = Mutable::new(5);
+    let mut s = m.signal();
+
+    util::with_noop_context(|cx| {
+        assert_eq!(s.poll_change_unpin(cx), Poll::Ready(Some(5)));
+
+        m.set_if(3, |old, new| new > old);
+        assert_eq!(m.get(), 5);
+        assert_eq!(s.poll_change_unpin(cx), Poll::Pending);
+
+        m.set_if(10, |old, new| new > old);
+        assert_eq!(m.get(), 10);
+        assert_eq!(s.poll_change_unpin(cx), Poll::Ready(Some(10)));
+    });
+}
+
+
+#[test]
+fn test_try_set_if() {
+    let m = Mutable::new(5);
+    let mut s = m.signal();
+
+    util::with_noop_context(|cx| {
+        assert_eq!(s.poll_change_unpin(cx), Poll::Ready(Some(5)));
+
+        assert!(!m.try_set_if(3, |old, new| new > old));
+        assert_eq!(m.get(), 5);
+        assert_eq!(s.poll_change_unpin(cx), Poll::Pending);
+
+        assert!(m.try_set_if(10, |old, new| new > old));
+        assert_eq!(m.get(), 10);
+        assert_eq!(s.poll_change_unpin(cx), Poll::Ready(Some(10)));
+    });
+}
+
+
+#[test]
+fn test_compare_and_set() {
+    let m = Mutable::new(5);
+    let mut s = m.signal();
+
+    util::with_noop_context(|cx| {
+        assert_eq!(s.poll_change_unpin(cx), Poll::Ready(Some(5)));
+
+        assert_eq!(m.compare_and_set(&4, 10), Err(5));
+        assert_eq!(m.get(), 5);
+        assert_eq!(s.poll_change_unpin(cx), Poll::Pending);
+
+        assert_eq!(m.compare_and_set(&5, 10), Ok(5));
+        assert_eq!(m.get(), 10);
+        assert_eq!(s.poll_change_unpin(cx), Poll::Ready(Some(10)));
+    });
+}
+
+
+#[test]
+fn test_lens() {
+    #[derive(Debug, Clone, PartialEq)]
+    struct State {
+        name: String,
+        age: u32,
+    }
+
+    let state = Mutable::new(State { name: "foo".to_string(), age: 5 });
+
+    let name = state.lens(|state| state.name.clone(), |state, name| state.name = name);
+    let age = state.lens(|state| state.age, |state, age| state.age = age);
+
+    let mut parent = state.signal_cloned();
+    let mut s = age.signal();
+
+    util::with_noop_context(|cx| {
+        assert_eq!(parent.poll_change_unpin(cx), Poll::Ready(Some(State { name: "foo".to_string(), age: 5 })));
+        assert_eq!(s.poll_change_unpin(cx), Poll::Ready(Some(5)));
+
+        age.set(10);
+        assert_eq!(age.get(), 10);
+        assert_eq!(parent.poll_change_unpin(cx), Poll::Ready(Some(State { name: "foo".to_string(), age: 10 })));
+        assert_eq!(s.poll_change_unpin(cx), Poll::Ready(Some(10)));
+
+        name.set("bar".to_string());
+        assert_eq!(name.get(), "bar");
+        assert_eq!(parent.poll_change_unpin(cx), Poll::Ready(Some(State { name: "bar".to_string(), age: 10 })));
+        assert_eq!(s.poll_change_unpin(cx), Poll::Ready(Some(10)));
+
+        // The lenses keep the Mutable alive
+        drop(state);
+        assert_eq!(s.poll_change_unpin(cx), Poll::Pending);
+
+        drop(name);
+        drop(age);
+        assert_eq!(s.poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[test]
+fn test_project() {
+    #[derive(Debug, Clone, PartialEq)]
+    struct Point(u32, u32);
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct State {
+        point: Point,
+    }
+
+    let state = Mutable::new(State { point: Point(1, 2) });
+
+    let x = project!(state => .point.0);
+    let point = project!(state => .point);
+
+    let mut s = x.signal();
+
+    util::with_noop_context(|cx| {
+        assert_eq!(s.poll_change_unpin(cx), Poll::Ready(Some(1)));
+
+        x.set(5);
+        assert_eq!(point.get(), Point(5, 2));
+        assert_eq!(s.poll_change_unpin(cx), Poll::Ready(Some(5)));
+
+        point.set(Point(10, 20));
+        assert_eq!(state.get_cloned(), State { point: Point(10, 20) });
+        assert_eq!(s.poll_change_unpin(cx), Poll::Ready(Some(10)));
+    });
+}
+
+
+#[cfg(feature = "std")]
+struct CountWaker(AtomicUsize);
+
+#[cfg(feature = "std")]
+impl ArcWake for CountWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+
+#[test]
+fn test_transaction() {
+    let m = Mutable::new(1);
+
+    let polls = util::get_signal_polls(m.signal(), move || {
+        let old = m.transaction(|value| {
+            let old = *value;
+            *value += 1;
+            *value *= 10;
+            old
+        });
+
+        assert_eq!(old, 1);
+    });
+
+    assert_eq!(polls, vec![
+        Poll::Ready(Some(1)),
+        Poll::Pending,
+        Poll::Ready(Some(20)),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[cfg(feature = "std")]
+#[test]
+fn test_batch() {
+    let a = Mutable::new(1);
+    let b = Mutable::new(2);
+
+    let count = Arc::new(CountWaker(AtomicUsize::new(0)));
+    let waker = waker(count.clone());
+    let cx = &mut Context::from_waker(&waker);
+
+    let mut signal = map_ref! {
+        let a = a.signal(),
+        let b = b.signal() => *a + *b
+    };
+
+    assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(3)));
+    assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+    let output = batch(|| {
+        a.set(10);
+
+        batch(|| {
+            b.set(20);
+        });
+
+        assert_eq!(count.0.load(Ordering::SeqCst), 0);
+
+        5
+    });
+
+    assert_eq!(output, 5);
+    assert_eq!(count.0.load(Ordering::SeqCst), 1);
+    assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(30)));
+
+    // Outside of a batch it wakes immediately
+    assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+    a.set(100);
+    assert_eq!(count.0.load(Ordering::SeqCst), 2);
+}
+
+
+struct OrderWaker {
+    id: u32,
+    order: Arc<std::sync::Mutex<Vec<u32>>>,
+}
+
+impl ArcWake for OrderWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.order.lock().unwrap().push(arc_self.id);
+    }
+}
+
+
+#[test]
+fn test_notify_order() {
+    let m = Mutable::new(1);
+    let order = Arc::new(std::sync::Mutex::new(vec![]));
+
+    let mut signals = vec![
+        (1, m.signal()),
+        (2, m.signal_with_priority(-1)),
+        (3, m.signal()),
+        (4, m.signal_with_priority(5)),
+    ];
+
+    // Dropping a signal frees up its key, which must not change the order
+    drop(signals.remove(0));
+    signals.push((5, m.signal()));
+    signals.push((6, m.signal()));
+
+    for (id, signal) in signals.iter_mut() {
+        let waker = waker(Arc::new(OrderWaker { id: *id, order: order.clone() }));
+        let cx = &mut Context::from_waker(&waker);
+
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(1)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+    }
+
+    m.set(2);
+
+    assert_eq!(*order.lock().unwrap(), vec![4, 3, 5, 6, 2]);
+}
+
+
+#[cfg(feature = "std")]
+#[test]
+fn test_batch_order() {
+    let a = Mutable::new(1);
+    let b = Mutable::new(2);
+    let order = Arc::new(std::sync::Mutex::new(vec![]));
+
+    let mut render = map_ref! {
+        let a = a.signal(),
+        let b = b.signal() => *a + *b
+    };
+
+    let mut validate = b.signal_with_priority(1);
+    let mut log = a.signal();
+
+    let poll = |id, signal: &mut dyn FnMut(&mut Context) -> Poll<Option<u32>>| {
+        let waker = waker(Arc::new(OrderWaker { id, order: order.clone() }));
+        let cx = &mut Context::from_waker(&waker);
+
+        assert!(signal(cx).is_ready());
+        assert_eq!(signal(cx), Poll::Pending);
+    };
+
+    poll(1, &mut |cx| render.poll_change_unpin(cx));
+    poll(2, &mut |cx| validate.poll_change_unpin(cx));
+    poll(3, &mut |cx| log.poll_change_unpin(cx));
+
+    batch(|| {
+        a.set(10);
+        b.set(20);
+        assert_eq!(*order.lock().unwrap(), Vec::<u32>::new());
+    });
+
+    // The render task is only woken up once, even though both of its inputs changed
+    assert_eq!(*order.lock().unwrap(), vec![2, 1, 3]);
+}
+
+
+#[test]
+fn test_signal_arc() {
+    let m = Mutable::new(vec![1, 2, 3]);
+
+    let mut s1 = m.signal_arc();
+    let mut s2 = m.signal_arc();
+
+    util::with_noop_context(|cx| {
+        let a = match s1.poll_change_unpin(cx) {
+            Poll::Ready(Some(a)) => a,
+            _ => panic!(),
+        };
+
+        let b = match s2.poll_change_unpin(cx) {
+            Poll::Ready(Some(b)) => b,
+            _ => panic!(),
+        };
+
+        // The value is only cloned once
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(Arc::ptr_eq(&a, &m.get_arc()));
+        assert_eq!(*a, vec![1, 2, 3]);
+
+        m.lock_mut().push(4);
+
+        let c = match s1.poll_change_unpin(cx) {
+            Poll::Ready(Some(c)) => c,
+            _ => panic!(),
+        };
+
+        assert!(!Arc::ptr_eq(&a, &c));
+        assert_eq!(*c, vec![1, 2, 3, 4]);
+        assert_eq!(*a, vec![1, 2, 3]);
+        assert_eq!(s1.poll_change_unpin(cx), Poll::Pending);
+    });
+}
+
+
+#[test]
+fn test_arc_helpers() {
+    let m = Mutable::new(Arc::new(vec![1, 2, 3]));
+
+    let mut len = m.signal_deref(|value| value.len());
+
+    util::with_noop_context(|cx| {
+        assert_eq!(len.poll_change_unpin(cx), Poll::Ready(Some(3)));
+        assert_eq!(len.poll_change_unpin(cx), Poll::Pending);
+
+        let old = m.get_cloned();
+
+        // The old Arc is shared, so it is cloned
+        m.make_mut(|value| value.push(4));
+        assert_eq!(*old, vec![1, 2, 3]);
+        assert_eq!(*m.get_cloned(), vec![1, 2, 3, 4]);
+        assert_eq!(len.poll_change_unpin(cx), Poll::Ready(Some(4)));
+
+        m.set_arc(vec![]);
+        assert_eq!(len.poll_change_unpin(cx), Poll::Ready(Some(0)));
+        assert_eq!(len.poll_change_unpin(cx), Poll::Pending);
+    });
+}
+
+
+#[test]
+fn test_drop_signal_while_locked() {
+    let m = Mutable::new(1);
+
+    let mut s1 = m.signal();
+    let s2 = m.signal();
+    assert_eq!(m.receiver_count(), 2);
+
+    {
+        let mut lock = m.lock_mut();
+        *lock = 2;
+        // Dropping a signal must not take the value lock
+        drop(s2);
+    }
+
+    assert_eq!(m.receiver_count(), 1);
+
+    util::with_noop_context(|cx| {
+        assert_eq!(s1.poll_change_unpin(cx), Poll::Ready(Some(2)));
+        assert_eq!(s1.poll_change_unpin(cx), Poll::Pending);
+    });
+}
+
+
+#[test]
+fn test_drop_during_thread_local_destruction() {
+    use std::cell::RefCell;
+    use futures_signals::signal::MutableSignal;
+
+    struct Holder(Option<(MutableSignal<u32>, Mutable<u32>)>);
+
+    impl Drop for Holder {
+        fn drop(&mut self) {
+            if let Some((_signal, m)) = self.0.take() {
+                // This notifies the signal, which must not touch the destroyed batch thread local
+                m.set(10);
+            }
+        }
+    }
+
+    thread_local! {
+        static HOLDER: RefCell<Holder> = const { RefCell::new(Holder(None)) };
+    }
+
+    std::thread::spawn(|| {
+        let m = Mutable::new(5);
+        let mut signal = m.signal();
+
+        util::with_noop_context(|cx| {
+            assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(5)));
+            assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+        });
+
+        HOLDER.with(|holder| {
+            holder.borrow_mut().0 = Some((signal, m));
+        });
+
+        // Thread locals are destroyed in reverse order, so the batch is destroyed before the holder
+        batch(|| {});
+    }).join().unwrap();
+}
@@ -0,0 +1,131 @@
+use std::task::Poll;
+use futures_signals::signal::{SignalExt, MutableAtomic};
+
+mod util;
+
+
+#[test]
+fn test_sync() {
+    let _: Box<dyn Send + Sync> = Box::new(MutableAtomic::new(1u32));
+    let _: Box<dyn Send + Sync> = Box::new(MutableAtomic::new(true).signal());
+}
+
+
+#[test]
+fn test_get_set() {
+    let m = MutableAtomic::new(1u64);
+    assert_eq!(m.get(), 1);
+
+    m.set(5);
+    assert_eq!(m.get(), 5);
+
+    assert_eq!(m.replace(10), 5);
+    assert_eq!(m.get(), 10);
+
+    let b = MutableAtomic::new(false);
+    b.set(true);
+    assert!(b.get());
+}
+
+
+#[test]
+fn test_signal() {
+    let m = MutableAtomic::new(1u32);
+
+    let polls = util::get_signal_polls(m.signal(), move || {
+        m.set(2);
+        m.set(3);
+    });
+
+    assert_eq!(polls, vec![
+        Poll::Ready(Some(1)),
+        Poll::Pending,
+        Poll::Ready(Some(3)),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_set_neq() {
+    let m = MutableAtomic::new(1usize);
+    let mut signal = m.signal();
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(1)));
+
+        m.set_neq(1);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        m.set_neq(2);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(2)));
+
+        let cloned = m.clone();
+        drop(m);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        drop(cloned);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[test]
+fn test_reuse_signal() {
+    let m = MutableAtomic::new(1u32);
+
+    let mut s1 = m.signal();
+
+    {
+        let s2 = m.signal();
+        drop(s2);
+    }
+
+    // This reuses the dropped signal's receiver
+    let mut s3 = m.signal();
+
+    util::with_noop_context(|cx| {
+        assert_eq!(s1.poll_change_unpin(cx), Poll::Ready(Some(1)));
+        assert_eq!(s3.poll_change_unpin(cx), Poll::Ready(Some(1)));
+        assert_eq!(s1.poll_change_unpin(cx), Poll::Pending);
+        assert_eq!(s3.poll_change_unpin(cx), Poll::Pending);
+
+        m.set(2);
+        assert_eq!(s1.poll_change_unpin(cx), Poll::Ready(Some(2)));
+        assert_eq!(s3.poll_change_unpin(cx), Poll::Ready(Some(2)));
+        assert_eq!(s3.poll_change_unpin(cx), Poll::Pending);
+
+        drop(m);
+        assert_eq!(s1.poll_change_unpin(cx), Poll::Ready(None));
+        assert_eq!(s3.poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[test]
+fn test_threads() {
+    let m = MutableAtomic::new(0u32);
+
+    let handles: Vec<_> = (0..4).map(|_| {
+        let signal = m.signal();
+
+        std::thread::spawn(move || {
+            futures_executor::block_on(signal.wait_for(100))
+        })
+    }).collect();
+
+    let setter = {
+        let m = m.clone();
+        std::thread::spawn(move || {
+            for i in 1..=100 {
+                m.set(i);
+            }
+        })
+    };
+
+    setter.join().unwrap();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), Some(100));
+    }
+}
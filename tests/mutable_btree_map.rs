@@ -0,0 +1,185 @@
+#![cfg(feature = "std")]
+
+use std::task::Poll;
+use std::collections::BTreeMap;
+use futures_signals::signal_map::{MapDiff, MutableBTreeMap, MutableBTreeMapLockMut};
+use futures_signals::signal::Mutable;
+use futures_signals::signal_vec::{VecDiff, MutableVec};
+
+mod util;
+
+
+fn is_eq<F>(input: BTreeMap<u32, u32>, output: BTreeMap<u32, u32>, f: F, polls: Vec<Poll<Option<MapDiff<u32, u32>>>>)
+    where F: FnOnce(&mut MutableBTreeMapLockMut<u32, u32>) {
+
+    let map = MutableBTreeMap::with_values(input);
+
+    let mut end = None;
+
+    assert_eq!(util::get_signal_map_polls(map.signal_map(), || {
+        {
+            let mut map = map.lock_mut();
+            f(&mut map);
+            end = Some((*map).clone());
+        }
+        drop(map);
+    }), polls);
+
+    assert_eq!(end.unwrap(), output);
+}
+
+fn btree_map(entries: Vec<(u32, u32)>) -> BTreeMap<u32, u32> {
+    entries.into_iter().collect()
+}
+
+
+#[test]
+fn test_sync() {
+    let _: Box<dyn Send + Sync> = Box::new(MutableBTreeMap::<u32, u32>::new());
+    let _: Box<dyn Send + Sync> = Box::new(MutableBTreeMap::<u32, u32>::new().signal_map());
+    let _: Box<dyn Send + Sync> = Box::new(MutableBTreeMap::<u32, u32>::new().signal_map_cloned());
+}
+
+
+#[test]
+fn test_insert() {
+    is_eq(btree_map(vec![]), btree_map(vec![(1, 10), (2, 20)]), |map| {
+        assert_eq!(map.insert(2, 20), None);
+        assert_eq!(map.insert(1, 10), None);
+    }, vec![
+        Poll::Pending,
+        Poll::Ready(Some(MapDiff::Insert { key: 2, value: 20 })),
+        Poll::Ready(Some(MapDiff::Insert { key: 1, value: 10 })),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_update() {
+    is_eq(btree_map(vec![(1, 10)]), btree_map(vec![(1, 15)]), |map| {
+        assert_eq!(map.insert(1, 15), Some(10));
+    }, vec![
+        Poll::Ready(Some(MapDiff::Replace { entries: vec![(1, 10)] })),
+        Poll::Pending,
+        Poll::Ready(Some(MapDiff::Update { key: 1, value: 15 })),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_remove() {
+    is_eq(btree_map(vec![(1, 10), (2, 20)]), btree_map(vec![(2, 20)]), |map| {
+        assert_eq!(map.remove(&3), None);
+        assert_eq!(map.remove(&1), Some(10));
+    }, vec![
+        Poll::Ready(Some(MapDiff::Replace { entries: vec![(1, 10), (2, 20)] })),
+        Poll::Pending,
+        Poll::Ready(Some(MapDiff::Remove { key: 1 })),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_clear() {
+    is_eq(btree_map(vec![]), btree_map(vec![]), |map| map.clear(), vec![
+        Poll::Pending,
+        Poll::Ready(None),
+    ]);
+
+    is_eq(btree_map(vec![(1, 10)]), btree_map(vec![]), |map| map.clear(), vec![
+        Poll::Ready(Some(MapDiff::Replace { entries: vec![(1, 10)] })),
+        Poll::Pending,
+        Poll::Ready(Some(MapDiff::Clear {})),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_replace() {
+    is_eq(btree_map(vec![(1, 10)]), btree_map(vec![(3, 30), (2, 20)]), |map| {
+        map.replace(btree_map(vec![(3, 30), (2, 20)]));
+    }, vec![
+        Poll::Ready(Some(MapDiff::Replace { entries: vec![(1, 10)] })),
+        Poll::Pending,
+        Poll::Ready(Some(MapDiff::Replace { entries: vec![(2, 20), (3, 30)] })),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_signal_vec_keys() {
+    let map = MutableBTreeMap::with_values(btree_map(vec![(2, 20), (4, 40)]));
+
+    assert_eq!(util::get_signal_vec_polls(map.signal_vec_keys(), || {
+        {
+            let mut lock = map.lock_mut();
+            lock.insert(3, 30);
+            lock.insert(1, 10);
+            lock.insert(5, 50);
+            lock.insert(3, 35);
+            lock.remove(&4);
+            lock.clear();
+        }
+        drop(map);
+    }), vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![2, 4] })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::InsertAt { index: 1, value: 3 })),
+        Poll::Ready(Some(VecDiff::InsertAt { index: 0, value: 1 })),
+        Poll::Ready(Some(VecDiff::InsertAt { index: 4, value: 5 })),
+        Poll::Ready(Some(VecDiff::RemoveAt { index: 3 })),
+        Poll::Ready(Some(VecDiff::Clear {})),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_signal_vec_entries() {
+    let map = MutableBTreeMap::with_values(btree_map(vec![(2, 20), (4, 40)]));
+
+    assert_eq!(util::get_signal_vec_polls(map.signal_vec_entries(), || {
+        {
+            let mut lock = map.lock_mut();
+            lock.insert(3, 30);
+            lock.insert(1, 10);
+            lock.insert(5, 50);
+            lock.insert(3, 35);
+            lock.remove(&4);
+            lock.clear();
+        }
+        drop(map);
+    }), vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![(2, 20), (4, 40)] })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::InsertAt { index: 1, value: (3, 30) })),
+        Poll::Ready(Some(VecDiff::InsertAt { index: 0, value: (1, 10) })),
+        Poll::Ready(Some(VecDiff::InsertAt { index: 4, value: (5, 50) })),
+        Poll::Ready(Some(VecDiff::UpdateAt { index: 2, value: (3, 35) })),
+        Poll::Ready(Some(VecDiff::RemoveAt { index: 3 })),
+        Poll::Ready(Some(VecDiff::Clear {})),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_serde() {
+    let map = MutableBTreeMap::with_values(btree_map(vec![(1, 10), (2, 20)]));
+
+    let json = serde_json::to_string(&map).unwrap();
+    assert_eq!(json, r#"{"1":10,"2":20}"#);
+
+    let map: MutableBTreeMap<u32, u32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(*map.lock_ref(), btree_map(vec![(1, 10), (2, 20)]));
+
+    // Nested reactive containers round-trip as plain JSON
+    let nested: Mutable<MutableBTreeMap<String, MutableVec<u32>>> = serde_json::from_str(r#"{"foo":[1,2],"bar":[]}"#).unwrap();
+    assert_eq!(nested.lock_ref().lock_ref().get("foo").unwrap().lock_ref().as_slice(), &[1, 2]);
+    assert_eq!(serde_json::to_string(&nested).unwrap(), r#"{"bar":[],"foo":[1,2]}"#);
+}
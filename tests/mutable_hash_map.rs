@@ -0,0 +1,121 @@
+#![cfg(feature = "std")]
+
+use std::task::Poll;
+use std::collections::HashMap;
+use futures_signals::signal_map::{MapDiff, MutableHashMap, MutableHashMapLockMut};
+
+mod util;
+
+
+fn is_eq<F>(input: HashMap<u32, u32>, output: HashMap<u32, u32>, f: F, polls: Vec<Poll<Option<MapDiff<u32, u32>>>>)
+    where F: FnOnce(&mut MutableHashMapLockMut<u32, u32>) {
+
+    let map = MutableHashMap::with_values(input);
+
+    let mut end = None;
+
+    assert_eq!(util::get_signal_map_polls(map.signal_map(), || {
+        {
+            let mut map = map.lock_mut();
+            f(&mut map);
+            end = Some((*map).clone());
+        }
+        drop(map);
+    }), polls);
+
+    assert_eq!(end.unwrap(), output);
+}
+
+fn hash_map(entries: Vec<(u32, u32)>) -> HashMap<u32, u32> {
+    entries.into_iter().collect()
+}
+
+
+#[test]
+fn test_sync() {
+    let _: Box<dyn Send + Sync> = Box::new(MutableHashMap::<u32, u32>::new());
+    let _: Box<dyn Send + Sync> = Box::new(MutableHashMap::<u32, u32>::new().signal_map());
+    let _: Box<dyn Send + Sync> = Box::new(MutableHashMap::<u32, u32>::new().signal_map_cloned());
+}
+
+
+#[test]
+fn test_insert() {
+    is_eq(hash_map(vec![]), hash_map(vec![(1, 10), (2, 20)]), |map| {
+        assert_eq!(map.insert(2, 20), None);
+        assert_eq!(map.insert(1, 10), None);
+    }, vec![
+        Poll::Pending,
+        Poll::Ready(Some(MapDiff::Insert { key: 2, value: 20 })),
+        Poll::Ready(Some(MapDiff::Insert { key: 1, value: 10 })),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_update() {
+    is_eq(hash_map(vec![(1, 10)]), hash_map(vec![(1, 15)]), |map| {
+        assert_eq!(map.insert(1, 15), Some(10));
+    }, vec![
+        Poll::Ready(Some(MapDiff::Replace { entries: vec![(1, 10)] })),
+        Poll::Pending,
+        Poll::Ready(Some(MapDiff::Update { key: 1, value: 15 })),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_remove() {
+    is_eq(hash_map(vec![(1, 10)]), hash_map(vec![]), |map| {
+        assert_eq!(map.remove(&3), None);
+        assert_eq!(map.remove(&1), Some(10));
+    }, vec![
+        Poll::Ready(Some(MapDiff::Replace { entries: vec![(1, 10)] })),
+        Poll::Pending,
+        Poll::Ready(Some(MapDiff::Remove { key: 1 })),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_clear() {
+    is_eq(hash_map(vec![]), hash_map(vec![]), |map| map.clear(), vec![
+        Poll::Pending,
+        Poll::Ready(None),
+    ]);
+
+    is_eq(hash_map(vec![(1, 10)]), hash_map(vec![]), |map| map.clear(), vec![
+        Poll::Ready(Some(MapDiff::Replace { entries: vec![(1, 10)] })),
+        Poll::Pending,
+        Poll::Ready(Some(MapDiff::Clear {})),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_replace() {
+    is_eq(hash_map(vec![(1, 10)]), hash_map(vec![(2, 20)]), |map| {
+        map.replace(hash_map(vec![(2, 20)]));
+    }, vec![
+        Poll::Ready(Some(MapDiff::Replace { entries: vec![(1, 10)] })),
+        Poll::Pending,
+        Poll::Ready(Some(MapDiff::Replace { entries: vec![(2, 20)] })),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_serde() {
+    let map = MutableHashMap::with_values(hash_map(vec![(1, 10)]));
+
+    let json = serde_json::to_string(&map).unwrap();
+    assert_eq!(json, r#"{"1":10}"#);
+
+    let map: MutableHashMap<u32, u32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(*map.lock_ref(), hash_map(vec![(1, 10)]));
+}
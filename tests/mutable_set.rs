@@ -0,0 +1,110 @@
+#![cfg(feature = "std")]
+
+use std::task::Poll;
+use std::collections::BTreeSet;
+use futures_signals::signal_set::{SetDiff, MutableSet, MutableSetLockMut};
+
+mod util;
+
+
+fn is_eq<F>(input: BTreeSet<u32>, output: BTreeSet<u32>, f: F, polls: Vec<Poll<Option<SetDiff<u32>>>>)
+    where F: FnOnce(&mut MutableSetLockMut<u32>) {
+
+    let set = MutableSet::with_values(input);
+
+    let mut end = None;
+
+    assert_eq!(util::get_signal_set_polls(set.signal_set(), || {
+        {
+            let mut set = set.lock_mut();
+            f(&mut set);
+            end = Some((*set).clone());
+        }
+        drop(set);
+    }), polls);
+
+    assert_eq!(end.unwrap(), output);
+}
+
+fn btree_set(values: Vec<u32>) -> BTreeSet<u32> {
+    values.into_iter().collect()
+}
+
+
+#[test]
+fn test_sync() {
+    let _: Box<dyn Send + Sync> = Box::new(MutableSet::<u32>::new());
+    let _: Box<dyn Send + Sync> = Box::new(MutableSet::<u32>::new().signal_set());
+    let _: Box<dyn Send + Sync> = Box::new(MutableSet::<u32>::new().signal_set_cloned());
+}
+
+
+#[test]
+fn test_insert() {
+    is_eq(btree_set(vec![1]), btree_set(vec![1, 2, 3]), |set| {
+        assert!(set.insert(3));
+        assert!(!set.insert(1));
+        assert!(set.insert(2));
+    }, vec![
+        Poll::Ready(Some(SetDiff::Replace { values: vec![1] })),
+        Poll::Pending,
+        Poll::Ready(Some(SetDiff::Insert { value: 3 })),
+        Poll::Ready(Some(SetDiff::Insert { value: 2 })),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_remove() {
+    is_eq(btree_set(vec![1, 2]), btree_set(vec![2]), |set| {
+        assert!(!set.remove(&3));
+        assert!(set.remove(&1));
+    }, vec![
+        Poll::Ready(Some(SetDiff::Replace { values: vec![1, 2] })),
+        Poll::Pending,
+        Poll::Ready(Some(SetDiff::Remove { value: 1 })),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_clear() {
+    is_eq(btree_set(vec![]), btree_set(vec![]), |set| set.clear(), vec![
+        Poll::Pending,
+        Poll::Ready(None),
+    ]);
+
+    is_eq(btree_set(vec![1]), btree_set(vec![]), |set| set.clear(), vec![
+        Poll::Ready(Some(SetDiff::Replace { values: vec![1] })),
+        Poll::Pending,
+        Poll::Ready(Some(SetDiff::Clear {})),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_replace() {
+    is_eq(btree_set(vec![1]), btree_set(vec![2, 3]), |set| {
+        set.replace(btree_set(vec![3, 2]));
+    }, vec![
+        Poll::Ready(Some(SetDiff::Replace { values: vec![1] })),
+        Poll::Pending,
+        Poll::Ready(Some(SetDiff::Replace { values: vec![2, 3] })),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_serde() {
+    let set = MutableSet::with_values(btree_set(vec![2, 1]));
+
+    let json = serde_json::to_string(&set).unwrap();
+    assert_eq!(json, "[1,2]");
+
+    let set: MutableSet<u32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(*set.lock_ref(), btree_set(vec![1, 2]));
+}
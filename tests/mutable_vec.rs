@@ -1,3 +1,5 @@
+#![cfg(feature = "std")]
+
 use std::task::Poll;
 use std::cmp::{PartialOrd, Ordering};
 use futures_signals::signal_vec::{VecDiff, MutableVec, MutableVecLockMut};
@@ -41,6 +43,128 @@ fn test_push() {
 }
 
 
+#[test]
+fn test_insert() {
+    is_eq(vec![5, 10], vec![15, 5, 10, 20], |v| {
+        v.insert(0, 15);
+        v.insert(3, 20);
+    }, vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![5, 10] })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::InsertAt { index: 0, value: 15 })),
+        Poll::Ready(Some(VecDiff::Push { value: 20 })),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_set() {
+    is_eq(vec![5, 10], vec![5, 20], |v| v.set(1, 20), vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![5, 10] })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::UpdateAt { index: 1, value: 20 })),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_remove() {
+    is_eq(vec![5, 10, 15], vec![10], |v| {
+        assert_eq!(v.remove(0), 5);
+        assert_eq!(v.remove(1), 15);
+    }, vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![5, 10, 15] })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::RemoveAt { index: 0 })),
+        Poll::Ready(Some(VecDiff::Pop {})),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_pop() {
+    is_eq(vec![5], vec![], |v| {
+        assert_eq!(v.pop(), Some(5));
+        assert_eq!(v.pop(), None);
+    }, vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![5] })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::Pop {})),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_clear() {
+    is_eq(vec![], vec![], |v| v.clear(), vec![
+        Poll::Pending,
+        Poll::Ready(None),
+    ]);
+
+    is_eq(vec![5, 10], vec![], |v| v.clear(), vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![5, 10] })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::Clear {})),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_swap_remove() {
+    is_eq(vec![5, 10, 15, 20], vec![5, 20, 15], |v| assert_eq!(v.swap_remove(1), 10), vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![5, 10, 15, 20] })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::RemoveAt { index: 1 })),
+        Poll::Ready(Some(VecDiff::Move { old_index: 2, new_index: 1 })),
+        Poll::Ready(None),
+    ]);
+
+    is_eq(vec![5, 10, 15], vec![5, 15], |v| assert_eq!(v.swap_remove(1), 10), vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![5, 10, 15] })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::RemoveAt { index: 1 })),
+        Poll::Ready(None),
+    ]);
+
+    is_eq(vec![5, 10, 15], vec![5, 10], |v| assert_eq!(v.swap_remove(2), 15), vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![5, 10, 15] })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::Pop {})),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_truncate() {
+    is_eq(vec![5, 10, 15], vec![5], |v| v.truncate(1), vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![5, 10, 15] })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::Pop {})),
+        Poll::Ready(Some(VecDiff::Pop {})),
+        Poll::Ready(None),
+    ]);
+
+    is_eq(vec![5, 10, 15], vec![5, 10, 15], |v| v.truncate(5), vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![5, 10, 15] })),
+        Poll::Pending,
+        Poll::Ready(None),
+    ]);
+
+    is_eq(vec![5, 10, 15], vec![], |v| v.truncate(0), vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![5, 10, 15] })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::Clear {})),
+        Poll::Ready(None),
+    ]);
+}
+
+
 #[test]
 fn test_move_from_to() {
     is_eq(vec![5, 10, 15], vec![5, 10, 15], |v| v.move_from_to(0, 0), vec![
@@ -203,3 +327,25 @@ fn test_ord() {
         assert_eq!(a.lock_ref().cmp(&b), Ordering::Equal);
     }
 }
+
+
+#[test]
+fn test_serde() {
+    let v = MutableVec::new_with_values(vec![5, 10, 15]);
+
+    let json = serde_json::to_string(&v).unwrap();
+    assert_eq!(json, "[5,10,15]");
+
+    let v: MutableVec<u32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(*v.lock_ref(), [5, 10, 15][..]);
+
+    assert_eq!(util::get_signal_vec_polls(v.signal_vec(), || {
+        v.lock_mut().push(20);
+        drop(v);
+    }), vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![5, 10, 15] })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::Push { value: 20 })),
+        Poll::Ready(None),
+    ]);
+}
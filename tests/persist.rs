@@ -0,0 +1,105 @@
+#![cfg(feature = "persist")]
+
+use std::pin::Pin;
+use std::rc::Rc;
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::time::Duration;
+use std::task::Poll;
+use futures_util::future::{poll_fn, FutureExt};
+use futures_signals::persist::{Storage, Persisted};
+use futures_signals::timer::Timer;
+
+mod util;
+
+
+#[derive(Default)]
+struct MemoryStorage {
+    bytes: RefCell<Option<Vec<u8>>>,
+    saves: Cell<usize>,
+}
+
+impl Storage for MemoryStorage {
+    fn load(&self) -> Option<Vec<u8>> {
+        self.bytes.borrow().clone()
+    }
+
+    fn save(&self, bytes: &[u8]) {
+        self.saves.set(self.saves.get() + 1);
+        *self.bytes.borrow_mut() = Some(bytes.to_vec());
+    }
+}
+
+
+#[derive(Clone, Default)]
+struct TestTimer {
+    time: Rc<Cell<f64>>,
+}
+
+impl Timer for TestTimer {
+    type Sleep = Pin<Box<dyn Future<Output = ()>>>;
+
+    fn now(&self) -> f64 {
+        self.time.get()
+    }
+
+    fn sleep(&self, duration: Duration) -> Self::Sleep {
+        let time = self.time.clone();
+        let end = self.now() + (duration.as_millis() as f64);
+
+        Box::pin(poll_fn(move |_| {
+            if time.get() >= end {
+                Poll::Ready(())
+
+            } else {
+                Poll::Pending
+            }
+        }))
+    }
+}
+
+
+#[test]
+fn test_persisted() {
+    let storage = Rc::new(MemoryStorage::default());
+    let timer = TestTimer::default();
+
+    let (persisted, future) = Persisted::new(storage.clone(), timer.clone(), Duration::from_millis(100), || 5u32);
+    let mut future = future.boxed_local();
+
+    assert_eq!(persisted.get(), 5);
+
+    util::with_noop_context(|cx| {
+        assert_eq!(future.poll_unpin(cx), Poll::Pending);
+        assert_eq!(storage.saves.get(), 0);
+
+        persisted.set(6);
+        assert_eq!(future.poll_unpin(cx), Poll::Pending);
+
+        timer.time.set(50.0);
+        persisted.set(7);
+        assert_eq!(future.poll_unpin(cx), Poll::Pending);
+
+        // The debounce restarted when it changed to 7
+        timer.time.set(120.0);
+        assert_eq!(future.poll_unpin(cx), Poll::Pending);
+        assert_eq!(storage.saves.get(), 0);
+
+        timer.time.set(150.0);
+        assert_eq!(future.poll_unpin(cx), Poll::Pending);
+        assert_eq!(storage.saves.get(), 1);
+        assert_eq!(storage.load(), Some(b"7".to_vec()));
+
+        // Unsaved changes are saved when it is dropped
+        persisted.set(8);
+        assert_eq!(future.poll_unpin(cx), Poll::Pending);
+        drop(persisted);
+        assert_eq!(future.poll_unpin(cx), Poll::Ready(()));
+        assert_eq!(storage.saves.get(), 2);
+        assert_eq!(storage.load(), Some(b"8".to_vec()));
+    });
+
+    // It loads the saved value
+    let (persisted, _future) = Persisted::new(storage, timer, Duration::from_millis(100), || 5u32);
+    assert_eq!(persisted.get(), 8);
+}
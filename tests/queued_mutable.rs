@@ -0,0 +1,103 @@
+use std::task::Poll;
+use futures_signals::signal::{QueuedMutable, SignalExt};
+#[cfg(feature = "std")]
+use std::sync::Arc;
+#[cfg(feature = "std")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "std")]
+use std::task::Context;
+#[cfg(feature = "std")]
+use futures_signals::signal::batch;
+#[cfg(feature = "std")]
+use futures_util::task::{waker, ArcWake};
+
+mod util;
+
+
+#[test]
+fn test_every_change() {
+    let mutable = QueuedMutable::new(1);
+
+    let mut signal = mutable.signal();
+
+    mutable.set(2);
+    mutable.set(3);
+
+    let mut late = mutable.signal();
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(1)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(2)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(3)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        assert_eq!(late.poll_change_unpin(cx), Poll::Ready(Some(3)));
+        assert_eq!(late.poll_change_unpin(cx), Poll::Pending);
+
+        mutable.set(4);
+        drop(mutable);
+
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(4)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(None));
+
+        assert_eq!(late.poll_change_unpin(cx), Poll::Ready(Some(4)));
+        assert_eq!(late.poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[test]
+fn test_capacity() {
+    let mutable = QueuedMutable::with_capacity("a".to_string(), 2);
+
+    let signal = mutable.signal_cloned();
+
+    mutable.set("b".to_string());
+    mutable.set("c".to_string());
+    drop(mutable);
+
+    util::assert_signal_eq(signal, vec![
+        Poll::Ready(Some("b".to_string())),
+        Poll::Ready(Some("c".to_string())),
+        Poll::Ready(None),
+    ]);
+}
+
+
+// `batch` only delays the wakeups with std
+#[cfg(feature = "std")]
+struct CountWaker(AtomicUsize);
+
+#[cfg(feature = "std")]
+impl ArcWake for CountWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+
+#[cfg(feature = "std")]
+#[test]
+fn test_batch() {
+    let mutable = QueuedMutable::new(1);
+    let mut signal = mutable.signal();
+
+    let count = Arc::new(CountWaker(AtomicUsize::new(0)));
+    let waker = waker(count.clone());
+    let cx = &mut Context::from_waker(&waker);
+
+    assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(1)));
+    assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+    batch(|| {
+        mutable.set(2);
+        mutable.set(3);
+        assert_eq!(count.0.load(Ordering::SeqCst), 0);
+    });
+
+    // It is only woken up once, but it still outputs every change
+    assert_eq!(count.0.load(Ordering::SeqCst), 1);
+    assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(2)));
+    assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(3)));
+    assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+}
@@ -0,0 +1,342 @@
+#![cfg(feature = "replication")]
+
+use std::io;
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::pin::Pin;
+use std::task::{Poll, Context};
+use futures_executor::block_on;
+use futures_util::future::ready;
+use futures_util::io::{AsyncRead, AsyncWrite, Cursor};
+use futures_util::stream::{self, Stream, StreamExt};
+use futures_signals::signal::{Mutable, SignalExt};
+use futures_signals::signal_vec::{MutableVec, VecDiff};
+use futures_signals::signal_map::{MutableBTreeMap, MapDiff};
+use futures_signals::replication::{signal_frames, signal_vec_frames, signal_map_frames, encode_frame, send_frames, receive_frames, send_frames_reconnect, receive_frames_reconnect, SignalReplica, SignalVecReplica, SignalMapReplica};
+
+mod util;
+
+
+// Returns all of the frames which are ready
+fn ready_bytes<S>(stream: &mut S) -> Vec<u8> where S: Stream<Item = Vec<u8>> + Unpin {
+    let mut bytes = vec![];
+
+    util::with_noop_context(|cx| {
+        while let Poll::Ready(Some(frame)) = stream.poll_next_unpin(cx) {
+            bytes.extend(frame);
+        }
+    });
+
+    bytes
+}
+
+
+#[test]
+fn test_signal() {
+    let mutable = Mutable::new("foo".to_string());
+    let mut frames = Box::pin(signal_frames(mutable.signal_cloned()));
+
+    let mut replica = SignalReplica::<String>::new();
+    assert_eq!(replica.read_only().get_cloned(), None);
+
+    // The bytes can be split anywhere
+    let bytes = ready_bytes(&mut frames);
+    replica.receive(&bytes[0..2]).unwrap();
+    assert_eq!(replica.read_only().get_cloned(), None);
+    replica.receive(&bytes[2..]).unwrap();
+    assert_eq!(replica.read_only().get_cloned(), Some("foo".to_string()));
+
+    mutable.set("bar".to_string());
+    replica.receive(&ready_bytes(&mut frames)).unwrap();
+    assert_eq!(replica.read_only().get_cloned(), Some("bar".to_string()));
+
+    let mut signal = replica.signal_cloned();
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(Some("bar".to_string()))));
+    });
+}
+
+
+#[test]
+fn test_signal_vec() {
+    let vec = MutableVec::new_with_values(vec![1, 2, 3]);
+    let mut frames = Box::pin(signal_vec_frames(vec.signal_vec()));
+
+    let mut replica = SignalVecReplica::<u32>::new();
+    replica.receive(&ready_bytes(&mut frames)).unwrap();
+    assert_eq!(*replica.lock_ref(), [1, 2, 3]);
+
+    {
+        let mut lock = vec.lock_mut();
+        lock.push(4);
+        lock.remove(0);
+        lock.move_from_to(0, 2);
+        lock.set(0, 5);
+    }
+
+    replica.receive(&ready_bytes(&mut frames)).unwrap();
+    assert_eq!(*replica.lock_ref(), *vec.lock_ref());
+}
+
+
+#[test]
+fn test_signal_map() {
+    let map = MutableBTreeMap::new();
+    map.lock_mut().insert_cloned("a".to_string(), 1);
+
+    let mut frames = Box::pin(signal_map_frames(map.signal_map_cloned()));
+
+    let mut replica = SignalMapReplica::<String, u32>::new();
+    replica.receive(&ready_bytes(&mut frames)).unwrap();
+    assert_eq!(replica.lock_ref().get("a"), Some(&1));
+
+    {
+        let mut lock = map.lock_mut();
+        lock.insert_cloned("b".to_string(), 2);
+        lock.remove(&"a".to_string());
+    }
+
+    replica.receive(&ready_bytes(&mut frames)).unwrap();
+    assert_eq!(*replica.lock_ref(), *map.lock_ref());
+}
+
+
+#[test]
+fn test_invalid_frame() {
+    let mut replica = SignalReplica::<u32>::new();
+
+    let mut bytes = encode_frame(&"not a number");
+    bytes.extend(encode_frame(&5));
+
+    assert!(replica.receive(&bytes).is_err());
+    assert_eq!(replica.read_only().get(), None);
+
+    // The rest of the frames are applied later
+    replica.receive(&[]).unwrap();
+    assert_eq!(replica.read_only().get(), Some(5));
+}
+
+
+#[test]
+fn test_invalid_index() {
+    let mut replica = SignalVecReplica::<u32>::new();
+    replica.receive(&encode_frame(&VecDiff::Replace { values: vec![1, 2] })).unwrap();
+
+    let invalid = vec![
+        VecDiff::InsertAt { index: 3, value: 5 },
+        VecDiff::UpdateAt { index: 2, value: 5 },
+        VecDiff::RemoveAt { index: 2 },
+        VecDiff::Move { old_index: 0, new_index: 2 },
+        VecDiff::Move { old_index: 2, new_index: 0 },
+    ];
+
+    for change in invalid {
+        assert!(replica.receive(&encode_frame(&change)).is_err());
+        assert_eq!(*replica.lock_ref(), [1, 2]);
+    }
+
+    replica.receive(&encode_frame(&VecDiff::<u32>::Clear {})).unwrap();
+    assert!(replica.receive(&encode_frame(&VecDiff::<u32>::Pop {})).is_err());
+
+    // The frames after the invalid frame are still applied
+    let mut bytes = encode_frame(&VecDiff::<u32>::RemoveAt { index: 0 });
+    bytes.extend(encode_frame(&VecDiff::InsertAt { index: 0, value: 5 }));
+
+    assert!(replica.receive(&bytes).is_err());
+    replica.receive(&[]).unwrap();
+    assert_eq!(*replica.lock_ref(), [5]);
+}
+
+
+#[test]
+fn test_max_frame_size() {
+    let mut replica = SignalReplica::<String>::with_max_frame_size(10);
+
+    let large = encode_frame(&"this is more than 10 bytes");
+
+    // It is rejected as soon as the length is received
+    assert!(replica.receive(&large[..6]).is_err());
+    assert_eq!(replica.read_only().get_cloned(), None);
+
+    // The rest of the large frame is discarded
+    let mut bytes = large[6..].to_vec();
+    bytes.extend(encode_frame(&"small"));
+
+    replica.receive(&bytes).unwrap();
+    assert_eq!(replica.read_only().get_cloned(), Some("small".to_string()));
+}
+
+
+// Returns some bytes and then fails, like a connection which was lost
+struct Broken(Vec<u8>);
+
+impl AsyncRead for Broken {
+    fn poll_read(mut self: Pin<&mut Self>, _cx: &mut Context, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        if self.0.is_empty() {
+            Poll::Ready(Err(io::ErrorKind::ConnectionReset.into()))
+
+        } else {
+            let len = self.0.len().min(buf.len());
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0.drain(..len);
+            Poll::Ready(Ok(len))
+        }
+    }
+}
+
+impl AsyncWrite for Broken {
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context, _buf: &[u8]) -> Poll<io::Result<usize>> {
+        Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+// Writes the bytes into a shared Vec
+struct Recorder(Rc<RefCell<Vec<u8>>>);
+
+impl AsyncWrite for Recorder {
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context, buf: &[u8]) -> Poll<io::Result<usize>> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+
+#[test]
+fn test_transport() {
+    let mut bytes = vec![];
+
+    block_on(send_frames(stream::iter(vec![encode_frame(&1), encode_frame(&2)]), &mut bytes)).unwrap();
+
+    let mut replica = SignalReplica::<u32>::new();
+    block_on(receive_frames(Cursor::new(bytes), &mut replica)).unwrap();
+    assert_eq!(replica.read_only().get(), Some(2));
+
+    // Invalid frames are an error
+    let result = block_on(receive_frames(Cursor::new(encode_frame(&"foo")), &mut replica));
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+
+#[test]
+fn test_send_reconnect() {
+    let mutable = Mutable::new(5);
+    let mut connections = 0;
+    let bytes = Rc::new(RefCell::new(vec![]));
+
+    block_on(send_frames_reconnect(
+        || {
+            connections += 1;
+
+            if connections == 1 {
+                ready(Ok(Box::new(Broken(vec![])) as Box<dyn AsyncWrite + Unpin>))
+
+            } else {
+                ready(Ok(Box::new(Recorder(bytes.clone())) as Box<dyn AsyncWrite + Unpin>))
+            }
+        },
+        // Only the snapshot is sent, so that the stream ends
+        || signal_frames(mutable.signal()).take(1),
+    )).unwrap();
+
+    assert_eq!(connections, 2);
+    assert_eq!(*bytes.borrow(), encode_frame(&5));
+
+    // Connection errors are returned
+    let result = block_on(send_frames_reconnect(
+        || ready(Err::<Broken, _>(io::ErrorKind::ConnectionRefused.into())),
+        || signal_frames(mutable.signal()),
+    ));
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::ConnectionRefused);
+}
+
+
+#[test]
+fn test_receive_reconnect() {
+    let mut connections = 0;
+    let mut replica = SignalReplica::<u32>::new();
+
+    let frame = encode_frame(&5);
+
+    block_on(receive_frames_reconnect(
+        || {
+            connections += 1;
+
+            match connections {
+                // The connection is lost in the middle of a frame
+                1 => ready(Ok(Some(Box::new(Broken(frame[..3].to_vec())) as Box<dyn AsyncRead + Unpin>))),
+                // The connection is closed, so it reconnects
+                2 => ready(Ok(Some(Box::new(Cursor::new(frame.clone())) as Box<dyn AsyncRead + Unpin>))),
+                _ => ready(Ok(None)),
+            }
+        },
+        &mut replica,
+    )).unwrap();
+
+    assert_eq!(connections, 3);
+    assert_eq!(replica.read_only().get(), Some(5));
+}
+
+
+#[test]
+fn test_empty_snapshot() {
+    let vec = MutableVec::<u32>::new();
+    let map = MutableBTreeMap::<u32, u32>::new();
+
+    let mut vec_frames = Box::pin(signal_vec_frames(vec.signal_vec()));
+    let mut map_frames = Box::pin(signal_map_frames(map.signal_map()));
+
+    assert_eq!(ready_bytes(&mut vec_frames), encode_frame(&VecDiff::<u32>::Replace { values: vec![] }));
+    assert_eq!(ready_bytes(&mut map_frames), encode_frame(&MapDiff::<u32, u32>::Replace { entries: vec![] }));
+
+    // It isn't sent twice if the SignalVec starts with a snapshot
+    vec.lock_mut().push(1);
+    let mut vec_frames = Box::pin(signal_vec_frames(vec.signal_vec()));
+    assert_eq!(ready_bytes(&mut vec_frames), encode_frame(&VecDiff::Replace { values: vec![1] }));
+}
+
+
+#[test]
+fn test_receive_reconnect_empty() {
+    let vec = MutableVec::new_with_values(vec![1, 2, 3]);
+    let mut replica = SignalVecReplica::<u32>::new();
+    let mut connections = 0;
+
+    block_on(receive_frames_reconnect(
+        || {
+            connections += 1;
+
+            let bytes = match connections {
+                1 => ready_bytes(&mut Box::pin(signal_vec_frames(vec.signal_vec()))),
+                2 => {
+                    // The source becomes empty while the replica is disconnected
+                    vec.lock_mut().clear();
+                    ready_bytes(&mut Box::pin(signal_vec_frames(vec.signal_vec())))
+                },
+                _ => return ready(Ok(None)),
+            };
+
+            ready(Ok(Some(Cursor::new(bytes))))
+        },
+        &mut replica,
+    )).unwrap();
+
+    assert_eq!(connections, 3);
+    assert!(replica.lock_ref().is_empty());
+}
@@ -1,10 +1,13 @@
 use std::rc::Rc;
 use std::cell::Cell;
 use std::task::Poll;
+#[cfg(feature = "std")]
 use futures_signals::cancelable_future;
-use futures_signals::signal::{SignalExt, Mutable, channel};
+use futures_signals::combine;
+use futures_signals::signal::{self, SignalExt, SignalResultExt, SignalOptionExt, SignalBoolExt, Mutable, channel, bounded_channel};
 use futures_signals::signal_vec::VecDiff;
-use futures_util::future::{ready, poll_fn};
+use futures_util::future::{poll_fn, FutureExt};
+use futures_util::stream::StreamExt;
 
 mod util;
 
@@ -95,10 +98,14 @@ fn test_mutable_drop() {
 }
 
 #[test]
+#[allow(clippy::let_underscore_future)]
 fn test_send_sync() {
-    let a = cancelable_future(ready(()), || ());
-    let _: Box<dyn Send + Sync> = Box::new(a.0);
-    let _: Box<dyn Send + Sync> = Box::new(a.1);
+    #[cfg(feature = "std")]
+    {
+        let a = cancelable_future(futures_util::future::ready(()), || ());
+        let _: Box<dyn Send + Sync> = Box::new(a.0);
+        let _: Box<dyn Send + Sync> = Box::new(a.1);
+    }
 
     let _: Box<dyn Send + Sync> = Box::new(Mutable::new(1));
     let _: Box<dyn Send + Sync> = Box::new(Mutable::new(1).signal());
@@ -107,6 +114,10 @@ fn test_send_sync() {
     let a = channel(1);
     let _: Box<dyn Send + Sync> = Box::new(a.0);
     let _: Box<dyn Send + Sync> = Box::new(a.1);
+
+    let a = bounded_channel::<u32>(1);
+    let _: Box<dyn Send + Sync> = Box::new(a.0);
+    let _: Box<dyn Send + Sync> = Box::new(a.1);
 }
 
 
@@ -206,3 +217,850 @@ fn test_switch_signal_vec() {
         Poll::Ready(None)
     ]);
 }
+
+
+#[test]
+fn test_option() {
+    util::assert_signal_eq(signal::option(Some(signal::always(5))), vec![
+        Poll::Ready(Some(Some(5))),
+        Poll::Ready(None),
+    ]);
+
+    util::assert_signal_eq(signal::option(None::<signal::Always<u32>>), vec![
+        Poll::Ready(Some(None)),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_from_option() {
+    let mutable = Mutable::new(1);
+    let mut signal = signal::from_option(Some(mutable.signal()));
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(Some(1))));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        mutable.set(2);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(Some(2))));
+
+        drop(mutable);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(None));
+    });
+
+    let signal: signal::OptionSignal<signal::Always<u32>> = None.into();
+
+    util::assert_signal_eq(signal, vec![
+        Poll::Ready(Some(None)),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_from_stream_with_initial() {
+    let (sender, receiver) = futures_channel::mpsc::unbounded();
+
+    let mut signal = signal::from_stream_with_initial(0, receiver);
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(0)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        sender.unbounded_send(1).unwrap();
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(1)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        drop(sender);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(None));
+    });
+
+    // The initial value is skipped if the Stream already has a value
+    util::assert_signal_eq(signal::from_stream_with_initial(0, futures_util::stream::iter(vec![5])), vec![
+        Poll::Ready(Some(5)),
+        Poll::Ready(None),
+    ]);
+
+    util::assert_signal_eq(signal::from_stream_with_initial(0, futures_util::stream::empty()), vec![
+        Poll::Ready(Some(0)),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[cfg(feature = "std")]
+#[test]
+fn test_debug() {
+    let input = util::Source::new(vec![
+        Poll::Ready(1),
+        Poll::Pending,
+        Poll::Ready(2),
+    ]);
+
+    util::assert_signal_eq(input.debug(), vec![
+        Poll::Ready(Some(1)),
+        Poll::Pending,
+        Poll::Ready(Some(2)),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_inspect() {
+    let values = Rc::new(std::cell::RefCell::new(vec![]));
+    let ended = Rc::new(Cell::new(0));
+
+    let input = util::Source::new(vec![
+        Poll::Ready(1),
+        Poll::Pending,
+        Poll::Ready(2),
+    ]);
+
+    let signal = input
+        .inspect({
+            let values = values.clone();
+            move |value| values.borrow_mut().push(*value)
+        })
+        .inspect_end({
+            let ended = ended.clone();
+            move || ended.set(ended.get() + 1)
+        });
+
+    util::assert_signal_eq(signal, vec![
+        Poll::Ready(Some(1)),
+        Poll::Pending,
+        Poll::Ready(Some(2)),
+        Poll::Ready(None),
+    ]);
+
+    assert_eq!(*values.borrow(), vec![1, 2]);
+    assert_eq!(ended.get(), 1);
+}
+
+
+#[test]
+fn test_from_values() {
+    util::assert_signal_eq(signal::from_values(vec![1, 2, 3]), vec![
+        Poll::Ready(Some(1)),
+        Poll::Ready(Some(2)),
+        Poll::Ready(Some(3)),
+        Poll::Ready(None),
+    ]);
+
+    util::assert_signal_eq(signal::from_polls(vec![Poll::Ready(1), Poll::Pending, Poll::Ready(2)]), vec![
+        Poll::Ready(Some(1)),
+        Poll::Pending,
+        Poll::Ready(Some(2)),
+        Poll::Ready(None),
+    ]);
+
+    util::assert_signal_eq(signal::from_values(Vec::<u32>::new()).map(|x| x + 1), vec![
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_block_on_first() {
+    let mutable = Mutable::new(5);
+
+    assert_eq!(signal::block_on_first(mutable.signal().map(|x| x * 2)), Some(10));
+    assert_eq!(signal::block_on_first(signal::from_polls(vec![Poll::Pending, Poll::Ready(1)])), None);
+    assert_eq!(signal::block_on_first(signal::from_values(Vec::<u32>::new())), None);
+}
+
+
+#[test]
+fn test_poll_now() {
+    let mutable = Mutable::new(1);
+    let mut signal = mutable.signal();
+
+    assert_eq!(signal.poll_now(), Some(Some(1)));
+    assert_eq!(signal.poll_now(), None);
+
+    mutable.set(2);
+    assert_eq!(signal.poll_now(), Some(Some(2)));
+
+    drop(mutable);
+    assert_eq!(signal.poll_now(), Some(None));
+}
+
+
+#[test]
+fn test_versioned() {
+    let input = util::Source::new(vec![
+        Poll::Ready("a"),
+        Poll::Pending,
+        Poll::Ready("b"),
+        Poll::Ready("c"),
+    ]);
+
+    util::assert_signal_eq(input.versioned(), vec![
+        Poll::Ready(Some((0, "a"))),
+        Poll::Pending,
+        Poll::Ready(Some((1, "b"))),
+        Poll::Ready(Some((2, "c"))),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_diff() {
+    let input = util::Source::new(vec![
+        Poll::Ready(5),
+        Poll::Pending,
+        Poll::Ready(8),
+        Poll::Ready(3),
+    ]);
+
+    util::assert_signal_eq(input.diff(|old, new| *new - *old.unwrap_or(new)), vec![
+        Poll::Ready(Some(0)),
+        Poll::Pending,
+        Poll::Ready(Some(3)),
+        Poll::Ready(Some(-5)),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_pairwise() {
+    let input = util::Source::new(vec![
+        Poll::Ready(1),
+        Poll::Pending,
+        Poll::Ready(2),
+        Poll::Ready(3),
+    ]);
+
+    util::assert_signal_eq(input.pairwise(), vec![
+        Poll::Ready(Some((None, 1))),
+        Poll::Pending,
+        Poll::Ready(Some((Some(1), 2))),
+        Poll::Ready(Some((Some(2), 3))),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_window() {
+    let input = util::Source::new(vec![
+        Poll::Ready(1),
+        Poll::Pending,
+        Poll::Ready(2),
+        Poll::Ready(3),
+        Poll::Ready(4),
+    ]);
+
+    util::assert_signal_eq(input.window(3).map(|values| values.into_iter().collect::<Vec<_>>()), vec![
+        Poll::Ready(Some(vec![1])),
+        Poll::Pending,
+        Poll::Ready(Some(vec![1, 2])),
+        Poll::Ready(Some(vec![1, 2, 3])),
+        Poll::Ready(Some(vec![2, 3, 4])),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_skip() {
+    let input = util::Source::new(vec![
+        Poll::Ready(1),
+        Poll::Pending,
+        Poll::Ready(2),
+        Poll::Ready(3),
+        Poll::Pending,
+        Poll::Ready(4),
+    ]);
+
+    util::assert_signal_eq(input.skip(2), vec![
+        Poll::Pending,
+        Poll::Ready(Some(3)),
+        Poll::Pending,
+        Poll::Ready(Some(4)),
+        Poll::Ready(None),
+    ]);
+
+    let mutable = Mutable::new(1);
+    let mut signal = mutable.signal().skip(1);
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        mutable.set(2);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(2)));
+    });
+}
+
+
+#[test]
+fn test_skip_while() {
+    let input = util::Source::new(vec![
+        Poll::Ready(1),
+        Poll::Pending,
+        Poll::Ready(2),
+        Poll::Ready(5),
+        Poll::Ready(1),
+    ]);
+
+    util::assert_signal_eq(input.skip_while(|x| *x < 3), vec![
+        Poll::Pending,
+        Poll::Ready(Some(5)),
+        Poll::Ready(Some(1)),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_take() {
+    let input = util::Source::new(vec![
+        Poll::Ready(1),
+        Poll::Pending,
+        Poll::Ready(2),
+        Poll::Ready(3),
+    ]);
+
+    util::assert_signal_eq(input.take(2), vec![
+        Poll::Ready(Some(1)),
+        Poll::Pending,
+        Poll::Ready(Some(2)),
+        Poll::Ready(None),
+    ]);
+
+    util::assert_signal_eq(signal::always(1).take(0), vec![
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_take_while() {
+    let input = util::Source::new(vec![
+        Poll::Ready(1),
+        Poll::Pending,
+        Poll::Ready(2),
+        Poll::Ready(5),
+        Poll::Ready(1),
+    ]);
+
+    util::assert_signal_eq(input.take_while(|x| *x < 3), vec![
+        Poll::Ready(Some(1)),
+        Poll::Pending,
+        Poll::Ready(Some(2)),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_take_until() {
+    let mutable = Mutable::new(1);
+    let (sender, receiver) = futures_channel::oneshot::channel::<()>();
+
+    let mut signal = mutable.signal().take_until(receiver);
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(1)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        mutable.set(2);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(2)));
+
+        sender.send(()).unwrap();
+        mutable.set(3);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(None));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[test]
+fn test_stop_if() {
+    let input = util::Source::new(vec![
+        Poll::Ready("pending"),
+        Poll::Pending,
+        Poll::Ready("running"),
+        Poll::Ready("done"),
+        Poll::Ready("pending"),
+    ]);
+
+    util::assert_signal_eq(input.stop_if(|status| *status == "done"), vec![
+        Poll::Ready(Some("pending")),
+        Poll::Pending,
+        Poll::Ready(Some("running")),
+        Poll::Ready(Some("done")),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_merge() {
+    let left = Mutable::new(1);
+    let right = Mutable::new(10);
+
+    let mut signal = signal::merge(left.signal(), right.signal());
+
+    util::with_noop_context(|cx| {
+        // Both changed at the same time
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(10)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        left.set(2);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(2)));
+
+        right.set(20);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(20)));
+
+        // It alternates, so the left one wins this time
+        left.set(3);
+        right.set(30);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(3)));
+
+        left.set(4);
+        right.set(40);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(40)));
+
+        drop(left);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        right.set(50);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(50)));
+
+        drop(right);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[test]
+fn test_zip() {
+    let left = Mutable::new(1);
+
+    let right = util::Source::new(vec![
+        Poll::Pending,
+        Poll::Ready("a"),
+        Poll::Pending,
+        Poll::Ready("b"),
+    ]);
+
+    let mut signal = signal::zip(left.signal(), right);
+
+    util::with_noop_context(|cx| {
+        // It waits for both to have a value
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some((1, "a"))));
+
+        left.set(2);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some((2, "a"))));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some((2, "b"))));
+
+        drop(left);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[test]
+fn test_combine() {
+    let name = Mutable::new("foo".to_string());
+    let age = Mutable::new(5);
+    let admin = Mutable::new(false);
+
+    let mut signal = combine!(name.signal_cloned(), age.signal(), admin.signal());
+    let mut single = combine!(age.signal());
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(("foo".to_string(), 5, false))));
+        assert_eq!(single.poll_change_unpin(cx), Poll::Ready(Some((5,))));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        age.set(6);
+        admin.set(true);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(("foo".to_string(), 6, true))));
+        assert_eq!(single.poll_change_unpin(cx), Poll::Ready(Some((6,))));
+    });
+}
+
+
+#[test]
+fn test_switch() {
+    let outer = Mutable::new(0);
+    let inner = vec![Mutable::new("a"), Mutable::new("b")];
+
+    let mut signal = outer.signal().switch({
+        let inner = inner.clone();
+        move |index| inner[index].signal()
+    });
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some("a")));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        inner[0].set("a2");
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some("a2")));
+
+        outer.set(1);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some("b")));
+
+        // The old inner Signal is no longer followed
+        inner[0].set("a3");
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        inner[1].set("b2");
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some("b2")));
+    });
+}
+
+
+#[test]
+fn test_result_ext() {
+    let input = util::Source::new(vec![
+        Poll::Ready(Ok(1)),
+        Poll::Pending,
+        Poll::Ready(Err("bad")),
+        Poll::Ready(Ok(5)),
+        Poll::Ready(Ok(2)),
+    ]);
+
+    let output = input
+        .try_filter(|x| *x != 5)
+        .map_ok(|x| x * 10)
+        .map_err(|error: &str| error.len());
+
+    util::assert_signal_eq(output, vec![
+        Poll::Ready(Some(Ok(10))),
+        Poll::Pending,
+        Poll::Ready(Some(Err(3))),
+        Poll::Ready(Some(Ok(20))),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_option_ext() {
+    let input = util::Source::new(vec![
+        Poll::Ready(Some(Some(1))),
+        Poll::Pending,
+        Poll::Ready(Some(None)),
+        Poll::Ready(None),
+        Poll::Ready(Some(Some(2))),
+    ]);
+
+    let output = input
+        .flatten_option()
+        .map_some(|x| x * 10)
+        .unwrap_or(0);
+
+    util::assert_signal_eq(output, vec![
+        Poll::Ready(Some(10)),
+        Poll::Pending,
+        Poll::Ready(Some(0)),
+        Poll::Ready(Some(0)),
+        Poll::Ready(Some(20)),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_bool_ext() {
+    let left = Mutable::new(false);
+    let right = Mutable::new(true);
+
+    assert_eq!(signal::block_on_first(left.signal().not()), Some(true));
+    assert_eq!(signal::block_on_first(left.signal().and(right.signal())), Some(false));
+    assert_eq!(signal::block_on_first(left.signal().or(right.signal())), Some(true));
+    assert_eq!(signal::block_on_first(right.signal().and(right.signal().not().not())), Some(true));
+}
+
+
+#[test]
+fn test_from_future() {
+    let mut polls = vec![Poll::Ready(5), Poll::Pending, Poll::Pending];
+
+    let future = poll_fn(move |cx| {
+        let poll = polls.pop().unwrap();
+
+        if poll.is_pending() {
+            cx.waker().wake_by_ref();
+        }
+
+        poll
+    });
+
+    util::assert_signal_eq(signal::from_future(future), vec![
+        Poll::Ready(Some(None)),
+        Poll::Pending,
+        Poll::Ready(Some(Some(5))),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_map_async_latest() {
+    struct Guard(Rc<Cell<usize>>);
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() - 1);
+        }
+    }
+
+    let alive = Rc::new(Cell::new(0));
+
+    let input = util::Source::new(vec![
+        Poll::Ready(1),
+        Poll::Pending,
+        Poll::Ready(2),
+    ]);
+
+    let output = input.map_async_latest(move |x| {
+        // The old Future must be dropped before the new Future is created
+        assert_eq!(alive.get(), 0);
+        alive.set(alive.get() + 1);
+
+        let guard = Guard(alive.clone());
+        let mut first = true;
+
+        poll_fn(move |cx| {
+            let _ = &guard;
+
+            if first {
+                first = false;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+
+            } else {
+                Poll::Ready(x * 10)
+            }
+        })
+    });
+
+    util::assert_signal_eq(output, vec![
+        Poll::Ready(Some(signal::AsyncState::Pending)),
+        Poll::Ready(Some(signal::AsyncState::Cancelled)),
+        Poll::Ready(Some(signal::AsyncState::Pending)),
+        Poll::Ready(Some(signal::AsyncState::Ready(20))),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_map_async_queued() {
+    let running = Rc::new(Cell::new(false));
+
+    let input = util::Source::new(vec![
+        Poll::Ready(1),
+        Poll::Pending,
+        Poll::Ready(2),
+        Poll::Ready(3),
+    ]);
+
+    let output = input.map_async_queued(move |x| {
+        // Only one Future runs at a time
+        assert!(!running.get());
+        running.set(true);
+
+        let running = running.clone();
+        let mut first = true;
+
+        poll_fn(move |cx| {
+            if first {
+                first = false;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+
+            } else {
+                running.set(false);
+                Poll::Ready(x * 10)
+            }
+        })
+    });
+
+    util::assert_signal_eq(output, vec![
+        Poll::Ready(Some(None)),
+        Poll::Ready(Some(Some(10))),
+        Poll::Pending,
+        Poll::Ready(Some(Some(20))),
+        Poll::Pending,
+        Poll::Ready(Some(Some(30))),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_filter_by() {
+    let input = util::Source::new(vec![
+        Poll::Ready(1),
+        Poll::Pending,
+        Poll::Ready(2),
+        Poll::Pending,
+        Poll::Ready(3),
+        Poll::Ready(4),
+        Poll::Pending,
+        Poll::Pending,
+        Poll::Ready(5),
+    ]);
+
+    let gate = util::Source::new(vec![
+        Poll::Ready(true),
+        Poll::Pending,
+        Poll::Ready(false),
+        Poll::Pending,
+        Poll::Pending,
+        Poll::Ready(true),
+    ]);
+
+    util::assert_signal_eq(input.filter_by(gate), vec![
+        Poll::Ready(Some(1)),
+        Poll::Pending,
+        Poll::Pending,
+        Poll::Ready(Some(4)),
+        Poll::Ready(Some(5)),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_pausable() {
+    let mutable = Mutable::new(1);
+
+    let (signal, handle) = mutable.signal().pausable();
+
+    let mut signal = signal;
+
+    assert_eq!(signal.poll_now(), Some(Some(1)));
+
+    handle.pause();
+    assert!(handle.is_paused());
+
+    mutable.set(2);
+    mutable.set(3);
+    assert_eq!(signal.poll_now(), None);
+
+    handle.resume();
+    assert_eq!(signal.poll_now(), Some(Some(3)));
+    assert_eq!(signal.poll_now(), None);
+
+    handle.pause();
+    drop(handle);
+    mutable.set(4);
+    assert_eq!(signal.poll_now(), Some(None));
+}
+
+
+#[test]
+fn test_buffered_stream() {
+    let collect = |overflow| {
+        let (mut pump, mut stream) = signal::from_values(vec![1, 2, 3, 4]).buffered_stream(2, overflow);
+        let mut output = vec![];
+
+        util::with_noop_context(|cx| {
+            // The Signal is polled even though the Stream isn't being polled
+            assert_eq!(pump.poll_unpin(cx), Poll::Ready(()));
+
+            loop {
+                match stream.poll_next_unpin(cx) {
+                    Poll::Ready(Some(value)) => output.push(value),
+                    Poll::Ready(None) => break,
+                    Poll::Pending => panic!("stream should not be pending"),
+                }
+            }
+        });
+
+        output
+    };
+
+    assert_eq!(collect(signal::Overflow::DropOldest), vec![
+        Ok(3),
+        Ok(4),
+    ]);
+
+    assert_eq!(collect(signal::Overflow::DropNewest), vec![
+        Ok(1),
+        Ok(2),
+    ]);
+
+    assert_eq!(collect(signal::Overflow::Error), vec![
+        Ok(1),
+        Ok(2),
+        Err(signal::BufferOverflow { dropped: 2 }),
+    ]);
+}
+
+
+#[test]
+fn test_buffered_stream_lagging() {
+    let mutable = Mutable::new(1);
+    let (mut pump, mut stream) = mutable.signal().buffered_stream(2, signal::Overflow::Error);
+
+    util::with_noop_context(|cx| {
+        assert_eq!(pump.poll_unpin(cx), Poll::Pending);
+
+        mutable.set(2);
+        assert_eq!(pump.poll_unpin(cx), Poll::Pending);
+
+        // The queue is full, so this is discarded
+        mutable.set(3);
+        assert_eq!(pump.poll_unpin(cx), Poll::Pending);
+
+        assert_eq!(stream.poll_next_unpin(cx), Poll::Ready(Some(Ok(1))));
+
+        mutable.set(4);
+        assert_eq!(pump.poll_unpin(cx), Poll::Pending);
+
+        assert_eq!(stream.poll_next_unpin(cx), Poll::Ready(Some(Ok(2))));
+        assert_eq!(stream.poll_next_unpin(cx), Poll::Ready(Some(Err(signal::BufferOverflow { dropped: 1 }))));
+        assert_eq!(stream.poll_next_unpin(cx), Poll::Ready(Some(Ok(4))));
+        assert_eq!(stream.poll_next_unpin(cx), Poll::Pending);
+
+        drop(mutable);
+        assert_eq!(pump.poll_unpin(cx), Poll::Ready(()));
+        assert_eq!(stream.poll_next_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[test]
+fn test_buffered_stream_drop() {
+    let mutable = Mutable::new(1);
+    let (mut pump, stream) = mutable.signal().buffered_stream(2, signal::Overflow::Error);
+
+    util::with_noop_context(|cx| {
+        assert_eq!(pump.poll_unpin(cx), Poll::Pending);
+
+        // The pump stops when the Stream is dropped
+        drop(stream);
+        assert_eq!(pump.poll_unpin(cx), Poll::Ready(()));
+    });
+
+    let (pump, mut stream) = mutable.signal().buffered_stream(2, signal::Overflow::Error);
+
+    util::with_noop_context(|cx| {
+        // The Stream ends when the pump is dropped
+        drop(pump);
+        assert_eq!(stream.poll_next_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[test]
+#[should_panic(expected = "buffered_stream overflowed its capacity of 2")]
+fn test_buffered_stream_panic() {
+    let (mut pump, _stream) = signal::from_values(vec![1, 2, 3]).buffered_stream(2, signal::Overflow::Panic);
+
+    util::with_noop_context(|cx| {
+        let _ = pump.poll_unpin(cx);
+    });
+}
@@ -0,0 +1,195 @@
+use std::task::Poll;
+use futures_signals::signal_map::{MapDiff, SignalMapExt};
+
+mod util;
+
+
+#[test]
+fn map_value() {
+    let input = util::Source::new(vec![
+        Poll::Ready(MapDiff::Replace { entries: vec![(1, 1), (2, 2)] }),
+        Poll::Pending,
+        Poll::Ready(MapDiff::Insert { key: 3, value: 3 }),
+        Poll::Ready(MapDiff::Update { key: 1, value: 4 }),
+        Poll::Pending,
+        Poll::Ready(MapDiff::Remove { key: 2 }),
+        Poll::Ready(MapDiff::Clear {}),
+    ]);
+
+    let output = input.map_value(|value| value * 10);
+
+    util::assert_signal_map_eq(output, vec![
+        Poll::Ready(Some(MapDiff::Replace { entries: vec![(1, 10), (2, 20)] })),
+        Poll::Pending,
+        Poll::Ready(Some(MapDiff::Insert { key: 3, value: 30 })),
+        Poll::Ready(Some(MapDiff::Update { key: 1, value: 40 })),
+        Poll::Pending,
+        Poll::Ready(Some(MapDiff::Remove { key: 2 })),
+        Poll::Ready(Some(MapDiff::Clear {})),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn filter() {
+    let input = util::Source::new(vec![
+        Poll::Ready(MapDiff::Replace { entries: vec![(1, 1), (2, 6), (3, 3)] }),
+        Poll::Pending,
+        Poll::Ready(MapDiff::Insert { key: 4, value: 7 }),
+        Poll::Ready(MapDiff::Insert { key: 5, value: 2 }),
+        Poll::Pending,
+        Poll::Ready(MapDiff::Update { key: 1, value: 4 }),
+        Poll::Ready(MapDiff::Update { key: 2, value: 0 }),
+        Poll::Ready(MapDiff::Update { key: 3, value: 8 }),
+        Poll::Ready(MapDiff::Update { key: 4, value: 9 }),
+        Poll::Pending,
+        Poll::Ready(MapDiff::Remove { key: 4 }),
+        Poll::Ready(MapDiff::Remove { key: 5 }),
+        Poll::Ready(MapDiff::Clear {}),
+    ]);
+
+    let output = input.filter(|_key, value| *value < 5);
+
+    util::assert_signal_map_eq(output, vec![
+        Poll::Ready(Some(MapDiff::Replace { entries: vec![(1, 1), (3, 3)] })),
+        Poll::Pending,
+        Poll::Ready(Some(MapDiff::Insert { key: 5, value: 2 })),
+        Poll::Pending,
+        Poll::Ready(Some(MapDiff::Update { key: 1, value: 4 })),
+        Poll::Ready(Some(MapDiff::Insert { key: 2, value: 0 })),
+        Poll::Ready(Some(MapDiff::Remove { key: 3 })),
+        Poll::Pending,
+        Poll::Ready(Some(MapDiff::Remove { key: 5 })),
+        Poll::Ready(Some(MapDiff::Clear {})),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn key_cloned() {
+    let input = util::Source::new(vec![
+        Poll::Ready(MapDiff::Replace { entries: vec![(1, 1), (2, 2)] }),
+        Poll::Pending,
+        Poll::Ready(MapDiff::Insert { key: 3, value: 3 }),
+        Poll::Ready(MapDiff::Update { key: 1, value: 4 }),
+        Poll::Pending,
+        Poll::Ready(MapDiff::Update { key: 2, value: 5 }),
+        Poll::Pending,
+        Poll::Ready(MapDiff::Remove { key: 2 }),
+        Poll::Pending,
+        Poll::Ready(MapDiff::Insert { key: 2, value: 6 }),
+        Poll::Pending,
+        Poll::Ready(MapDiff::Remove { key: 1 }),
+        Poll::Ready(MapDiff::Clear {}),
+        Poll::Pending,
+        Poll::Ready(MapDiff::Replace { entries: vec![(2, 7)] }),
+    ]);
+
+    util::assert_signal_eq(input.key_cloned(2), vec![
+        Poll::Ready(Some(Some(2))),
+        Poll::Pending,
+        Poll::Ready(Some(Some(5))),
+        Poll::Ready(Some(None)),
+        Poll::Ready(Some(Some(6))),
+        Poll::Ready(Some(None)),
+        Poll::Ready(Some(Some(7))),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn len() {
+    let input = util::Source::new(vec![
+        Poll::Ready(MapDiff::Replace { entries: vec![(1, 1), (2, 2)] }),
+        Poll::Pending,
+        Poll::Ready(MapDiff::Update { key: 1, value: 3 }),
+        Poll::Pending,
+        Poll::Ready(MapDiff::Insert { key: 3, value: 3 }),
+        Poll::Ready(MapDiff::Remove { key: 1 }),
+        Poll::Pending,
+        Poll::Ready(MapDiff::Insert { key: 4, value: 4 }),
+        Poll::Pending,
+        Poll::Ready(MapDiff::Clear {}),
+    ]);
+
+    util::assert_signal_eq(input.len(), vec![
+        Poll::Ready(Some(2)),
+        Poll::Pending,
+        Poll::Pending,
+        Poll::Ready(Some(3)),
+        Poll::Ready(Some(0)),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn is_empty() {
+    let input = util::Source::new(vec![
+        Poll::Ready(MapDiff::Insert { key: 1, value: 1 }),
+        Poll::Pending,
+        Poll::Ready(MapDiff::Insert { key: 2, value: 2 }),
+        Poll::Pending,
+        Poll::Ready(MapDiff::Remove { key: 1 }),
+        Poll::Ready(MapDiff::Remove { key: 2 }),
+        Poll::Pending,
+        Poll::Ready(MapDiff::Replace { entries: vec![] }),
+    ]);
+
+    util::assert_signal_eq(input.is_empty(), vec![
+        Poll::Ready(Some(false)),
+        Poll::Ready(Some(true)),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn test_apply_to_map() {
+    use std::collections::BTreeMap;
+    #[cfg(feature = "std")]
+    use std::collections::HashMap;
+
+    let changes = vec![
+        MapDiff::Replace { entries: vec![(1, "a"), (2, "b")] },
+        MapDiff::Insert { key: 3, value: "c" },
+        MapDiff::Update { key: 1, value: "d" },
+        MapDiff::Remove { key: 2 },
+    ];
+
+    let mut btree_map = BTreeMap::new();
+    #[cfg(feature = "std")]
+    let mut hash_map = HashMap::new();
+
+    for change in changes {
+        #[cfg(feature = "std")]
+        change.clone().apply_to_hash_map(&mut hash_map);
+        change.apply_to_btree_map(&mut btree_map);
+    }
+
+    assert_eq!(btree_map, vec![(1, "d"), (3, "c")].into_iter().collect());
+    #[cfg(feature = "std")]
+    assert_eq!(hash_map, vec![(1, "d"), (3, "c")].into_iter().collect());
+
+    MapDiff::Clear {}.apply_to_btree_map(&mut btree_map);
+    assert!(btree_map.is_empty());
+}
+
+
+#[test]
+fn test_map_diff_serde() {
+    let changes = vec![
+        MapDiff::Replace { entries: vec![("a".to_string(), 1)] },
+        MapDiff::Insert { key: "b".to_string(), value: 2 },
+        MapDiff::Update { key: "a".to_string(), value: 3 },
+        MapDiff::Remove { key: "b".to_string() },
+        MapDiff::Clear {},
+    ];
+
+    let json = serde_json::to_string(&changes).unwrap();
+    let output: Vec<MapDiff<String, u32>> = serde_json::from_str(&json).unwrap();
+    assert_eq!(output, changes);
+}
@@ -0,0 +1,31 @@
+use std::task::Poll;
+use futures_signals::signal_set::{SetDiff, SignalSetExt};
+
+mod util;
+
+
+#[test]
+fn contains() {
+    let input = util::Source::new(vec![
+        Poll::Ready(SetDiff::Replace { values: vec![1, 2] }),
+        Poll::Pending,
+        Poll::Ready(SetDiff::Insert { value: 3 }),
+        Poll::Pending,
+        Poll::Ready(SetDiff::Remove { value: 1 }),
+        Poll::Pending,
+        Poll::Ready(SetDiff::Insert { value: 1 }),
+        Poll::Ready(SetDiff::Remove { value: 1 }),
+        Poll::Ready(SetDiff::Insert { value: 1 }),
+        Poll::Pending,
+        Poll::Ready(SetDiff::Clear {}),
+    ]);
+
+    util::assert_signal_eq(input.contains(1), vec![
+        Poll::Ready(Some(true)),
+        Poll::Pending,
+        Poll::Ready(Some(false)),
+        Poll::Ready(Some(true)),
+        Poll::Ready(Some(false)),
+        Poll::Ready(None),
+    ]);
+}
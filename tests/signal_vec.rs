@@ -1,9 +1,14 @@
 use std::task::Poll;
-use futures_signals::signal_vec::{MutableVec, SignalVecExt, VecDiff};
+#[cfg(feature = "std")]
+use futures_signals::signal::Mutable;
+use futures_signals::signal_vec::{SignalVecExt, VecDiff};
+#[cfg(feature = "std")]
+use futures_signals::signal_vec::MutableVec;
 
 mod util;
 
 
+#[cfg(feature = "std")]
 #[test]
 fn sync() {
     let _: Box<dyn Send + Sync> = Box::new(MutableVec::<()>::new());
@@ -16,6 +21,39 @@ fn sync() {
 }
 
 
+#[test]
+fn map() {
+    let input = util::Source::new(vec![
+        Poll::Ready(VecDiff::Replace { values: vec![0, 1, 2] }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::InsertAt { index: 0, value: 3 }),
+        Poll::Ready(VecDiff::UpdateAt { index: 1, value: 4 }),
+        Poll::Ready(VecDiff::Move { old_index: 0, new_index: 2 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Push { value: 5 }),
+        Poll::Ready(VecDiff::RemoveAt { index: 0 }),
+        Poll::Ready(VecDiff::Pop {}),
+        Poll::Ready(VecDiff::Clear {}),
+    ]);
+
+    let output = input.map(|x| x * 10);
+
+    util::assert_signal_vec_eq(output, vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![0, 10, 20] })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::InsertAt { index: 0, value: 30 })),
+        Poll::Ready(Some(VecDiff::UpdateAt { index: 1, value: 40 })),
+        Poll::Ready(Some(VecDiff::Move { old_index: 0, new_index: 2 })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::Push { value: 50 })),
+        Poll::Ready(Some(VecDiff::RemoveAt { index: 0 })),
+        Poll::Ready(Some(VecDiff::Pop {})),
+        Poll::Ready(Some(VecDiff::Clear {})),
+        Poll::Ready(None),
+    ]);
+}
+
+
 #[test]
 fn filter() {
     /*#[derive(Debug, PartialEq, Eq)]
@@ -114,6 +152,403 @@ fn filter() {
 }
 
 
+#[cfg(feature = "std")]
+#[test]
+fn map_signal() {
+    let a = Mutable::new(1);
+    let b = Mutable::new(2);
+
+    let input = MutableVec::new_with_values(vec![a.clone(), b.clone()]);
+
+    let mut output = input.signal_vec_cloned().map_signal(|value| value.signal());
+
+    util::with_noop_context(|cx| {
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::Replace { values: vec![1, 2] })));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        b.set(5);
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::UpdateAt { index: 1, value: 5 })));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        a.set(3);
+        b.set(4);
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::UpdateAt { index: 0, value: 3 })));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::UpdateAt { index: 1, value: 4 })));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        input.lock_mut().push_cloned(Mutable::new(10));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::Push { value: 10 })));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        input.lock_mut().remove(0);
+        a.set(100);
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::RemoveAt { index: 0 })));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+    });
+}
+
+
+#[test]
+fn filter_update_move() {
+    let input = util::Source::new(vec![
+        Poll::Ready(VecDiff::Replace { values: vec![1, 2, 3, 4] }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::UpdateAt { index: 0, value: 6 }),
+        Poll::Ready(VecDiff::UpdateAt { index: 1, value: 8 }),
+        Poll::Ready(VecDiff::UpdateAt { index: 3, value: 5 }),
+        Poll::Ready(VecDiff::UpdateAt { index: 2, value: 7 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Move { old_index: 0, new_index: 3 }),
+        Poll::Ready(VecDiff::Move { old_index: 1, new_index: 0 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Push { value: 10 }),
+        Poll::Ready(VecDiff::Pop {}),
+        Poll::Ready(VecDiff::Push { value: 3 }),
+        Poll::Ready(VecDiff::Pop {}),
+    ]);
+
+    let output = input.filter(|x| x % 2 == 0);
+
+    util::assert_signal_vec_eq(output, vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![2, 4] })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::InsertAt { index: 0, value: 6 })),
+        Poll::Ready(Some(VecDiff::UpdateAt { index: 1, value: 8 })),
+        Poll::Ready(Some(VecDiff::RemoveAt { index: 2 })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::Move { old_index: 0, new_index: 1 })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::Push { value: 10 })),
+        Poll::Ready(Some(VecDiff::Pop {})),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn filter_move_backwards() {
+    let input = util::Source::new(vec![
+        Poll::Ready(VecDiff::Replace { values: vec![1, 2, 3, 4] }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Move { old_index: 3, new_index: 0 }),
+    ]);
+
+    let output = input.filter(|x| x % 2 == 0);
+
+    util::assert_signal_vec_eq(output, vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![2, 4] })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::Move { old_index: 1, new_index: 0 })),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[cfg(feature = "std")]
+#[test]
+fn filter_signal_cloned() {
+    let flags: Vec<Mutable<bool>> = vec![true, false, true, false, false].into_iter().map(Mutable::new).collect();
+
+    let input = MutableVec::new_with_values(vec![0, 1, 2, 3]);
+
+    let mut output = input.signal_vec().filter_signal_cloned({
+        let flags = flags.clone();
+        move |index| flags[*index].signal()
+    });
+
+    util::with_noop_context(|cx| {
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::Replace { values: vec![0, 2] })));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        flags[1].set(true);
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::InsertAt { index: 1, value: 1 })));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        flags[0].set(false);
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::RemoveAt { index: 0 })));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        flags[3].set(false);
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        input.lock_mut().move_from_to(2, 0);
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::Move { old_index: 1, new_index: 0 })));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        input.lock_mut().push(4);
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        flags[4].set(true);
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::InsertAt { index: 2, value: 4 })));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        input.lock_mut().clear();
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::Clear {})));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+    });
+}
+
+
+#[test]
+fn filter_map() {
+    let input = util::Source::new(vec![
+        Poll::Ready(VecDiff::Replace { values: vec![1, 2, 3, 4] }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::InsertAt { index: 1, value: 6 }),
+        Poll::Ready(VecDiff::InsertAt { index: 0, value: 5 }),
+        Poll::Ready(VecDiff::UpdateAt { index: 1, value: 7 }),
+        Poll::Ready(VecDiff::UpdateAt { index: 2, value: 8 }),
+        Poll::Ready(VecDiff::RemoveAt { index: 5 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Push { value: 10 }),
+        Poll::Ready(VecDiff::Clear {}),
+    ]);
+
+    let output = input.filter_map(|x| {
+        if x % 2 == 0 {
+            Some(x.to_string())
+
+        } else {
+            None
+        }
+    });
+
+    util::assert_signal_vec_eq(output, vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec!["2".to_string(), "4".to_string()] })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::InsertAt { index: 0, value: "6".to_string() })),
+        Poll::Ready(Some(VecDiff::UpdateAt { index: 0, value: "8".to_string() })),
+        Poll::Ready(Some(VecDiff::RemoveAt { index: 2 })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::Push { value: "10".to_string() })),
+        Poll::Ready(Some(VecDiff::Clear {})),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn filter_map_move() {
+    let input = util::Source::new(vec![
+        Poll::Ready(VecDiff::Replace { values: vec![1, 2, 3, 4] }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Move { old_index: 3, new_index: 0 }),
+        Poll::Ready(VecDiff::Move { old_index: 0, new_index: 3 }),
+        Poll::Ready(VecDiff::Move { old_index: 0, new_index: 2 }),
+    ]);
+
+    let output = input.filter_map(|x| if x % 2 == 0 { Some(x * 10) } else { None });
+
+    util::assert_signal_vec_eq(output, vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![20, 40] })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::Move { old_index: 1, new_index: 0 })),
+        Poll::Ready(Some(VecDiff::Move { old_index: 0, new_index: 1 })),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn sort_by_cloned() {
+    let input = util::Source::new(vec![
+        Poll::Ready(VecDiff::Replace { values: vec![3, 1, 2] }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Push { value: 0 }),
+        Poll::Ready(VecDiff::InsertAt { index: 0, value: 5 }),
+        Poll::Ready(VecDiff::UpdateAt { index: 1, value: 4 }),
+        Poll::Ready(VecDiff::UpdateAt { index: 2, value: 6 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::RemoveAt { index: 0 }),
+        Poll::Ready(VecDiff::Pop {}),
+        Poll::Ready(VecDiff::Move { old_index: 0, new_index: 2 }),
+        Poll::Ready(VecDiff::Pop {}),
+        Poll::Ready(VecDiff::Pop {}),
+        Poll::Ready(VecDiff::Clear {}),
+        Poll::Ready(VecDiff::Push { value: 1 }),
+        Poll::Ready(VecDiff::InsertAt { index: 0, value: 1 }),
+    ]);
+
+    let output = input.sort_by_cloned(Ord::cmp);
+
+    util::assert_signal_vec_eq(output, vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![1, 2, 3] })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::InsertAt { index: 0, value: 0 })),
+        Poll::Ready(Some(VecDiff::Push { value: 5 })),
+        Poll::Ready(Some(VecDiff::UpdateAt { index: 3, value: 4 })),
+        Poll::Ready(Some(VecDiff::RemoveAt { index: 1 })),
+        Poll::Ready(Some(VecDiff::Push { value: 6 })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::RemoveAt { index: 3 })),
+        Poll::Ready(Some(VecDiff::RemoveAt { index: 0 })),
+        Poll::Ready(Some(VecDiff::RemoveAt { index: 1 })),
+        Poll::Ready(Some(VecDiff::RemoveAt { index: 0 })),
+        Poll::Ready(Some(VecDiff::Clear {})),
+        Poll::Ready(Some(VecDiff::Push { value: 1 })),
+        Poll::Ready(Some(VecDiff::InsertAt { index: 0, value: 1 })),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn len() {
+    let input = util::Source::new(vec![
+        Poll::Ready(VecDiff::Replace { values: vec![1, 2, 3] }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Push { value: 4 }),
+        Poll::Ready(VecDiff::Pop {}),
+        Poll::Pending,
+        Poll::Ready(VecDiff::UpdateAt { index: 0, value: 5 }),
+        Poll::Ready(VecDiff::Move { old_index: 0, new_index: 1 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::InsertAt { index: 0, value: 6 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::RemoveAt { index: 0 }),
+        Poll::Ready(VecDiff::RemoveAt { index: 0 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Replace { values: vec![1, 2] }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Clear {}),
+    ]);
+
+    util::assert_signal_eq(input.len(), vec![
+        Poll::Ready(Some(3)),
+        Poll::Pending,
+        Poll::Pending,
+        Poll::Ready(Some(4)),
+        Poll::Ready(Some(2)),
+        Poll::Pending,
+        Poll::Ready(Some(0)),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn is_empty() {
+    let input = util::Source::new(vec![
+        Poll::Ready(VecDiff::Replace { values: vec![] }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Push { value: 1 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Push { value: 2 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::RemoveAt { index: 0 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Pop {}),
+        Poll::Ready(VecDiff::Push { value: 3 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Clear {}),
+    ]);
+
+    util::assert_signal_eq(input.is_empty(), vec![
+        Poll::Ready(Some(true)),
+        Poll::Ready(Some(false)),
+        Poll::Pending,
+        Poll::Ready(Some(true)),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn dedupe_cloned() {
+    let input = util::Source::new(vec![
+        Poll::Ready(VecDiff::Replace { values: vec![1, 2, 3] }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Replace { values: vec![1, 2, 3] }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::UpdateAt { index: 1, value: 2 }),
+        Poll::Ready(VecDiff::UpdateAt { index: 1, value: 5 }),
+        Poll::Ready(VecDiff::UpdateAt { index: 1, value: 5 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::InsertAt { index: 0, value: 4 }),
+        Poll::Ready(VecDiff::UpdateAt { index: 0, value: 4 }),
+        Poll::Ready(VecDiff::Move { old_index: 0, new_index: 3 }),
+        Poll::Ready(VecDiff::UpdateAt { index: 3, value: 4 }),
+        Poll::Ready(VecDiff::UpdateAt { index: 0, value: 1 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Replace { values: vec![1, 5, 3, 4] }),
+        Poll::Ready(VecDiff::Clear {}),
+        Poll::Ready(VecDiff::Replace { values: vec![] }),
+    ]);
+
+    util::assert_signal_vec_eq(input.dedupe_cloned(), vec![
+        Poll::Ready(Some(VecDiff::Replace { values: vec![1, 2, 3] })),
+        Poll::Pending,
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::UpdateAt { index: 1, value: 5 })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::InsertAt { index: 0, value: 4 })),
+        Poll::Ready(Some(VecDiff::Move { old_index: 0, new_index: 3 })),
+        Poll::Pending,
+        Poll::Ready(Some(VecDiff::Clear {})),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[cfg(feature = "std")]
+#[test]
+fn delay_remove() {
+    let flags: Vec<Mutable<bool>> = (0..4).map(|_| Mutable::new(false)).collect();
+
+    let input = MutableVec::new_with_values(vec![0, 1, 2]);
+
+    let mut output = input.signal_vec().delay_remove({
+        let flags = flags.clone();
+        move |value| {
+            let signal = flags[*value].signal();
+
+            async move {
+                futures_signals::signal::SignalExt::wait_for(signal, true).await;
+            }
+        }
+    });
+
+    util::with_noop_context(|cx| {
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::Replace { values: vec![0, 1, 2] })));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        input.lock_mut().remove(1);
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        input.lock_mut().insert(1, 3);
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::InsertAt { index: 2, value: 3 })));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        flags[1].set(true);
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::RemoveAt { index: 1 })));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        input.lock_mut().pop();
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        input.lock_mut().move_from_to(0, 1);
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::Move { old_index: 0, new_index: 2 })));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        flags[2].set(true);
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::RemoveAt { index: 1 })));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        flags[0].set(true);
+        input.lock_mut().clear();
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::Pop {})));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        flags[3].set(true);
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(Some(VecDiff::Pop {})));
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Pending);
+
+        drop(input);
+        assert_eq!(output.poll_vec_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
 #[test]
 fn sum() {
     let input = util::Source::new(vec![
@@ -151,6 +586,149 @@ fn sum() {
 
 
 #[test]
+fn incremental_sum() {
+    let input = util::Source::new(vec![
+        Poll::Ready(VecDiff::Push { value: 1.5 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::InsertAt { index: 0, value: 2.0 }),
+        Poll::Ready(VecDiff::Push { value: 0.5 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Pop {}),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Push { value: 3.0 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::UpdateAt { index: 0, value: -1.0 }),
+    ]);
+
+    let output = input.incremental_sum();
+
+    util::assert_signal_eq(output, vec![
+        Poll::Ready(Some(1.5)),
+        Poll::Ready(Some(4.0)),
+        Poll::Ready(Some(3.5)),
+        Poll::Ready(Some(6.5)),
+        Poll::Ready(Some(3.5)),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn incremental_sum_removes_without_recalculating() {
+    // Counts how many times a value is added to the sum
+    #[derive(Debug, Default, Clone, PartialEq)]
+    struct Counted {
+        value: i32,
+        adds: usize,
+    }
+
+    impl std::ops::AddAssign for Counted {
+        fn add_assign(&mut self, other: Self) {
+            self.value += other.value;
+            self.adds += 1;
+        }
+    }
+
+    impl std::ops::SubAssign for Counted {
+        fn sub_assign(&mut self, other: Self) {
+            self.value -= other.value;
+        }
+    }
+
+    fn counted(value: i32) -> Counted {
+        Counted { value, adds: 0 }
+    }
+
+    let input = util::Source::new(vec![
+        Poll::Ready(VecDiff::Replace { values: vec![counted(1), counted(2), counted(3)] }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::RemoveAt { index: 0 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::UpdateAt { index: 1, value: counted(10) }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Pop {}),
+    ]);
+
+    let output = input.incremental_sum();
+
+    util::assert_signal_eq(output, vec![
+        Poll::Ready(Some(Counted { value: 6, adds: 3 })),
+        Poll::Ready(Some(Counted { value: 5, adds: 3 })),
+        Poll::Ready(Some(Counted { value: 12, adds: 4 })),
+        Poll::Ready(Some(Counted { value: 2, adds: 4 })),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn incremental_sum_update_overflow() {
+    // 200 + 100 doesn't fit in a u8, but the final sum does
+    let input = util::Source::new(vec![
+        Poll::Ready(VecDiff::Replace { values: vec![200u8] }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::UpdateAt { index: 0, value: 100 }),
+    ]);
+
+    let output = input.incremental_sum();
+
+    util::assert_signal_eq(output, vec![
+        Poll::Ready(Some(200)),
+        Poll::Ready(Some(100)),
+        Poll::Ready(None),
+    ]);
+}
+
+
+#[test]
+fn to_stream() {
+    let input = util::Source::new(vec![
+        Poll::Ready(VecDiff::Replace { values: vec![0, 1] }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Push { value: 2 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Move { old_index: 0, new_index: 2 }),
+    ]);
+
+    let output: Vec<VecDiff<u32>> = futures_executor::block_on(futures::stream::StreamExt::collect(input.to_stream()));
+
+    assert_eq!(output, vec![
+        VecDiff::Replace { values: vec![0, 1] },
+        VecDiff::Push { value: 2 },
+        VecDiff::Move { old_index: 0, new_index: 2 },
+    ]);
+}
+
+
+#[test]
+fn for_each() {
+    let input = util::Source::new(vec![
+        Poll::Ready(VecDiff::Replace { values: vec![0, 1] }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Push { value: 2 }),
+        Poll::Ready(VecDiff::RemoveAt { index: 0 }),
+        Poll::Pending,
+        Poll::Ready(VecDiff::Clear {}),
+    ]);
+
+    let mut output = vec![];
+
+    futures_executor::block_on(input.for_each(|change| {
+        output.push(change);
+        async {}
+    }));
+
+    assert_eq!(output, vec![
+        VecDiff::Replace { values: vec![0, 1] },
+        VecDiff::Push { value: 2 },
+        VecDiff::RemoveAt { index: 0 },
+        VecDiff::Clear {},
+    ]);
+}
+
+
+#[test]
+#[allow(clippy::into_iter_on_ref)]
 fn to_signal_map() {
     let input = util::Source::new(vec![
         Poll::Pending,
@@ -227,3 +805,68 @@ fn debug_to_signal_cloned() {
     let input: util::Source<VecDiff<u32>> = util::Source::new(vec![]);
     assert_eq!(format!("{:?}", input.to_signal_cloned()), "ToSignalCloned { ... }");
 }
+
+
+#[cfg(feature = "std")]
+#[test]
+fn test_apply_to_vec() {
+    let input = MutableVec::new_with_values(vec![1, 2, 3]);
+    let mut signal = input.signal_vec();
+    let mut output = vec![];
+
+    let mut sync = |output: &mut Vec<u32>| {
+        util::with_noop_context(|cx| {
+            while let Poll::Ready(Some(change)) = signal.poll_vec_change_unpin(cx) {
+                change.apply_to_vec(output);
+            }
+        });
+    };
+
+    sync(&mut output);
+    assert_eq!(output, vec![1, 2, 3]);
+
+    {
+        let mut lock = input.lock_mut();
+        lock.push(4);
+        lock.insert(0, 5);
+        lock.set(1, 6);
+        lock.move_from_to(0, 4);
+        lock.remove(2);
+        lock.pop();
+    }
+
+    sync(&mut output);
+    assert_eq!(output, *input.lock_ref());
+
+    input.lock_mut().clear();
+    sync(&mut output);
+    assert_eq!(output, Vec::<u32>::new());
+}
+
+
+#[test]
+fn test_vec_diff_serde() {
+    let changes = vec![
+        VecDiff::Replace { values: vec![1, 2, 3] },
+        VecDiff::InsertAt { index: 0, value: 4 },
+        VecDiff::UpdateAt { index: 1, value: 5 },
+        VecDiff::RemoveAt { index: 2 },
+        VecDiff::Move { old_index: 0, new_index: 1 },
+        VecDiff::Push { value: 6 },
+        VecDiff::Pop {},
+        VecDiff::Clear {},
+    ];
+
+    let json = serde_json::to_string(&changes).unwrap();
+    let output: Vec<VecDiff<u32>> = serde_json::from_str(&json).unwrap();
+    assert_eq!(output, changes);
+
+    // The changes can be replayed on the other side
+    let mut vec = vec![];
+
+    for change in output.into_iter().take(6) {
+        change.apply_to_vec(&mut vec);
+    }
+
+    assert_eq!(vec, vec![5, 4, 3, 6]);
+}
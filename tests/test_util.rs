@@ -0,0 +1,75 @@
+#![cfg(feature = "test-util")]
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::task::Poll;
+use futures_util::future::ready;
+use futures_signals::assert_signal_eq;
+use futures_signals::signal::{self, Mutable, SignalExt};
+use futures_signals::test_util::ManualExecutor;
+
+
+#[test]
+fn test_assert_signal_eq() {
+    assert_signal_eq!(signal::from_polls(vec![Poll::Ready(1), Poll::Pending, Poll::Ready(2)]).map(|x| x + 1), [
+        Poll::Ready(Some(2)),
+        Poll::Pending,
+        Poll::Ready(Some(3)),
+        Poll::Ready(None),
+    ]);
+
+    let mutable = Mutable::new(5);
+
+    assert_signal_eq!(mutable.signal(), [
+        Poll::Ready(Some(5)),
+        Poll::Pending,
+    ]);
+
+    assert_signal_eq!(signal::always(1), []);
+}
+
+
+#[test]
+#[should_panic]
+fn test_assert_signal_eq_fail() {
+    assert_signal_eq!(signal::always(1), [
+        Poll::Ready(Some(1)),
+        Poll::Ready(Some(1)),
+    ]);
+}
+
+
+#[test]
+fn test_manual_executor() {
+    let mutable = Mutable::new(1);
+    let output = Rc::new(RefCell::new(vec![]));
+
+    let mut executor = ManualExecutor::new();
+    assert!(!executor.step());
+
+    executor.spawn(mutable.signal().map(|x| x + 1).for_each({
+        let output = output.clone();
+        move |x| {
+            output.borrow_mut().push(x);
+            ready(())
+        }
+    }));
+
+    assert_eq!(executor.len(), 1);
+    assert!(output.borrow().is_empty());
+
+    assert!(executor.step());
+    assert_eq!(*output.borrow(), vec![2]);
+    assert!(!executor.step());
+
+    mutable.set(5);
+    mutable.set(6);
+    assert_eq!(*output.borrow(), vec![2]);
+
+    assert_eq!(executor.run_until_stalled(), 1);
+    assert_eq!(*output.borrow(), vec![2, 7]);
+
+    drop(mutable);
+    assert_eq!(executor.run_until_stalled(), 1);
+    assert!(executor.is_empty());
+}
@@ -0,0 +1,213 @@
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::future::Future;
+use std::time::Duration;
+use std::task::Poll;
+use futures_util::future::poll_fn;
+use futures_signals::signal::{SignalExt, Mutable};
+use futures_signals::animation::MutableAnimation;
+use futures_signals::timer::{Timer, timestamps};
+
+mod util;
+
+
+#[derive(Clone)]
+struct TestTimer {
+    time: Arc<Mutex<f64>>,
+}
+
+impl TestTimer {
+    fn advance(&self, amount: f64) {
+        *self.time.lock().unwrap() += amount;
+    }
+}
+
+impl Timer for TestTimer {
+    type Sleep = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+    fn now(&self) -> f64 {
+        *self.time.lock().unwrap()
+    }
+
+    fn sleep(&self, duration: Duration) -> Self::Sleep {
+        let time = self.time.clone();
+        let end = self.now() + (duration.as_millis() as f64);
+
+        Box::pin(poll_fn(move |_| {
+            if *time.lock().unwrap() >= end {
+                Poll::Ready(())
+
+            } else {
+                Poll::Pending
+            }
+        }))
+    }
+}
+
+
+#[test]
+fn test_timestamps() {
+    let timer = TestTimer { time: Arc::new(Mutex::new(0.0)) };
+    let mut signal = timestamps(&timer, Duration::from_millis(10));
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(0.0)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        timer.advance(5.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        timer.advance(10.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(15.0)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+    });
+}
+
+
+#[test]
+fn test_animation() {
+    let timer = TestTimer { time: Arc::new(Mutex::new(0.0)) };
+    let animation = MutableAnimation::new(100.0);
+    let mut signal = animation.signal(timestamps(timer.clone(), Duration::from_millis(10)));
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(0.0)));
+
+        animation.animate_to(1.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        timer.advance(50.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(0.5)));
+    });
+}
+
+
+#[test]
+fn test_tween() {
+    let timer = TestTimer { time: Arc::new(Mutex::new(0.0)) };
+    let source = Mutable::new(0.0);
+    let mut signal = source.signal().tween(timer.clone(), Duration::from_millis(100), |from: &f64, to: &f64, t| from + (to - from) * t);
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(0.0)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        source.set(10.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(0.0)));
+
+        timer.advance(20.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(2.0)));
+
+        timer.advance(30.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(5.0)));
+
+        // It starts from the current output, on the next frame
+        source.set(1.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        timer.advance(25.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(5.0)));
+
+        timer.advance(25.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(4.0)));
+
+        timer.advance(400.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(1.0)));
+
+        timer.advance(100.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        drop(source);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[test]
+fn test_rate_limit() {
+    let timer = TestTimer { time: Arc::new(Mutex::new(0.0)) };
+    let source = Mutable::new(0);
+    let mut signal = source.signal().rate_limit(timer.clone(), 2, Duration::from_millis(100));
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(0)));
+
+        timer.advance(10.0);
+        source.set(1);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(1)));
+
+        source.set(2);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        source.set(3);
+        timer.advance(50.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        timer.advance(40.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(3)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        source.set(4);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        timer.advance(10.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(4)));
+    });
+}
+
+
+// A Timer whose sleeps finish halfway through
+#[derive(Clone)]
+struct EarlyTimer(TestTimer);
+
+impl Timer for EarlyTimer {
+    type Sleep = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+    fn now(&self) -> f64 {
+        self.0.now()
+    }
+
+    fn sleep(&self, duration: Duration) -> Self::Sleep {
+        self.0.sleep(duration / 2)
+    }
+}
+
+#[test]
+fn test_rate_limit_early_timer() {
+    let timer = TestTimer { time: Arc::new(Mutex::new(0.0)) };
+    let source = Mutable::new(0);
+    let mut signal = source.signal().rate_limit(EarlyTimer(timer.clone()), 1, Duration::from_millis(100));
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(0)));
+
+        source.set(1);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        // The sleep fires early, but the interval hasn't passed yet
+        timer.advance(50.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        timer.advance(25.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        timer.advance(25.0);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(1)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+    });
+}
+
+
+#[cfg(feature = "async-std")]
+#[test]
+fn test_async_std_timer() {
+    use futures_util::stream::StreamExt;
+    use futures_signals::timer::AsyncStdTimer;
+
+    let times: Vec<f64> = futures_executor::block_on(
+        timestamps(AsyncStdTimer::new(), Duration::from_millis(10)).to_stream().take(2).collect()
+    );
+
+    assert_eq!(times.len(), 2);
+    assert!(times[1] - times[0] >= 10.0);
+}
@@ -5,6 +5,8 @@ use std::sync::Arc;
 use std::pin::Pin;
 use std::task::{Poll, Context};
 use futures_signals::signal_vec::{VecDiff, SignalVec};
+use futures_signals::signal_map::{MapDiff, SignalMap};
+use futures_signals::signal_set::{SetDiff, SignalSet};
 use futures_signals::signal::Signal;
 use futures_util::future::poll_fn;
 use futures_util::task::{waker, ArcWake};
@@ -12,7 +14,7 @@ use futures_executor::block_on;
 use pin_utils::pin_mut;
 
 
-#[allow(dead_code)]
+#[allow(dead_code, clippy::type_complexity)]
 pub struct ForEachSignal<A> where A: Signal {
     signal: A,
     callbacks: Vec<Box<dyn FnMut(&mut Context, Poll<Option<A::Item>>)>>,
@@ -151,6 +153,26 @@ pub fn get_signal_vec_polls<A, F>(signal: A, f: F) -> Vec<Poll<Option<VecDiff<A:
 }
 
 
+#[allow(dead_code, clippy::type_complexity)]
+pub fn get_signal_map_polls<A, F>(signal: A, f: F) -> Vec<Poll<Option<MapDiff<A::Key, A::Value>>>>
+    where A: SignalMap,
+          F: FnOnce() {
+    pin_mut!(signal);
+    // TODO is the as_mut correct ?
+    get_polls(f, |cx| Pin::as_mut(&mut signal).poll_map_change(cx))
+}
+
+
+#[allow(dead_code)]
+pub fn get_signal_set_polls<A, F>(signal: A, f: F) -> Vec<Poll<Option<SetDiff<A::Item>>>>
+    where A: SignalSet,
+          F: FnOnce() {
+    pin_mut!(signal);
+    // TODO is the as_mut correct ?
+    get_polls(f, |cx| Pin::as_mut(&mut signal).poll_set_change(cx))
+}
+
+
 #[allow(dead_code)]
 pub fn get_all_polls<A, B, F>(signal: A, mut initial: B, mut f: F) -> Vec<Poll<Option<A::Item>>> where A: Signal, F: FnMut(&B, &mut Context) -> B {
     let mut output = vec![];
@@ -246,6 +268,17 @@ pub fn assert_signal_vec_eq<A, S>(signal: S, expected: Vec<Poll<Option<VecDiff<A
     );
 }
 
+#[allow(dead_code)]
+pub fn assert_signal_map_eq<K, V, S>(signal: S, expected: Vec<Poll<Option<MapDiff<K, V>>>>)
+    where K: std::fmt::Debug + PartialEq,
+          V: std::fmt::Debug + PartialEq,
+          S: SignalMap<Key = K, Value = V> {
+
+    assert_eq!(
+        get_signal_map_polls(signal, || {}),
+        expected,
+    );
+}
 
 
 #[allow(dead_code)]
@@ -263,6 +296,7 @@ impl<A> Source<A> {
         Self { changes }
     }
 
+    #[allow(clippy::len_zero)]
     fn poll(&mut self, cx: &mut Context) -> Poll<Option<A>> {
         if self.changes.len() > 0 {
             match self.changes.remove(0) {
@@ -296,3 +330,22 @@ impl<A> SignalVec for Source<VecDiff<A>> {
         self.poll(cx)
     }
 }
+
+impl<K, V> SignalMap for Source<MapDiff<K, V>> {
+    type Key = K;
+    type Value = V;
+
+    #[inline]
+    fn poll_map_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<MapDiff<Self::Key, Self::Value>>> {
+        self.poll(cx)
+    }
+}
+
+impl<A> SignalSet for Source<SetDiff<A>> {
+    type Item = A;
+
+    #[inline]
+    fn poll_set_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<SetDiff<Self::Item>>> {
+        self.poll(cx)
+    }
+}
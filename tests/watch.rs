@@ -0,0 +1,73 @@
+#![cfg(feature = "tokio")]
+
+use std::rc::Rc;
+use std::task::Poll;
+use tokio::sync::watch;
+use futures_signals::signal::{SignalExt, Mutable, from_watch, to_watch, to_watch_local};
+
+mod util;
+
+
+#[test]
+fn test_from_watch() {
+    let (sender, receiver) = watch::channel(1);
+
+    let mut signal = from_watch(receiver).map(|x| x * 10);
+
+    util::with_noop_context(|cx| {
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(10)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        sender.send(2).unwrap();
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(Some(20)));
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Pending);
+
+        drop(sender);
+        assert_eq!(signal.poll_change_unpin(cx), Poll::Ready(None));
+    });
+}
+
+
+#[test]
+fn test_to_watch() {
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+
+    let mutable = Mutable::new(1);
+    let mut receiver = to_watch(mutable.signal(), runtime.handle());
+
+    runtime.block_on(async {
+        receiver.changed().await.unwrap();
+        assert_eq!(*receiver.borrow_and_update(), Some(1));
+
+        mutable.set(2);
+        receiver.changed().await.unwrap();
+        assert_eq!(*receiver.borrow_and_update(), Some(2));
+
+        drop(mutable);
+        assert!(receiver.changed().await.is_err());
+    });
+}
+
+
+#[test]
+fn test_to_watch_local() {
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let local = tokio::task::LocalSet::new();
+
+    // Rc is not Send
+    let mutable = Mutable::new(Rc::new(1));
+
+    local.block_on(&runtime, async {
+        let mut receiver = to_watch_local(mutable.signal_cloned().map(|x| *x));
+
+        receiver.changed().await.unwrap();
+        assert_eq!(*receiver.borrow_and_update(), Some(1));
+
+        mutable.set(Rc::new(2));
+        receiver.changed().await.unwrap();
+        assert_eq!(*receiver.borrow_and_update(), Some(2));
+
+        drop(mutable);
+        assert!(receiver.changed().await.is_err());
+    });
+}